@@ -1,5 +1,6 @@
 use crate::all_storages::AllStorages;
-use crate::atomic_refcell::{ARef, ARefMut};
+use crate::atomic_refcell::{ARef, ARefMut, AtomicRefCell};
+use crate::entities::Entities;
 use crate::error;
 use crate::storage::{SBox, Storage, StorageId};
 use alloc::vec::Vec;
@@ -209,17 +210,19 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&StorageId::of::<S>());
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow();
             drop(storages);
             match storage {
                 Ok(storage) => Ok(ARef::map(storage, |storage| {
                     storage.as_any().downcast_ref().unwrap()
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             Err(error::GetStorage::MissingStorage {
@@ -235,13 +238,10 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow();
             drop(storages);
-            storage.map_err(|err| error::GetStorage::StorageBorrow {
-                name: None,
-                id: storage_id,
-                borrow: err,
-            })
+            storage.map_err(|err| storage_borrow(None, storage_id, err, cell))
         } else {
             Err(error::GetStorage::MissingStorage {
                 name: None,
@@ -254,17 +254,19 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&StorageId::of::<S>());
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow_mut();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow_mut();
             drop(storages);
             match storage {
                 Ok(storage) => Ok(ARefMut::map(storage, |storage| {
-                    storage.as_any_mut().downcast_mut().unwrap()
+                    downcast_storage_mut(storage)
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             Err(error::GetStorage::MissingStorage {
@@ -280,13 +282,10 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow_mut();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow_mut();
             drop(storages);
-            storage.map_err(|err| error::GetStorage::StorageBorrow {
-                name: None,
-                id: storage_id,
-                borrow: err,
-            })
+            storage.map_err(|err| storage_borrow(None, storage_id, err, cell))
         } else {
             Err(error::GetStorage::MissingStorage {
                 name: None,
@@ -314,33 +313,32 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow();
             drop(storages);
             match storage {
                 Ok(storage) => Ok(ARef::map(storage, |storage| {
                     storage.as_any().downcast_ref().unwrap()
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             drop(storages);
             let mut storages = self.storages.write();
 
-            let storage = unsafe {
+            let cell = unsafe {
                 &*storages
                     .entry(storage_id)
-                    .or_insert_with(|| SBox::new(f()))
+                    .or_insert_with(|| SBox::new(f()).watched_by(&self.wait_state))
                     .0
-            }
-            .borrow()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            };
+            let storage = cell.borrow().map_err(|err| {
+                storage_borrow(Some(type_name::<S>()), StorageId::of::<S>(), err, cell)
             });
 
             Ok(ARef::map(storage?, |storage| {
@@ -373,17 +371,19 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow();
 
             match storage {
                 Ok(storage) => Ok(ARef::map(storage, |storage| {
                     storage.as_any().downcast_ref().unwrap()
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             if (self.thread_id_generator)() != self.main_thread_id {
@@ -391,23 +391,25 @@ impl CustomStorageAccess for AllStorages {
                     name: Some(type_name::<S>()),
                     id: StorageId::of::<S>(),
                     borrow: error::Borrow::WrongThread,
+                    #[cfg(feature = "borrow_diagnostics")]
+                    holders: Vec::new(),
                 });
             }
 
             drop(storages);
             let mut storages = self.storages.write();
 
-            let storage = unsafe {
+            let cell = unsafe {
                 &*storages
                     .entry(storage_id)
-                    .or_insert_with(|| SBox::new_non_send(f(), self.thread_id_generator.clone()))
+                    .or_insert_with(|| {
+                        SBox::new_non_send(f(), self.thread_id_generator.clone())
+                            .watched_by(&self.wait_state)
+                    })
                     .0
-            }
-            .borrow()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            };
+            let storage = cell.borrow().map_err(|err| {
+                storage_borrow(Some(type_name::<S>()), StorageId::of::<S>(), err, cell)
             });
 
             Ok(ARef::map(storage?, |storage| {
@@ -440,33 +442,32 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow();
 
             match storage {
                 Ok(storage) => Ok(ARef::map(storage, |storage| {
                     storage.as_any().downcast_ref().unwrap()
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             drop(storages);
             let mut storages = self.storages.write();
 
-            let storage = unsafe {
+            let cell = unsafe {
                 &*storages
                     .entry(storage_id)
-                    .or_insert_with(|| SBox::new_non_sync(f()))
+                    .or_insert_with(|| SBox::new_non_sync(f()).watched_by(&self.wait_state))
                     .0
-            }
-            .borrow()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            };
+            let storage = cell.borrow().map_err(|err| {
+                storage_borrow(Some(type_name::<S>()), StorageId::of::<S>(), err, cell)
             });
 
             Ok(ARef::map(storage?, |storage| {
@@ -499,17 +500,19 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow();
 
             match storage {
                 Ok(storage) => Ok(ARef::map(storage, |storage| {
                     storage.as_any().downcast_ref().unwrap()
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             if (self.thread_id_generator)() != self.main_thread_id {
@@ -517,25 +520,25 @@ impl CustomStorageAccess for AllStorages {
                     name: Some(type_name::<S>()),
                     id: StorageId::of::<S>(),
                     borrow: error::Borrow::WrongThread,
+                    #[cfg(feature = "borrow_diagnostics")]
+                    holders: Vec::new(),
                 });
             }
 
             drop(storages);
             let mut storages = self.storages.write();
 
-            let storage = unsafe {
+            let cell = unsafe {
                 &*storages
                     .entry(storage_id)
                     .or_insert_with(|| {
                         SBox::new_non_send_sync(f(), self.thread_id_generator.clone())
+                            .watched_by(&self.wait_state)
                     })
                     .0
-            }
-            .borrow()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            };
+            let storage = cell.borrow().map_err(|err| {
+                storage_borrow(Some(type_name::<S>()), StorageId::of::<S>(), err, cell)
             });
 
             Ok(ARef::map(storage?, |storage| {
@@ -566,33 +569,32 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow_mut();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow_mut();
             drop(storages);
             match storage {
                 Ok(storage) => Ok(ARefMut::map(storage, |storage| {
-                    storage.as_any_mut().downcast_mut().unwrap()
+                    downcast_storage_mut(storage)
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             drop(storages);
             let mut storages = self.storages.write();
 
-            let storage = unsafe {
+            let cell = unsafe {
                 &*storages
                     .entry(storage_id)
-                    .or_insert_with(|| SBox::new(f()))
+                    .or_insert_with(|| SBox::new(f()).watched_by(&self.wait_state))
                     .0
-            }
-            .borrow_mut()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            };
+            let storage = cell.borrow_mut().map_err(|err| {
+                storage_borrow(Some(type_name::<S>()), StorageId::of::<S>(), err, cell)
             });
 
             Ok(ARefMut::map(storage?, |storage| {
@@ -625,17 +627,19 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow_mut();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow_mut();
 
             match storage {
                 Ok(storage) => Ok(ARefMut::map(storage, |storage| {
-                    storage.as_any_mut().downcast_mut().unwrap()
+                    downcast_storage_mut(storage)
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             if (self.thread_id_generator)() != self.main_thread_id {
@@ -643,23 +647,25 @@ impl CustomStorageAccess for AllStorages {
                     name: Some(type_name::<S>()),
                     id: StorageId::of::<S>(),
                     borrow: error::Borrow::WrongThread,
+                    #[cfg(feature = "borrow_diagnostics")]
+                    holders: Vec::new(),
                 });
             }
 
             drop(storages);
             let mut storages = self.storages.write();
 
-            let storage = unsafe {
+            let cell = unsafe {
                 &*storages
                     .entry(storage_id)
-                    .or_insert_with(|| SBox::new_non_send(f(), self.thread_id_generator.clone()))
+                    .or_insert_with(|| {
+                        SBox::new_non_send(f(), self.thread_id_generator.clone())
+                            .watched_by(&self.wait_state)
+                    })
                     .0
-            }
-            .borrow_mut()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            };
+            let storage = cell.borrow_mut().map_err(|err| {
+                storage_borrow(Some(type_name::<S>()), StorageId::of::<S>(), err, cell)
             });
 
             Ok(ARefMut::map(storage?, |storage| {
@@ -692,33 +698,32 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow_mut();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow_mut();
 
             match storage {
                 Ok(storage) => Ok(ARefMut::map(storage, |storage| {
-                    storage.as_any_mut().downcast_mut().unwrap()
+                    downcast_storage_mut(storage)
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             drop(storages);
             let mut storages = self.storages.write();
 
-            let storage = unsafe {
+            let cell = unsafe {
                 &*storages
                     .entry(storage_id)
-                    .or_insert_with(|| SBox::new_non_sync(f()))
+                    .or_insert_with(|| SBox::new_non_sync(f()).watched_by(&self.wait_state))
                     .0
-            }
-            .borrow_mut()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            };
+            let storage = cell.borrow_mut().map_err(|err| {
+                storage_borrow(Some(type_name::<S>()), StorageId::of::<S>(), err, cell)
             });
 
             Ok(ARefMut::map(storage?, |storage| {
@@ -751,17 +756,19 @@ impl CustomStorageAccess for AllStorages {
         let storages = self.storages.read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
-            let storage = unsafe { &*storage.0 }.borrow_mut();
+            let cell = unsafe { &*storage.0 };
+            let storage = cell.borrow_mut();
 
             match storage {
                 Ok(storage) => Ok(ARefMut::map(storage, |storage| {
-                    storage.as_any_mut().downcast_mut().unwrap()
+                    downcast_storage_mut(storage)
                 })),
-                Err(err) => Err(error::GetStorage::StorageBorrow {
-                    name: Some(type_name::<S>()),
-                    id: StorageId::of::<S>(),
-                    borrow: err,
-                }),
+                Err(err) => Err(storage_borrow(
+                    Some(type_name::<S>()),
+                    StorageId::of::<S>(),
+                    err,
+                    cell,
+                )),
             }
         } else {
             if (self.thread_id_generator)() != self.main_thread_id {
@@ -769,25 +776,25 @@ impl CustomStorageAccess for AllStorages {
                     name: Some(type_name::<S>()),
                     id: StorageId::of::<S>(),
                     borrow: error::Borrow::WrongThread,
+                    #[cfg(feature = "borrow_diagnostics")]
+                    holders: Vec::new(),
                 });
             }
 
             drop(storages);
             let mut storages = self.storages.write();
 
-            let storage = unsafe {
+            let cell = unsafe {
                 &*storages
                     .entry(storage_id)
                     .or_insert_with(|| {
                         SBox::new_non_send_sync(f(), self.thread_id_generator.clone())
+                            .watched_by(&self.wait_state)
                     })
                     .0
-            }
-            .borrow_mut()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            };
+            let storage = cell.borrow_mut().map_err(|err| {
+                storage_borrow(Some(type_name::<S>()), StorageId::of::<S>(), err, cell)
             });
 
             Ok(ARefMut::map(storage?, |storage| {
@@ -799,15 +806,45 @@ impl CustomStorageAccess for AllStorages {
         self.storages
             .read()
             .iter()
-            .flat_map(|(storage_id, storage)| unsafe {
-                (*storage.0)
-                    .borrow()
-                    .map_err(|err| error::GetStorage::StorageBorrow {
-                        name: None,
-                        id: *storage_id,
-                        borrow: err,
-                    })
+            .flat_map(|(storage_id, storage)| {
+                let cell = unsafe { &*storage.0 };
+
+                cell.borrow()
+                    .map_err(|err| storage_borrow(None, *storage_id, err, cell))
             })
             .collect()
     }
 }
+
+/// Downcasts an exclusively borrowed storage, reserved entities are made alive on exclusive access.
+fn downcast_storage_mut<S: 'static>(storage: &mut dyn Storage) -> &mut S {
+    let storage = storage.as_any_mut();
+
+    if let Some(entities) = storage.downcast_mut::<Entities>() {
+        entities.materialize_reserved();
+    }
+
+    storage.downcast_mut().unwrap()
+}
+
+/// Returns a [`StorageBorrow`](error::GetStorage::StorageBorrow) error.\
+/// With `borrow_diagnostics`, the holders of `cell` are recorded for [`error::GetStorage::holders`].
+#[cfg_attr(not(feature = "borrow_diagnostics"), allow(unused_variables))]
+fn storage_borrow(
+    name: Option<&'static str>,
+    id: StorageId,
+    borrow: error::Borrow,
+    cell: &AtomicRefCell<dyn Storage>,
+) -> error::GetStorage {
+    error::GetStorage::StorageBorrow {
+        name,
+        id,
+        borrow,
+        #[cfg(feature = "borrow_diagnostics")]
+        holders: if matches!(borrow, error::Borrow::Unique | error::Borrow::Shared) {
+            cell.holders()
+        } else {
+            Vec::new()
+        },
+    }
+}
@@ -6,9 +6,13 @@ pub use custom_storage::CustomStorageAccess;
 pub use delete_any::{CustomDeleteAny, TupleDeleteAny};
 pub use retain::TupleRetainStorage;
 
-use crate::atomic_refcell::{ARef, ARefMut, AtomicRefCell};
+#[cfg(feature = "borrow_diagnostics")]
+use crate::atomic_refcell::BorrowHolder;
+use crate::atomic_refcell::{ARef, ARefMut, AtomicRefCell, WaitState};
 use crate::borrow::Borrow;
 use crate::component::{Component, Unique};
+#[cfg(feature = "serde1")]
+use crate::delta::DeltaComponents;
 use crate::entities::Entities;
 use crate::entity_id::EntityId;
 use crate::get_component::GetComponent;
@@ -17,6 +21,7 @@ use crate::memory_usage::AllStoragesMemoryUsage;
 use crate::public_transport::RwLock;
 use crate::public_transport::ShipyardRwLock;
 use crate::r#mut::Mut;
+use crate::relation::{Relation, Relations};
 use crate::reserve::BulkEntityIter;
 use crate::sparse_set::{BulkAddEntity, SparseSet, TupleAddComponent, TupleDelete, TupleRemove};
 #[cfg(feature = "std")]
@@ -29,6 +34,7 @@ use crate::views::EntitiesViewMut;
 use crate::{error, ShipHashMap};
 use alloc::boxed::Box;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 use core::any::type_name;
 use core::hash::BuildHasherDefault;
 use core::marker::PhantomData;
@@ -102,10 +108,17 @@ impl<Lock, ThreadId> AllStoragesBuilder<Lock, ThreadId> {
 }
 
 impl AllStoragesBuilder<LockPresent, ThreadIdPresent> {
-    pub(crate) fn build(self, counter: Arc<AtomicU32>) -> AtomicRefCell<AllStorages> {
+    pub(crate) fn build(
+        self,
+        counter: Arc<AtomicU32>,
+        wait_state: Arc<WaitState>,
+    ) -> AtomicRefCell<AllStorages> {
         let mut storages = ShipHashMap::with_hasher(BuildHasherDefault::default());
 
-        storages.insert(StorageId::of::<Entities>(), SBox::new(Entities::new()));
+        storages.insert(
+            StorageId::of::<Entities>(),
+            SBox::new(Entities::new()).watched_by(&wait_state),
+        );
 
         let storages = if let Some(custom_lock) = self.custom_lock {
             RwLock::new_custom(custom_lock, storages)
@@ -127,19 +140,23 @@ impl AllStoragesBuilder<LockPresent, ThreadIdPresent> {
 
         #[cfg(feature = "thread_local")]
         {
-            AtomicRefCell::new_non_send(
-                AllStorages {
-                    storages,
-                    main_thread_id,
-                    thread_id_generator: thread_id_generator.clone(),
-                    counter,
-                },
-                thread_id_generator,
-            )
+            AllStorages {
+                storages,
+                main_thread_id,
+                thread_id_generator: thread_id_generator.clone(),
+                counter,
+                wait_state,
+            }
+            .into_cell(thread_id_generator)
         }
         #[cfg(not(feature = "thread_local"))]
         {
-            AtomicRefCell::new(AllStorages { storages, counter })
+            AllStorages {
+                storages,
+                counter,
+                wait_state,
+            }
+            .into_cell()
         }
     }
 }
@@ -159,6 +176,8 @@ pub struct AllStorages {
     #[cfg(feature = "thread_local")]
     thread_id_generator: Arc<dyn Fn() -> u64 + Send + Sync>,
     counter: Arc<AtomicU32>,
+    /// Threads blocked on a borrow of this `World`.
+    wait_state: Arc<WaitState>,
 }
 
 #[cfg(not(feature = "thread_local"))]
@@ -168,20 +187,58 @@ unsafe impl Sync for AllStorages {}
 
 impl AllStorages {
     #[cfg(feature = "std")]
-    pub(crate) fn new(counter: Arc<AtomicU32>) -> Self {
+    pub(crate) fn new(counter: Arc<AtomicU32>, wait_state: Arc<WaitState>) -> AtomicRefCell<Self> {
         let mut storages = ShipHashMap::with_hasher(BuildHasherDefault::default());
 
-        storages.insert(StorageId::of::<Entities>(), SBox::new(Entities::new()));
+        storages.insert(
+            StorageId::of::<Entities>(),
+            SBox::new(Entities::new()).watched_by(&wait_state),
+        );
 
-        AllStorages {
+        let all_storages = AllStorages {
             storages: RwLock::new_std(storages),
             #[cfg(feature = "thread_local")]
             main_thread_id: (std_thread_id_generator)(),
             #[cfg(feature = "thread_local")]
             thread_id_generator: Arc::new(std_thread_id_generator),
             counter,
+            wait_state,
+        };
+
+        #[cfg(not(feature = "thread_local"))]
+        {
+            all_storages.into_cell()
+        }
+        #[cfg(feature = "thread_local")]
+        {
+            all_storages.into_cell(Arc::new(std_thread_id_generator))
         }
     }
+    /// Puts `self` in the `World`'s cell, releasing it wakes up blocking borrows.
+    fn into_cell(
+        self,
+        #[cfg(feature = "thread_local")] thread_id_generator: Arc<dyn Fn() -> u64 + Send + Sync>,
+    ) -> AtomicRefCell<Self> {
+        let wait_state = self.wait_state.clone();
+
+        #[cfg(not(feature = "thread_local"))]
+        let mut cell = AtomicRefCell::new(self);
+        #[cfg(feature = "thread_local")]
+        let mut cell = AtomicRefCell::new_non_send(self, thread_id_generator);
+
+        cell.set_wait_state(wait_state);
+
+        cell
+    }
+    /// Inserts `storage`, replacing the storage of the same type if there is one.\
+    /// Used to put back a storage deserialized on its own, like a [`SparseSet`] or [`Entities`].
+    ///
+    /// The entities of a [`SparseSet`] are not added to [`Entities`].
+    pub fn insert_storage<S: 'static + Storage + Send + Sync>(&mut self, storage: S) {
+        self.storages
+            .get_mut()
+            .insert(StorageId::of::<S>(), SBox::new(storage).watched_by(&self.wait_state));
+    }
     /// Adds a new unique storage, unique storages store exactly one `T` at any time.  
     /// To access a unique storage value, use [`UniqueView`] or [`UniqueViewMut`].  
     ///
@@ -207,10 +264,13 @@ impl AllStorages {
         self.storages
             .write()
             .entry(storage_id)
-            .insert(SBox::new(UniqueStorage::new(
-                component,
-                self.get_tracking_timestamp(),
-            )));
+            .insert(
+                SBox::new(UniqueStorage::new(
+                    component,
+                    self.get_tracking_timestamp(),
+                ))
+                .watched_by(&self.wait_state),
+            );
     }
     /// Adds a new unique storage, unique storages store exactly one `T` at any time.  
     /// To access a unique storage value, use [NonSend] and [UniqueViewMut] or [UniqueViewMut].  
@@ -229,6 +289,7 @@ impl AllStorages {
                     UniqueStorage::new(component, self.get_tracking_timestamp()),
                     self.thread_id_generator.clone(),
                 )
+                .watched_by(&self.wait_state)
             });
         }
     }
@@ -245,6 +306,7 @@ impl AllStorages {
 
         self.storages.write().entry(storage_id).or_insert_with(|| {
             SBox::new_non_sync(UniqueStorage::new(component, self.get_tracking_timestamp()))
+                .watched_by(&self.wait_state)
         });
     }
     /// Adds a new unique storage, unique storages store exactly one `T` at any time.  
@@ -264,6 +326,7 @@ impl AllStorages {
                     UniqueStorage::new(component, self.get_tracking_timestamp()),
                     self.thread_id_generator.clone(),
                 )
+                .watched_by(&self.wait_state)
             });
         }
     }
@@ -322,6 +385,8 @@ impl AllStorages {
     /// Delete an entity and all its components.
     /// Returns `true` if `entity` was alive.
     ///
+    /// Entities related to `entity` with a [`CleanupPolicy::DeleteSource`](crate::CleanupPolicy::DeleteSource) relation are deleted too.
+    ///
     /// ### Example
     ///
     /// ```
@@ -355,13 +420,35 @@ impl AllStorages {
         if entities.delete_unchecked(entity) {
             drop(entities);
 
-            self.strip(entity);
+            let current = self.get_current();
+            let mut cascade = Vec::new();
+
+            self.delete_from_storages(entity, current, &mut cascade);
+
+            // storages like `Relations` can require other entities to be deleted
+            while let Some(entity) = cascade.pop() {
+                if self.entities_mut().unwrap().delete_unchecked(entity) {
+                    self.delete_from_storages(entity, current, &mut cascade);
+                }
+            }
 
             true
         } else {
             false
         }
     }
+    fn delete_from_storages(
+        &mut self,
+        entity: EntityId,
+        current: TrackingTimestamp,
+        cascade: &mut Vec<EntityId>,
+    ) {
+        for storage in self.storages.get_mut().values_mut() {
+            unsafe { &mut *storage.0 }
+                .get_mut()
+                .delete_entity(entity, current, cascade);
+        }
+    }
     /// Deletes all components from an entity without deleting it.
     ///
     /// ### Example
@@ -525,7 +612,7 @@ impl AllStorages {
     pub fn add_entity<T: TupleAddComponent>(&mut self, component: T) -> EntityId {
         let current = self.get_current();
 
-        let entity = self.exclusive_storage_mut::<Entities>().unwrap().generate();
+        let entity = self.exclusive_entities_mut().generate();
         component.add_component(self, entity, current);
 
         entity
@@ -587,16 +674,38 @@ impl AllStorages {
     pub fn add_component<T: TupleAddComponent>(&mut self, entity: EntityId, component: T) {
         let current = self.get_current();
 
-        if self
-            .exclusive_storage_mut::<Entities>()
-            .unwrap()
-            .is_alive(entity)
-        {
+        if self.exclusive_entities_mut().is_alive(entity) {
             component.add_component(self, entity, current);
         } else {
             panic!("{:?}", error::AddComponent::EntityIsNotAlive);
         }
     }
+    /// Adds a relation of kind `R` from `source` to `target`.\
+    /// Returns `false` if the relation was already present.
+    ///
+    /// ### Panics
+    ///
+    /// - `source` or `target` is not alive.
+    #[track_caller]
+    pub fn add_relation<R: Relation>(&mut self, source: EntityId, target: EntityId) -> bool {
+        let entities = self.exclusive_entities_mut();
+
+        if entities.is_alive(source) && entities.is_alive(target) {
+            self.exclusive_storage_or_insert_mut(
+                StorageId::of::<Relations<R>>(),
+                Relations::<R>::new,
+            )
+            .insert(source, target)
+        } else {
+            panic!("{:?}", error::AddComponent::EntityIsNotAlive);
+        }
+    }
+    /// Removes the relation of kind `R` from `source` to `target`.\
+    /// Returns `false` if the relation wasn't present.
+    pub fn remove_relation<R: Relation>(&mut self, source: EntityId, target: EntityId) -> bool {
+        self.exclusive_storage_mut::<Relations<R>>()
+            .is_ok_and(|relations| relations.remove(source, target))
+    }
     /// Deletes components from an entity. As opposed to `remove`, `delete` doesn't return anything.  
     /// `C` must always be a tuple, even for a single component.
     ///
@@ -749,7 +858,11 @@ let (entities, mut usizes) = all_storages
     #[cfg_attr(feature = "thread_local", doc = "[NonSend]: crate::NonSend")]
     #[cfg_attr(feature = "thread_local", doc = "[NonSync]: crate::NonSync")]
     #[cfg_attr(feature = "thread_local", doc = "[NonSendSync]: crate::NonSendSync")]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub fn borrow<V: Borrow>(&self) -> Result<V::View<'_>, error::GetStorage> {
+        #[cfg(feature = "borrow_diagnostics")]
+        let _holder = BorrowHolder::enter_location();
+
         let current = self.get_current();
 
         V::borrow(self, None, None, current)
@@ -847,6 +960,8 @@ You can use:
         let system_span = tracing::info_span!("system", name = ?type_name::<S>());
         #[cfg(feature = "tracing")]
         let _system_span = system_span.enter();
+        #[cfg(feature = "borrow_diagnostics")]
+        let _holder = BorrowHolder::enter_location();
 
         system
             .run((data,), self)
@@ -968,6 +1083,8 @@ let i = all_storages.run(sys1);
         let system_span = tracing::info_span!("system", name = ?type_name::<S>());
         #[cfg(feature = "tracing")]
         let _system_span = system_span.enter();
+        #[cfg(feature = "borrow_diagnostics")]
+        let _holder = BorrowHolder::enter_location();
 
         system
             .run((), self)
@@ -1030,11 +1147,19 @@ let i = all_storages.run(sys1);
         drop(storages);
         match storage {
             Ok(storage) => Ok(ARefMut::map(storage, |storage| {
-                storage.as_any_mut().downcast_mut().unwrap()
+                let entities: &mut Entities = storage.as_any_mut().downcast_mut().unwrap();
+                entities.materialize_reserved();
+                entities
             })),
             Err(err) => Err(error::GetStorage::Entities(err)),
         }
     }
+    /// Returns `Entities` with all reserved ids made alive.
+    pub(crate) fn exclusive_entities_mut(&mut self) -> &mut Entities {
+        let entities = self.exclusive_storage_mut::<Entities>().unwrap();
+        entities.materialize_reserved();
+        entities
+    }
     pub(crate) fn exclusive_storage_mut<T: 'static>(
         &mut self,
     ) -> Result<&mut T, error::GetStorage> {
@@ -1073,7 +1198,7 @@ let i = all_storages.run(sys1);
         unsafe {
             &mut *storages
                 .entry(storage_id)
-                .or_insert_with(|| SBox::new(f()))
+                .or_insert_with(|| SBox::new(f()).watched_by(&self.wait_state))
                 .0
         }
         .get_mut()
@@ -1097,7 +1222,10 @@ let i = all_storages.run(sys1);
         unsafe {
             &mut *storages
                 .entry(storage_id)
-                .or_insert_with(|| SBox::new_non_send(f(), self.thread_id_generator.clone()))
+                .or_insert_with(|| {
+                    SBox::new_non_send(f(), self.thread_id_generator.clone())
+                        .watched_by(&self.wait_state)
+                })
                 .0
         }
         .get_mut()
@@ -1120,7 +1248,7 @@ let i = all_storages.run(sys1);
         unsafe {
             &mut *storages
                 .entry(storage_id)
-                .or_insert_with(|| SBox::new_non_sync(f()))
+                .or_insert_with(|| SBox::new_non_sync(f()).watched_by(&self.wait_state))
                 .0
         }
         .get_mut()
@@ -1144,7 +1272,10 @@ let i = all_storages.run(sys1);
         unsafe {
             &mut *storages
                 .entry(storage_id)
-                .or_insert_with(|| SBox::new_non_send_sync(f(), self.thread_id_generator.clone()))
+                .or_insert_with(|| {
+                    SBox::new_non_send_sync(f(), self.thread_id_generator.clone())
+                        .watched_by(&self.wait_state)
+                })
                 .0
         }
         .get_mut()
@@ -1157,9 +1288,23 @@ let i = all_storages.run(sys1);
     /// Returns `true` if the entity is successfully spawned.
     #[inline]
     pub fn spawn(&mut self, entity: EntityId) -> bool {
-        self.exclusive_storage_mut::<Entities>()
-            .unwrap()
-            .spawn(entity)
+        self.exclusive_entities_mut().spawn(entity)
+    }
+    /// Like [`AllStorages::spawn`] but an older entity alive at the same index is deleted first,
+    /// its components don't end up attached to `entity`.
+    #[cfg(feature = "serde1")]
+    pub(crate) fn spawn_over_older(&mut self, entity: EntityId) -> bool {
+        if let Some(old_entity) = self.exclusive_entities_mut().older_alive(entity) {
+            self.delete_entity(old_entity);
+        }
+
+        self.spawn(entity)
+    }
+    /// Make the given entity alive, its index has to be outside of the local range.\
+    /// See [`Entities::try_spawn`].
+    #[inline]
+    pub fn try_spawn(&mut self, entity: EntityId) -> Result<(), error::Spawn> {
+        self.exclusive_entities_mut().try_spawn(entity)
     }
     /// Displays storages memory information.
     pub fn memory_usage(&self) -> AllStoragesMemoryUsage<'_> {
@@ -1179,6 +1324,30 @@ let i = all_storages.run(sys1);
         TrackingTimestamp::new(self.counter.load(core::sync::atomic::Ordering::Acquire))
     }
 
+    /// Collects the changes made to `C` components since `last`.\
+    /// Returns the timestamp to use as `last` for the next delta.
+    ///
+    /// See [`World::delta`](crate::World::delta).
+    #[cfg(feature = "serde1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde1")))]
+    pub fn delta<C: DeltaComponents>(
+        &self,
+        last: TrackingTimestamp,
+    ) -> Result<(C::Delta, TrackingTimestamp), error::GetStorage> {
+        let current = self.get_current();
+
+        Ok((C::delta(self, last, current)?, current))
+    }
+
+    /// Applies changes collected by [`AllStorages::delta`].
+    ///
+    /// See [`World::apply_delta`](crate::World::apply_delta).
+    #[cfg(feature = "serde1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde1")))]
+    pub fn apply_delta<C: DeltaComponents>(&mut self, delta: C::Delta) {
+        C::apply(self, delta);
+    }
+
     /// Enable insertion tracking for the given components.
     pub fn track_insertion<T: TupleTrack>(&mut self) -> &mut AllStorages {
         T::track_insertion(self);
@@ -1374,9 +1543,7 @@ for (i, j) in &mut iter {
 
     /// Returns true if entity matches a living entity.
     pub fn is_entity_alive(&mut self, entity: EntityId) -> bool {
-        self.exclusive_storage_mut::<Entities>()
-            .unwrap()
-            .is_alive(entity)
+        self.exclusive_entities_mut().is_alive(entity)
     }
 
     /// Moves an entity from a `World` to another.
@@ -1409,11 +1576,7 @@ for (i, j) in &mut iter {
         let current = self.get_current();
         let other_current = other.get_current();
 
-        if !self
-            .exclusive_storage_mut::<Entities>()
-            .unwrap()
-            .delete_unchecked(entity)
-        {
+        if !self.exclusive_entities_mut().delete_unchecked(entity) {
             panic!(
                 "Entity {:?} has to be alive to move it to another World.",
                 entity
@@ -1421,10 +1584,7 @@ for (i, j) in &mut iter {
         };
 
         assert!(
-            other
-                .exclusive_storage_mut::<Entities>()
-                .unwrap()
-                .spawn(entity),
+            other.exclusive_entities_mut().spawn(entity),
             "Other World already has an entity at {:?}'s index.",
             entity
         );
@@ -1471,22 +1631,14 @@ for (i, j) in &mut iter {
         let current = self.get_current();
         let other_current = other.get_current();
 
-        if !self
-            .exclusive_storage_mut::<Entities>()
-            .unwrap()
-            .is_alive(from)
-        {
+        if !self.exclusive_entities_mut().is_alive(from) {
             panic!(
                 "Entity {:?} has to be alive to move its components to another World.",
                 from
             );
         };
 
-        if !other
-            .exclusive_storage_mut::<Entities>()
-            .unwrap()
-            .is_alive(to)
-        {
+        if !other.exclusive_entities_mut().is_alive(to) {
             panic!(
                 "Entity {:?} has to be alive to receive components from another World.",
                 to
@@ -1,10 +1,24 @@
 mod borrow_state;
+#[cfg(feature = "borrow_diagnostics")]
+mod holder;
+#[cfg(feature = "std")]
+mod wait;
 
 pub use borrow_state::{ExclusiveBorrow, SharedBorrow};
+#[cfg(feature = "borrow_diagnostics")]
+pub use holder::BorrowHolder;
+#[cfg(feature = "std")]
+pub(crate) use wait::{retry_blocking, WaitState};
+
+/// Borrows can't block without `std`, there is nothing to wake up.
+#[cfg(not(feature = "std"))]
+#[derive(Default)]
+pub(crate) struct WaitState;
 
 use crate::error;
-#[cfg(feature = "thread_local")]
 use alloc::sync::Arc;
+#[cfg(feature = "borrow_diagnostics")]
+use alloc::vec::Vec;
 use borrow_state::BorrowState;
 use core::cell::UnsafeCell;
 use core::marker::PhantomData;
@@ -112,12 +126,20 @@ impl<T> AtomicRefCell<T> {
 }
 
 impl<T: ?Sized> AtomicRefCell<T> {
+    /// Releasing a borrow of this cell will wake up the threads waiting on `wait_state`.
+    pub(crate) fn set_wait_state(&mut self, wait_state: Arc<WaitState>) {
+        #[cfg(feature = "std")]
+        self.borrow_state.set_wait_state(wait_state);
+        #[cfg(not(feature = "std"))]
+        let _ = wait_state;
+    }
     /// Immutably borrows the wrapped value, returning an error if the value is currently mutably
     /// borrowed.
     ///
     /// The borrow lasts until the returned `Ref` exits scope. Multiple shared borrows can be
     /// taken out at the same time.
     #[inline]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub(crate) fn borrow(&self) -> Result<ARef<'_, &'_ T>, error::Borrow> {
         #[cfg(not(feature = "thread_local"))]
         {
@@ -183,6 +205,7 @@ impl<T: ?Sized> AtomicRefCell<T> {
     /// The borrow lasts until the returned `RefMut` exits scope. The value cannot be borrowed while this borrow is
     /// active.
     #[inline]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub(crate) fn borrow_mut(&self) -> Result<ARefMut<'_, &'_ mut T>, error::Borrow> {
         #[cfg(feature = "thread_local")]
         {
@@ -222,6 +245,11 @@ impl<T: ?Sized> AtomicRefCell<T> {
 
         self.inner.get_mut()
     }
+    /// Returns the holders of the active borrows.
+    #[cfg(feature = "borrow_diagnostics")]
+    pub(crate) fn holders(&self) -> Vec<BorrowHolder> {
+        self.borrow_state.holders()
+    }
 }
 
 /// Wraps an `AtomicRefcell`'s shared borrow.
@@ -1,18 +1,39 @@
+#[cfg(feature = "borrow_diagnostics")]
+use super::holder::{BorrowHolder, Holders};
+#[cfg(feature = "std")]
+use super::wait::WaitState;
 use crate::error;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+#[cfg(feature = "borrow_diagnostics")]
+use alloc::vec::Vec;
 use core::sync::atomic::{AtomicUsize, Ordering};
 
 const HIGH_BIT: usize = !(usize::MAX >> 1);
 const MAX_FAILED_BORROWS: usize = HIGH_BIT + (HIGH_BIT >> 1);
 
-pub(super) struct BorrowState(AtomicUsize);
+pub(super) struct BorrowState {
+    state: AtomicUsize,
+    #[cfg(feature = "borrow_diagnostics")]
+    holders: Holders,
+    /// Waiters of the `World` this borrow belongs to, woken up on release.
+    #[cfg(feature = "std")]
+    wait_state: Option<Arc<WaitState>>,
+}
 
 /// Unlocks a shared borrow on drop.
-pub struct SharedBorrow<'a>(&'a BorrowState);
+pub struct SharedBorrow<'a>(&'a BorrowState, #[cfg(feature = "borrow_diagnostics")] u64);
 
 impl Drop for SharedBorrow<'_> {
     #[inline]
     fn drop(&mut self) {
-        (self.0).0.fetch_sub(1, Ordering::Release);
+        #[cfg(feature = "borrow_diagnostics")]
+        self.0.holders.remove(self.1);
+
+        self.0.state.fetch_sub(1, Ordering::Release);
+
+        #[cfg(feature = "std")]
+        self.0.notify_release();
     }
 }
 
@@ -24,46 +45,88 @@ impl Clone for SharedBorrow<'_> {
 }
 
 /// Unlocks an exclusive borrow on drop.
-pub struct ExclusiveBorrow<'a>(&'a BorrowState);
+pub struct ExclusiveBorrow<'a>(&'a BorrowState, #[cfg(feature = "borrow_diagnostics")] u64);
 
 impl ExclusiveBorrow<'_> {
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub(crate) fn shared_reborrow(&self) -> SharedBorrow<'_> {
-        self.0.read_reborrow();
-
-        SharedBorrow(self.0)
+        self.0.read_reborrow()
     }
 }
 
 impl Drop for ExclusiveBorrow<'_> {
     #[inline]
     fn drop(&mut self) {
-        (self.0).0.store(0, Ordering::Release);
+        #[cfg(feature = "borrow_diagnostics")]
+        self.0.holders.remove(self.1);
+
+        self.0.state.store(0, Ordering::Release);
+
+        #[cfg(feature = "std")]
+        self.0.notify_release();
     }
 }
 
 impl BorrowState {
     #[inline]
     pub(super) fn new() -> Self {
-        BorrowState(AtomicUsize::new(0))
+        BorrowState {
+            state: AtomicUsize::new(0),
+            #[cfg(feature = "borrow_diagnostics")]
+            holders: Holders::default(),
+            #[cfg(feature = "std")]
+            wait_state: None,
+        }
+    }
+
+    /// Wakes up the threads waiting on `wait_state` each time a borrow is released.
+    #[cfg(feature = "std")]
+    pub(super) fn set_wait_state(&mut self, wait_state: Arc<WaitState>) {
+        self.wait_state = Some(wait_state);
     }
 
+    #[cfg(feature = "std")]
     #[inline]
+    fn notify_release(&self) {
+        if let Some(wait_state) = &self.wait_state {
+            wait_state.notify_release();
+        }
+    }
+
+    /// Returns the holders of the active borrows.
+    #[cfg(feature = "borrow_diagnostics")]
+    pub(super) fn holders(&self) -> Vec<BorrowHolder> {
+        self.holders.to_vec()
+    }
+
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
+    fn shared_borrow(&self) -> SharedBorrow<'_> {
+        SharedBorrow(
+            self,
+            #[cfg(feature = "borrow_diagnostics")]
+            self.holders.add(BorrowHolder::current()),
+        )
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub(super) fn read(&self) -> Result<SharedBorrow<'_>, error::Borrow> {
-        let new = self.0.fetch_add(1, Ordering::Acquire) + 1;
+        let new = self.state.fetch_add(1, Ordering::Acquire) + 1;
         if new & HIGH_BIT != 0 {
             self.cold_check_overflow(new);
 
             Err(error::Borrow::Unique)
         } else {
-            Ok(SharedBorrow(self))
+            Ok(self.shared_borrow())
         }
     }
 
     #[allow(unused)]
     #[inline]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub(super) fn exclusive_read(&self) -> Result<SharedBorrow<'_>, error::Borrow> {
         let old = match self
-            .0
+            .state
             .compare_exchange(0, 1, Ordering::Acquire, Ordering::Relaxed)
         {
             Ok(x) => x,
@@ -71,7 +134,7 @@ impl BorrowState {
         };
 
         if old == 0 {
-            Ok(SharedBorrow(self))
+            Ok(self.shared_borrow())
         } else if old & HIGH_BIT == 0 {
             Err(error::Borrow::Shared)
         } else {
@@ -80,26 +143,33 @@ impl BorrowState {
     }
 
     #[inline]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub(super) fn read_reborrow(&self) -> SharedBorrow<'_> {
-        let new = self.0.fetch_add(1, Ordering::Acquire) + 1;
+        let new = self.state.fetch_add(1, Ordering::Acquire) + 1;
 
         self.check_overflow(new);
 
-        SharedBorrow(self)
+        self.shared_borrow()
     }
 
     #[inline]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub(super) fn write(&self) -> Result<ExclusiveBorrow<'_>, error::Borrow> {
-        let old = match self
-            .0
-            .compare_exchange(0, HIGH_BIT, Ordering::Acquire, Ordering::Relaxed)
-        {
-            Ok(x) => x,
-            Err(x) => x,
-        };
+        let old =
+            match self
+                .state
+                .compare_exchange(0, HIGH_BIT, Ordering::Acquire, Ordering::Relaxed)
+            {
+                Ok(x) => x,
+                Err(x) => x,
+            };
 
         if old == 0 {
-            Ok(ExclusiveBorrow(self))
+            Ok(ExclusiveBorrow(
+                self,
+                #[cfg(feature = "borrow_diagnostics")]
+                self.holders.add(BorrowHolder::current()),
+            ))
         } else if old & HIGH_BIT == 0 {
             Err(error::Borrow::Shared)
         } else {
@@ -109,7 +179,7 @@ impl BorrowState {
 
     fn check_overflow(&self, new: usize) {
         if new == HIGH_BIT {
-            self.0.fetch_sub(1, Ordering::Release);
+            self.state.fetch_sub(1, Ordering::Release);
 
             panic!("too many immutable borrows");
         } else if new >= MAX_FAILED_BORROWS {
@@ -0,0 +1,109 @@
+use crate::scheduler::Label;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt;
+use core::panic::Location;
+use std::sync::{Mutex, PoisonError};
+
+std::thread_local! {
+    static CURRENT: RefCell<Option<BorrowHolder>> = const { RefCell::new(None) };
+}
+
+/// Holder of an active borrow, reported by [`GetStorage::holders`](crate::error::GetStorage::holders).
+#[derive(Clone, PartialEq, Eq)]
+pub enum BorrowHolder {
+    /// A system run by the scheduler.
+    System(Box<dyn Label>),
+    /// The call that made the borrow, outside of workloads.
+    Location(&'static Location<'static>),
+}
+
+impl BorrowHolder {
+    /// Makes `label` the holder of all borrows made on this thread until the returned scope is dropped.
+    pub(crate) fn enter_system(label: &dyn Label) -> HolderScope {
+        HolderScope::new(BorrowHolder::System(label.dyn_clone()), true)
+    }
+    /// Makes the caller the holder of all borrows made on this thread until the returned scope is dropped.\
+    /// Does nothing if a holder is already set.
+    #[track_caller]
+    pub(crate) fn enter_location() -> HolderScope {
+        HolderScope::new(BorrowHolder::Location(Location::caller()), false)
+    }
+    /// Returns the holder set on this thread or the caller.
+    #[track_caller]
+    pub(crate) fn current() -> BorrowHolder {
+        CURRENT
+            .with(|current| current.borrow().clone())
+            .unwrap_or_else(|| BorrowHolder::Location(Location::caller()))
+    }
+}
+
+impl fmt::Debug for BorrowHolder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BorrowHolder::System(label) => f.write_fmt(format_args!("system {:?}", label)),
+            BorrowHolder::Location(location) => fmt::Display::fmt(location, f),
+        }
+    }
+}
+
+impl fmt::Display for BorrowHolder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Restores the previous holder on drop.
+pub(crate) struct HolderScope(Option<Option<BorrowHolder>>);
+
+impl HolderScope {
+    fn new(holder: BorrowHolder, replace: bool) -> HolderScope {
+        CURRENT.with(|current| {
+            let mut current = current.borrow_mut();
+
+            if replace || current.is_none() {
+                HolderScope(Some(current.replace(holder)))
+            } else {
+                HolderScope(None)
+            }
+        })
+    }
+}
+
+impl Drop for HolderScope {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+}
+
+/// Holders of the active borrows of an `AtomicRefCell`.
+#[derive(Default)]
+pub(super) struct Holders(Mutex<(u64, Vec<(u64, BorrowHolder)>)>);
+
+impl Holders {
+    /// Records `holder` and returns the id used to remove it.
+    pub(super) fn add(&self, holder: BorrowHolder) -> u64 {
+        let mut holders = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let id = holders.0;
+        holders.0 += 1;
+        holders.1.push((id, holder));
+
+        id
+    }
+    pub(super) fn remove(&self, id: u64) {
+        let mut holders = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(index) = holders.1.iter().position(|(holder_id, _)| *holder_id == id) {
+            holders.1.swap_remove(index);
+        }
+    }
+    pub(super) fn to_vec(&self) -> Vec<BorrowHolder> {
+        let holders = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+
+        holders.1.iter().map(|(_, holder)| holder.clone()).collect()
+    }
+}
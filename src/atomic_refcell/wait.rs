@@ -0,0 +1,109 @@
+use core::cell::Cell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::time::Instant;
+
+std::thread_local! {
+    /// Releases made by this thread, a waiting thread doesn't wake itself up.
+    static OWN_RELEASES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Threads waiting for a borrow of a `World` to be released.
+#[derive(Default)]
+pub(crate) struct WaitState {
+    /// Number of threads waiting.
+    waiting: AtomicUsize,
+    /// Incremented each time a borrow is released while a thread is waiting.
+    #[allow(clippy::mutex_integer)]
+    releases: Mutex<u64>,
+    released: Condvar,
+}
+
+impl WaitState {
+    /// Wakes up the threads waiting in [`retry_blocking`].\
+    /// Only costs an atomic load when no thread is waiting.
+    #[inline]
+    pub(super) fn notify_release(&self) {
+        if self.waiting.load(Ordering::SeqCst) != 0 {
+            self.cold_notify_release();
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn cold_notify_release(&self) {
+        *self.releases.lock().unwrap_or_else(PoisonError::into_inner) += 1;
+        OWN_RELEASES.with(|own_releases| own_releases.set(own_releases.get() + 1));
+        self.released.notify_all();
+    }
+}
+
+/// Calls `f` until it succeeds or returns an error for which `should_wait` is `false`.\
+/// Between two calls, the thread parks until a borrow watched by `wait_state` is released.
+///
+/// Returns the last error if `timeout` elapses.
+pub(crate) fn retry_blocking<T, E>(
+    wait_state: &WaitState,
+    timeout: Option<Duration>,
+    mut f: impl FnMut() -> Result<T, E>,
+    should_wait: impl Fn(&E) -> bool,
+) -> Result<T, E> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    wait_state.waiting.fetch_add(1, Ordering::SeqCst);
+    let _waiting = Waiting(wait_state);
+
+    loop {
+        // read before trying to not miss a release happening between the failed try and the wait
+        let releases = *wait_state
+            .releases
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let own_releases = OWN_RELEASES.with(Cell::get);
+
+        let err = match f() {
+            Ok(value) => return Ok(value),
+            Err(err) if !should_wait(&err) => return Err(err),
+            Err(err) => err,
+        };
+
+        // the borrows `f` made before failing were released by this thread
+        let releases = releases + (OWN_RELEASES.with(Cell::get) - own_releases);
+
+        let guard = wait_state
+            .releases
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let nothing_released = |current: &mut u64| *current == releases;
+
+        match deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(err);
+                }
+
+                let _guard = wait_state
+                    .released
+                    .wait_timeout_while(guard, deadline - now, nothing_released)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+            None => {
+                let _guard = wait_state
+                    .released
+                    .wait_while(guard, nothing_released)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+        }
+    }
+}
+
+/// Unregisters the waiting thread on drop, even if `f` panics.
+struct Waiting<'a>(&'a WaitState);
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        self.0.waiting.fetch_sub(1, Ordering::SeqCst);
+    }
+}
@@ -81,6 +81,7 @@ unsafe impl BorrowInfo for &World {
             thread_safe: true,
             #[cfg(feature = "thread_local")]
             thread_safe: false,
+            optional: false,
         });
     }
 
@@ -97,6 +98,7 @@ unsafe impl<'a> BorrowInfo for AllStoragesView<'a> {
             thread_safe: true,
             #[cfg(feature = "thread_local")]
             thread_safe: false,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -112,6 +114,7 @@ unsafe impl<'a> BorrowInfo for AllStoragesViewMut<'a> {
             thread_safe: true,
             #[cfg(feature = "thread_local")]
             thread_safe: false,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -124,6 +127,7 @@ unsafe impl<'a> BorrowInfo for EntitiesView<'a> {
             mutability: Mutability::Shared,
             storage_id: StorageId::of::<Entities>(),
             thread_safe: true,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -136,6 +140,7 @@ unsafe impl<'a> BorrowInfo for EntitiesViewMut<'a> {
             mutability: Mutability::Exclusive,
             storage_id: StorageId::of::<Entities>(),
             thread_safe: true,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -151,6 +156,7 @@ where
             mutability: Mutability::Shared,
             storage_id: StorageId::of::<SparseSet<T>>(),
             thread_safe: true,
+            optional: false,
         });
     }
     fn enable_tracking(
@@ -177,6 +183,7 @@ where
             mutability: Mutability::Shared,
             storage_id: StorageId::of::<SparseSet<T>>(),
             thread_safe: true,
+            optional: false,
         });
     }
 
@@ -204,6 +211,7 @@ where
             mutability: Mutability::Shared,
             storage_id: StorageId::of::<SparseSet<T>>(),
             thread_safe: false,
+            optional: false,
         });
     }
 
@@ -231,6 +239,7 @@ where
             mutability: Mutability::Shared,
             storage_id: StorageId::of::<SparseSet<T>>(),
             thread_safe: false,
+            optional: false,
         });
     }
 
@@ -257,6 +266,7 @@ where
             mutability: Mutability::Exclusive,
             storage_id: StorageId::of::<SparseSet<T>>(),
             thread_safe: true,
+            optional: false,
         });
     }
     fn enable_tracking(
@@ -283,6 +293,7 @@ where
             mutability: Mutability::Exclusive,
             storage_id: StorageId::of::<SparseSet<T>>(),
             thread_safe: false,
+            optional: false,
         });
     }
 
@@ -310,6 +321,7 @@ where
             mutability: Mutability::Exclusive,
             storage_id: StorageId::of::<SparseSet<T>>(),
             thread_safe: true,
+            optional: false,
         });
     }
 
@@ -337,6 +349,7 @@ where
             mutability: Mutability::Exclusive,
             storage_id: StorageId::of::<SparseSet<T>>(),
             thread_safe: false,
+            optional: false,
         });
     }
 
@@ -360,6 +373,7 @@ unsafe impl<'a, T: Send + Sync + Unique> BorrowInfo for UniqueView<'a, T> {
             mutability: Mutability::Shared,
             storage_id: StorageId::of::<UniqueStorage<T>>(),
             thread_safe: true,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -373,6 +387,7 @@ unsafe impl<'a, T: Sync + Unique> BorrowInfo for NonSend<UniqueView<'a, T>> {
             mutability: Mutability::Shared,
             storage_id: StorageId::of::<UniqueStorage<T>>(),
             thread_safe: true,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -386,6 +401,7 @@ unsafe impl<'a, T: Send + Unique> BorrowInfo for NonSync<UniqueView<'a, T>> {
             mutability: Mutability::Shared,
             storage_id: StorageId::of::<UniqueStorage<T>>(),
             thread_safe: false,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -399,6 +415,7 @@ unsafe impl<'a, T: Unique> BorrowInfo for NonSendSync<UniqueView<'a, T>> {
             mutability: Mutability::Shared,
             storage_id: StorageId::of::<UniqueStorage<T>>(),
             thread_safe: false,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -411,6 +428,7 @@ unsafe impl<'a, T: Send + Sync + Unique> BorrowInfo for UniqueViewMut<'a, T> {
             mutability: Mutability::Exclusive,
             storage_id: StorageId::of::<UniqueStorage<T>>(),
             thread_safe: true,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -424,6 +442,7 @@ unsafe impl<'a, T: Sync + Unique> BorrowInfo for NonSend<UniqueViewMut<'a, T>> {
             mutability: Mutability::Exclusive,
             storage_id: StorageId::of::<UniqueStorage<T>>(),
             thread_safe: false,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -437,6 +456,7 @@ unsafe impl<'a, T: Send + Unique> BorrowInfo for NonSync<UniqueViewMut<'a, T>> {
             mutability: Mutability::Exclusive,
             storage_id: StorageId::of::<UniqueStorage<T>>(),
             thread_safe: true,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -450,6 +470,7 @@ unsafe impl<'a, T: Unique> BorrowInfo for NonSendSync<UniqueViewMut<'a, T>> {
             mutability: Mutability::Exclusive,
             storage_id: StorageId::of::<UniqueStorage<T>>(),
             thread_safe: false,
+            optional: false,
         });
     }
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
@@ -457,7 +478,14 @@ unsafe impl<'a, T: Unique> BorrowInfo for NonSendSync<UniqueViewMut<'a, T>> {
 
 unsafe impl<T: BorrowInfo> BorrowInfo for Option<T> {
     fn borrow_info(info: &mut Vec<TypeInfo>) {
+        let start = info.len();
+
         T::borrow_info(info);
+
+        // the storages are still borrowed but don't have to be present
+        for type_info in &mut info[start..] {
+            type_info.optional = true;
+        }
     }
     fn enable_tracking(
         enable_tracking: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>,
@@ -622,6 +622,8 @@ impl<T: Unique> Borrow for NonSendSync<UniqueViewMut<'_, T>> {
     }
 }
 
+/// Yields `None` instead of failing when `T` can't be borrowed, for example when a unique isn't present.\
+/// Workloads still take the borrows of `T` into account to schedule systems.
 impl<T: Borrow> Borrow for Option<T> {
     type View<'a> = Option<T::View<'a>>;
 
@@ -0,0 +1,160 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::sparse_set::SparseSet;
+use crate::storage::StorageId;
+use crate::track;
+use crate::tracking::{Track, TrackingTimestamp};
+use alloc::vec::Vec;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Changes made to a component storage between two [`TrackingTimestamp`]s, see [`World::delta`](crate::World::delta).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentDelta<T> {
+    /// Components inserted or modified.
+    pub changed: Vec<(EntityId, T)>,
+    /// Entities whose component was removed or deleted, they are still alive.
+    pub removed: Vec<EntityId>,
+    /// Entities deleted while they had a component, applying the delta deletes them.
+    pub deleted_entities: Vec<EntityId>,
+}
+
+impl<T> Default for ComponentDelta<T> {
+    fn default() -> Self {
+        ComponentDelta {
+            changed: Vec::new(),
+            removed: Vec::new(),
+            deleted_entities: Vec::new(),
+        }
+    }
+}
+
+impl<T> ComponentDelta<T> {
+    /// Returns `true` if the delta doesn't contain any change.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty() && self.deleted_entities.is_empty()
+    }
+}
+
+/// Components that can be synchronized with [`World::delta`](crate::World::delta) and [`World::apply_delta`](crate::World::apply_delta).
+///
+/// Implemented for components and tuples of components.
+pub trait DeltaComponents {
+    /// Changes of all components, a [`ComponentDelta`] or a tuple of them.
+    type Delta: Serialize + DeserializeOwned;
+
+    /// Collects the changes made between `last` and `current`.
+    #[allow(missing_docs)]
+    fn delta(
+        all_storages: &AllStorages,
+        last: TrackingTimestamp,
+        current: TrackingTimestamp,
+    ) -> Result<Self::Delta, error::GetStorage>;
+    /// Deletes the deleted entities of every component, then applies the removals and changes.
+    #[allow(missing_docs)]
+    fn apply(all_storages: &mut AllStorages, delta: Self::Delta) {
+        Self::delete_entities(all_storages, &delta);
+        Self::apply_changes(all_storages, delta);
+    }
+    /// Deletes the entities deleted on the other side.
+    #[allow(missing_docs)]
+    fn delete_entities(all_storages: &mut AllStorages, delta: &Self::Delta);
+    /// Removes the components of the tombstones then inserts the changed components.\
+    /// Spawning a changed entity deletes the older entity alive at its index.
+    #[allow(missing_docs)]
+    fn apply_changes(all_storages: &mut AllStorages, delta: Self::Delta);
+}
+
+impl<T: Component + Send + Sync + Clone + Serialize + DeserializeOwned> DeltaComponents for T {
+    type Delta = ComponentDelta<T>;
+
+    fn delta(
+        all_storages: &AllStorages,
+        last: TrackingTimestamp,
+        current: TrackingTimestamp,
+    ) -> Result<ComponentDelta<T>, error::GetStorage> {
+        let entities = all_storages.entities()?;
+        let sparse_set = all_storages.custom_storage::<SparseSet<T>>()?;
+        sparse_set.check_tracking::<Track<track::All>>()?;
+
+        let mut delta = ComponentDelta::default();
+        sparse_set.for_each_change_within(
+            last,
+            current,
+            |entity| {
+                if entities.is_alive(entity) {
+                    delta.removed.push(entity);
+                } else {
+                    delta.deleted_entities.push(entity);
+                }
+            },
+            |entity, component| delta.changed.push((entity, component.clone())),
+        );
+
+        Ok(delta)
+    }
+    fn delete_entities(all_storages: &mut AllStorages, delta: &ComponentDelta<T>) {
+        // entities in multiple storages are only deleted by the first one
+        for &entity in &delta.deleted_entities {
+            all_storages.delete_entity(entity);
+        }
+    }
+    fn apply_changes(all_storages: &mut AllStorages, mut delta: ComponentDelta<T>) {
+        let current = all_storages.get_current();
+
+        delta
+            .changed
+            .retain(|(entity, _)| all_storages.spawn_over_older(*entity));
+
+        let sparse_set = all_storages
+            .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<T>>(), SparseSet::<T>::new);
+
+        for entity in delta.removed {
+            sparse_set.dyn_delete(entity, current);
+        }
+
+        for (entity, component) in delta.changed {
+            sparse_set.insert(entity, component, current);
+        }
+    }
+}
+
+macro_rules! impl_delta_components {
+    ($(($type: ident, $index: tt))+) => {
+        impl<$($type: Component + Send + Sync + Clone + Serialize + DeserializeOwned,)+> DeltaComponents for ($($type,)+) {
+            type Delta = ($(ComponentDelta<$type>,)+);
+
+            fn delta(
+                all_storages: &AllStorages,
+                last: TrackingTimestamp,
+                current: TrackingTimestamp,
+            ) -> Result<Self::Delta, error::GetStorage> {
+                Ok(($(<$type as DeltaComponents>::delta(all_storages, last, current)?,)+))
+            }
+            fn delete_entities(all_storages: &mut AllStorages, delta: &Self::Delta) {
+                $(
+                    <$type as DeltaComponents>::delete_entities(all_storages, &delta.$index);
+                )+
+            }
+            fn apply_changes(all_storages: &mut AllStorages, delta: Self::Delta) {
+                $(
+                    <$type as DeltaComponents>::apply_changes(all_storages, delta.$index);
+                )+
+            }
+        }
+    };
+}
+
+macro_rules! delta_components {
+    ($(($type: ident, $index: tt))*;($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_delta_components![$(($type, $index))*];
+        delta_components![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))*;) => {
+        impl_delta_components![$(($type, $index))*];
+    }
+}
+
+delta_components![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
@@ -0,0 +1,556 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::atomic_refcell::{ARef, ARefMut, ExclusiveBorrow, SharedBorrow};
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::memory_usage::StorageMemoryUsage;
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::ShipHashMap;
+use alloc::alloc::{alloc, dealloc, handle_alloc_error, realloc};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::alloc::Layout;
+use core::hash::BuildHasherDefault;
+use core::mem::size_of;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+/// Describes a component type only known at runtime, stored in a [`DynamicStorage`].
+///
+/// By default components are plain bytes: they're cloned by copying them and don't need to be dropped.\
+/// Components owning resources have to provide a drop and a clone function.
+#[derive(Clone)]
+pub struct DynamicComponentInfo {
+    name: Cow<'static, str>,
+    layout: Layout,
+    drop: Option<unsafe fn(*mut u8)>,
+    clone: Option<unsafe fn(*const u8, *mut u8)>,
+}
+
+impl DynamicComponentInfo {
+    /// Describes plain bytes components with the given `layout`.
+    pub fn new(name: impl Into<Cow<'static, str>>, layout: Layout) -> DynamicComponentInfo {
+        DynamicComponentInfo {
+            name: name.into(),
+            layout,
+            drop: None,
+            clone: None,
+        }
+    }
+    /// Describes `T` components.\
+    /// Useful to share a Rust type with a scripting layer.
+    pub fn of<T: Clone + Send + Sync + 'static>() -> DynamicComponentInfo {
+        unsafe fn drop_in_place<T>(ptr: *mut u8) {
+            ptr.cast::<T>().drop_in_place();
+        }
+        unsafe fn clone<T: Clone>(src: *const u8, dst: *mut u8) {
+            dst.cast::<T>().write((*src.cast::<T>()).clone());
+        }
+
+        DynamicComponentInfo {
+            name: core::any::type_name::<T>().into(),
+            layout: Layout::new::<T>(),
+            drop: if core::mem::needs_drop::<T>() {
+                Some(drop_in_place::<T>)
+            } else {
+                None
+            },
+            clone: Some(clone::<T>),
+        }
+    }
+    /// Sets the function called on a component when it is dropped.
+    ///
+    /// ### Safety
+    ///
+    /// `drop` has to be safe to call with a pointer to any component stored with this info.\
+    /// Components have to be safe to send and share across threads.
+    pub unsafe fn with_drop(mut self, drop: unsafe fn(*mut u8)) -> DynamicComponentInfo {
+        self.drop = Some(drop);
+        self
+    }
+    /// Sets the function writing a clone of the component pointed by its first argument to the uninitialized memory pointed by the second.
+    ///
+    /// ### Safety
+    ///
+    /// `clone` has to be safe to call with a pointer to any component stored with this info
+    /// and has to initialize the destination.
+    pub unsafe fn with_clone(
+        mut self,
+        clone: unsafe fn(*const u8, *mut u8),
+    ) -> DynamicComponentInfo {
+        self.clone = Some(clone);
+        self
+    }
+    /// Returns the name of the component type.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Returns the layout of the component type.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+    /// Returns `true` if components can be cloned.\
+    /// Components with a drop function need a clone function to be cloned.
+    pub fn is_clone(&self) -> bool {
+        self.clone.is_some() || self.drop.is_none()
+    }
+    /// Returns `true` if components are plain bytes, without drop nor clone function.
+    fn is_plain(&self) -> bool {
+        self.drop.is_none() && self.clone.is_none()
+    }
+    /// Returns `true` if both infos have the same name, layout and the same functions present.
+    pub(crate) fn describes_same(&self, other: &DynamicComponentInfo) -> bool {
+        self.name == other.name
+            && self.layout == other.layout
+            && self.drop.is_some() == other.drop.is_some()
+            && self.clone.is_some() == other.clone.is_some()
+    }
+}
+
+/// Returns a dangling pointer aligned for `layout`, used when no memory is allocated.
+fn dangling(layout: Layout) -> NonNull<u8> {
+    // an alignment is never 0
+    NonNull::new(layout.align() as *mut u8).unwrap()
+}
+
+/// Type-erased component storage, the layout of its components is described by a [`DynamicComponentInfo`].
+///
+/// Components are stored contiguously and accessed through pointers.\
+/// It is usually added to the [`World`](crate::World) with [`World::add_dynamic_storage`](crate::World::add_dynamic_storage)
+/// and accessed with [`DynamicView`] and [`DynamicViewMut`].
+pub struct DynamicStorage {
+    info: DynamicComponentInfo,
+    stride: usize,
+    data: NonNull<u8>,
+    capacity: usize,
+    dense: Vec<EntityId>,
+    indices: ShipHashMap<EntityId, usize>,
+}
+
+// SAFE: plain bytes can be sent and shared,
+// `with_drop` and `with_clone` require other components to be `Send` and `Sync`.
+unsafe impl Send for DynamicStorage {}
+unsafe impl Sync for DynamicStorage {}
+
+impl DynamicStorage {
+    /// Creates an empty storage for components described by `info`.
+    pub fn new(info: DynamicComponentInfo) -> DynamicStorage {
+        let stride = info.layout.pad_to_align().size();
+
+        DynamicStorage {
+            data: dangling(info.layout),
+            capacity: if stride == 0 { usize::MAX } else { 0 },
+            stride,
+            info,
+            dense: Vec::new(),
+            indices: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+        }
+    }
+    /// Returns the description of the components.
+    pub fn info(&self) -> &DynamicComponentInfo {
+        &self.info
+    }
+    /// Moves the component pointed by `component` into the storage.\
+    /// If `entity` already had a component, it is dropped.
+    ///
+    /// ### Safety
+    ///
+    /// `component` has to point to a valid component described by this storage's info.\
+    /// The caller gives up ownership of the component and must not drop it.\
+    /// Plain bytes components have to be fully initialized.
+    pub unsafe fn insert(&mut self, entity: EntityId, component: *const u8) {
+        if let Some(&index) = self.indices.get(&entity) {
+            let ptr = self.ptr_at(index);
+
+            if let Some(drop) = self.info.drop {
+                drop(ptr);
+            }
+
+            ptr.copy_from_nonoverlapping(component, self.info.layout.size());
+        } else {
+            self.reserve_one();
+
+            self.ptr_at(self.dense.len())
+                .copy_from_nonoverlapping(component, self.info.layout.size());
+            self.indices.insert(entity, self.dense.len());
+            self.dense.push(entity);
+        }
+    }
+    /// Copies `bytes` into the storage as `entity`'s component.\
+    /// If `entity` already had a component, it is overwritten.
+    ///
+    /// ### Panics
+    ///
+    /// - The components have a drop or clone function, use [`insert`](DynamicStorage::insert) instead.
+    /// - `bytes` isn't as long as the component.
+    #[track_caller]
+    pub fn insert_bytes(&mut self, entity: EntityId, bytes: &[u8]) {
+        assert!(
+            self.info.is_plain(),
+            "{} components can't be inserted as bytes, they have a drop or clone function.",
+            self.info.name
+        );
+        assert_eq!(
+            bytes.len(),
+            self.info.layout.size(),
+            "{} components are {} bytes long.",
+            self.info.name,
+            self.info.layout.size()
+        );
+
+        // SAFE components without drop function are plain bytes
+        unsafe { self.insert(entity, bytes.as_ptr()) }
+    }
+    /// Returns a pointer to `entity`'s component.\
+    /// The pointer is valid until the storage is modified.
+    pub fn get(&self, entity: EntityId) -> Option<*const u8> {
+        self.indices
+            .get(&entity)
+            .map(|&index| self.ptr_at(index).cast_const())
+    }
+    /// Returns a mutable pointer to `entity`'s component.\
+    /// The pointer is valid until the storage is modified.
+    pub fn get_mut(&mut self, entity: EntityId) -> Option<*mut u8> {
+        self.indices.get(&entity).map(|&index| self.ptr_at(index))
+    }
+    /// Returns `entity`'s component as bytes.
+    ///
+    /// ### Panics
+    ///
+    /// - The components have a drop or clone function, their bytes might not all be initialized.
+    #[track_caller]
+    pub fn get_bytes(&self, entity: EntityId) -> Option<&[u8]> {
+        assert!(
+            self.info.is_plain(),
+            "{} components can't be read as bytes, they have a drop or clone function.",
+            self.info.name
+        );
+
+        self.get(entity)
+            .map(|ptr| unsafe { core::slice::from_raw_parts(ptr, self.info.layout.size()) })
+    }
+    /// Returns `true` if `entity` has a component in this storage.
+    pub fn contains(&self, entity: EntityId) -> bool {
+        self.indices.contains_key(&entity)
+    }
+    /// Removes and drops `entity`'s component.\
+    /// Returns `false` if `entity` didn't have a component.
+    pub fn remove(&mut self, entity: EntityId) -> bool {
+        if let Some(index) = self.indices.remove(&entity) {
+            let ptr = self.ptr_at(index);
+
+            if let Some(drop) = self.info.drop {
+                unsafe { drop(ptr) };
+            }
+
+            self.swap_remove(index);
+
+            true
+        } else {
+            false
+        }
+    }
+    /// Moves `entity`'s component out of the storage into `dst`.\
+    /// Returns `false` if `entity` didn't have a component, `dst` is left untouched in this case.
+    ///
+    /// ### Safety
+    ///
+    /// `dst` has to be valid for writes of the component's layout.
+    pub unsafe fn take(&mut self, entity: EntityId, dst: *mut u8) -> bool {
+        if let Some(index) = self.indices.remove(&entity) {
+            dst.copy_from_nonoverlapping(self.ptr_at(index), self.info.layout.size());
+
+            self.swap_remove(index);
+
+            true
+        } else {
+            false
+        }
+    }
+    /// Clones `from`'s component and inserts the clone as `to`'s component.\
+    /// Returns `false` if `from` didn't have a component.
+    ///
+    /// ### Panics
+    ///
+    /// - The components have a drop function but no clone function.
+    #[track_caller]
+    pub fn clone_component(&mut self, from: EntityId, to: EntityId) -> bool {
+        assert!(
+            self.info.is_clone(),
+            "{} components can't be cloned.",
+            self.info.name
+        );
+
+        if from == to {
+            return self.contains(from);
+        }
+
+        let src = match self.get(from) {
+            Some(src) => src,
+            None => return false,
+        };
+
+        let clone = self.info.clone;
+        let layout = self.info.layout;
+
+        unsafe {
+            let buffer = if layout.size() == 0 {
+                dangling(layout)
+            } else {
+                let ptr = alloc(layout);
+                NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(layout))
+            };
+
+            match clone {
+                Some(clone) => clone(src, buffer.as_ptr()),
+                None => buffer.as_ptr().copy_from_nonoverlapping(src, layout.size()),
+            }
+
+            self.insert(to, buffer.as_ptr());
+
+            if layout.size() != 0 {
+                dealloc(buffer.as_ptr(), layout);
+            }
+        }
+
+        true
+    }
+    /// Returns the number of components.
+    pub fn len(&self) -> usize {
+        self.dense.len()
+    }
+    /// Returns `true` if the storage is empty.
+    pub fn is_empty(&self) -> bool {
+        self.dense.is_empty()
+    }
+    /// Returns the entities owning a component, in storage order.
+    pub fn entities(&self) -> &[EntityId] {
+        &self.dense
+    }
+    /// Returns an iterator over the entities and a pointer to their component.
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, *const u8)> + '_ {
+        self.dense
+            .iter()
+            .enumerate()
+            .map(|(index, &entity)| (entity, self.ptr_at(index).cast_const()))
+    }
+    /// Returns an iterator over the entities and a mutable pointer to their component.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (EntityId, *mut u8)> + '_ {
+        let data = self.data;
+        let stride = self.stride;
+
+        self.dense
+            .iter()
+            .enumerate()
+            .map(move |(index, &entity)| (entity, unsafe { data.as_ptr().add(index * stride) }))
+    }
+    /// Drops all components.
+    pub fn clear(&mut self) {
+        if let Some(drop) = self.info.drop {
+            for index in 0..self.dense.len() {
+                unsafe { drop(self.ptr_at(index)) };
+            }
+        }
+
+        self.dense.clear();
+        self.indices.clear();
+    }
+
+    fn ptr_at(&self, index: usize) -> *mut u8 {
+        unsafe { self.data.as_ptr().add(index * self.stride) }
+    }
+    fn array_layout(&self, capacity: usize) -> Layout {
+        self.stride
+            .checked_mul(capacity)
+            .and_then(|size| Layout::from_size_align(size, self.info.layout.align()).ok())
+            .expect("DynamicStorage capacity overflow.")
+    }
+    fn reserve_one(&mut self) {
+        if self.dense.len() < self.capacity {
+            return;
+        }
+
+        let new_capacity = (self.capacity * 2).max(4);
+        let new_layout = self.array_layout(new_capacity);
+
+        let ptr = unsafe {
+            if self.capacity == 0 {
+                alloc(new_layout)
+            } else {
+                realloc(
+                    self.data.as_ptr(),
+                    self.array_layout(self.capacity),
+                    new_layout.size(),
+                )
+            }
+        };
+
+        self.data = NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(new_layout));
+        self.capacity = new_capacity;
+    }
+    /// Moves the last component to `index`, the component at `index` has to be dropped or moved out.
+    fn swap_remove(&mut self, index: usize) {
+        let last = self.dense.len() - 1;
+
+        if index != last {
+            unsafe {
+                self.ptr_at(index)
+                    .copy_from_nonoverlapping(self.ptr_at(last), self.info.layout.size());
+            }
+
+            self.indices.insert(self.dense[last], index);
+        }
+
+        self.dense.swap_remove(index);
+    }
+}
+
+impl Drop for DynamicStorage {
+    fn drop(&mut self) {
+        self.clear();
+
+        if self.stride != 0 && self.capacity != 0 {
+            unsafe { dealloc(self.data.as_ptr(), self.array_layout(self.capacity)) };
+        }
+    }
+}
+
+impl Storage for DynamicStorage {
+    #[inline]
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        self.remove(entity);
+    }
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        DynamicStorage::clear(self);
+    }
+    fn memory_usage(&self) -> Option<StorageMemoryUsage> {
+        let entity_size = size_of::<EntityId>();
+        let index_size = size_of::<(EntityId, usize)>();
+        let capacity = if self.stride == 0 { 0 } else { self.capacity };
+
+        Some(StorageMemoryUsage {
+            storage_name: self.info.name.clone(),
+            allocated_memory_bytes: size_of::<Self>()
+                + (capacity * self.stride)
+                + (self.dense.capacity() * entity_size)
+                + (self.indices.capacity() * index_size),
+            used_memory_bytes: size_of::<Self>()
+                + (self.dense.len() * (self.stride + entity_size + index_size)),
+            component_count: self.dense.len(),
+        })
+    }
+    fn name(&self) -> Cow<'static, str> {
+        self.info.name.clone()
+    }
+    fn is_empty(&self) -> bool {
+        self.dense.is_empty()
+    }
+}
+
+/// Shared view over a [`DynamicStorage`], addressed by [`StorageId`].
+pub struct DynamicView<'a> {
+    storage: &'a DynamicStorage,
+    all_borrow: Option<SharedBorrow<'a>>,
+    borrow: SharedBorrow<'a>,
+}
+
+impl<'a> DynamicView<'a> {
+    /// Borrows the [`DynamicStorage`] stored at `storage_id`.
+    ///
+    /// ### Errors
+    ///
+    /// - Storage borrow failed.
+    /// - The storage at `storage_id` isn't a [`DynamicStorage`].
+    pub fn new(
+        storage_id: StorageId,
+        all_storages: ARef<'a, &'a AllStorages>,
+    ) -> Result<DynamicView<'a>, error::CustomStorageView> {
+        let (all_storages, all_borrow) = unsafe { ARef::destructure(all_storages) };
+
+        let storage = all_storages.custom_storage_by_id(storage_id)?;
+        let (storage, borrow) = unsafe { ARef::destructure(storage) };
+
+        if let Some(storage) = storage.as_any().downcast_ref() {
+            Ok(DynamicView {
+                storage,
+                all_borrow: Some(all_borrow),
+                borrow,
+            })
+        } else {
+            Err(error::CustomStorageView::WrongType(storage.name()))
+        }
+    }
+}
+
+impl Deref for DynamicView<'_> {
+    type Target = DynamicStorage;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.storage
+    }
+}
+
+impl Clone for DynamicView<'_> {
+    #[inline]
+    fn clone(&self) -> Self {
+        DynamicView {
+            storage: self.storage,
+            all_borrow: self.all_borrow.clone(),
+            borrow: self.borrow.clone(),
+        }
+    }
+}
+
+/// Exclusive view over a [`DynamicStorage`], addressed by [`StorageId`].
+pub struct DynamicViewMut<'a> {
+    storage: &'a mut DynamicStorage,
+    _all_borrow: Option<SharedBorrow<'a>>,
+    _borrow: ExclusiveBorrow<'a>,
+}
+
+impl<'a> DynamicViewMut<'a> {
+    /// Borrows the [`DynamicStorage`] stored at `storage_id` exclusively.
+    ///
+    /// ### Errors
+    ///
+    /// - Storage borrow failed.
+    /// - The storage at `storage_id` isn't a [`DynamicStorage`].
+    pub fn new(
+        storage_id: StorageId,
+        all_storages: ARef<'a, &'a AllStorages>,
+    ) -> Result<DynamicViewMut<'a>, error::CustomStorageView> {
+        let (all_storages, all_borrow) = unsafe { ARef::destructure(all_storages) };
+
+        let storage = all_storages.custom_storage_mut_by_id(storage_id)?;
+        let (storage, borrow) = unsafe { ARefMut::destructure(storage) };
+
+        let name = storage.name();
+
+        if let Some(storage) = storage.any_mut().downcast_mut() {
+            Ok(DynamicViewMut {
+                storage,
+                _all_borrow: Some(all_borrow),
+                _borrow: borrow,
+            })
+        } else {
+            Err(error::CustomStorageView::WrongType(name))
+        }
+    }
+}
+
+impl Deref for DynamicViewMut<'_> {
+    type Target = DynamicStorage;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.storage
+    }
+}
+
+impl DerefMut for DynamicViewMut<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.storage
+    }
+}
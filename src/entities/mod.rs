@@ -1,4 +1,6 @@
 mod iterator;
+#[cfg(feature = "serde1")]
+mod serde;
 
 pub use iterator::EntitiesIter;
 
@@ -11,9 +13,21 @@ use crate::memory_usage::StorageMemoryUsage;
 use crate::reserve::{BulkEntityIter, BulkReserve};
 use crate::storage::Storage;
 use crate::tracking::TrackingTimestamp;
+use crate::ShipHashMap;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::hash::BuildHasherDefault;
 use core::iter::repeat_with;
+use core::ops::Range;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// `reserved_cursor` value when no id was reserved from the removed list.
+const CURSOR_START: usize = 0;
+/// `reserved_cursor` value when all ids of the removed list were reserved.
+const CURSOR_END: usize = usize::MAX;
+/// Highest start of a local range.\
+/// `Entities` stores an id per index, the indices below the local range take up memory once a local entity is created.
+pub(crate) const MAX_LOCAL_RANGE_START: u64 = 1 << 24;
 
 /// Entities holds the EntityIds to all entities: living, removed and dead.
 ///
@@ -33,10 +47,24 @@ use core::iter::repeat_with;
 // Removed entities form a linked list inside the vector, using their index part to point to the next.
 // Removed entities are added to one end and removed from the other.
 // Dead entities are simply never added to the linked list.
+// Reserved ids are taken from the removed list then past the end of the vector,
+// they're only written to the vector when `Entities` is accessed exclusively.
+// When a local range is set, the removed list only contains indices of this range,
+// the other removed indices point to `max_index` and are only reused by `spawn`.
+// New local entities start at the local range, the vector is only filled up to it when one is created.
 pub struct Entities {
     pub(crate) data: Vec<EntityId>,
     list: Option<(usize, usize)>,
     on_deletion: Option<Box<dyn FnMut(EntityId) + Send + Sync>>,
+    /// Index + 1 of the next removed entity to reserve, or `CURSOR_START`/`CURSOR_END`.
+    reserved_cursor: AtomicUsize,
+    /// Number of ids reserved past the end of `data`.
+    reserved_len: AtomicUsize,
+    wrap_generation: bool,
+    /// Indices generated by this `Entities`, all of them if `None`.
+    local_range: Option<Range<u64>>,
+    remote_to_local: ShipHashMap<EntityId, EntityId>,
+    local_to_remote: ShipHashMap<EntityId, EntityId>,
 }
 
 impl Entities {
@@ -46,6 +74,12 @@ impl Entities {
             data: Vec::new(),
             list: None,
             on_deletion: None,
+            reserved_cursor: AtomicUsize::new(CURSOR_START),
+            reserved_len: AtomicUsize::new(0),
+            wrap_generation: false,
+            local_range: None,
+            remote_to_local: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            local_to_remote: ShipHashMap::with_hasher(BuildHasherDefault::default()),
         }
     }
     /// Returns `true` if `entity` matches a living entity.
@@ -57,6 +91,16 @@ impl Entities {
             false
         }
     }
+    /// Returns the entity alive at `entity`'s index if it has an older generation.
+    #[cfg(feature = "serde1")]
+    pub(crate) fn older_alive(&self, entity: EntityId) -> Option<EntityId> {
+        match self.data.get(entity.uindex()) {
+            Some(&old_entity) if old_entity.gen() < entity.gen() && self.is_alive(old_entity) => {
+                Some(old_entity)
+            }
+            _ => None,
+        }
+    }
     /// Adds `component` to `entity`, multiple components can be added at the same time using a tuple.  
     /// `Entities` is only borrowed immutably.  
     ///
@@ -133,7 +177,95 @@ impl Entities {
             panic!("{:?}", error::AddComponent::EntityIsNotAlive);
         }
     }
+    /// Returns a new [`EntityId`] without borrowing `Entities` exclusively.\
+    /// Multiple threads can reserve ids at the same time, each id is only handed out once.
+    ///
+    /// The entity becomes alive the next time `Entities` is borrowed exclusively
+    /// or when the current workload batch ends.
+    /// Until then [`is_alive`](Entities::is_alive) returns `false` and components can't be added to it.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesView, World};
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity = world.borrow::<EntitiesView>().unwrap().reserve_entity();
+    /// assert!(!world.borrow::<EntitiesView>().unwrap().is_alive(entity));
+    ///
+    /// world.add_entity(());
+    /// assert!(world.borrow::<EntitiesView>().unwrap().is_alive(entity));
+    /// ```
+    pub fn reserve_entity(&self) -> EntityId {
+        loop {
+            let cursor = self.reserved_cursor.load(Ordering::Relaxed);
+            let (node, new) = match (cursor, self.list) {
+                (CURSOR_END, _) | (_, None) => break,
+                (CURSOR_START, Some((new, old))) => (old, new),
+                (cursor, Some((new, _))) => (cursor - 1, new),
+            };
+            let next = if node == new {
+                CURSOR_END
+            } else {
+                // SAFE node is part of the removed list
+                unsafe { self.data.get_unchecked(node).uindex() + 1 }
+            };
+
+            if self
+                .reserved_cursor
+                .compare_exchange_weak(cursor, next, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                // SAFE node is part of the removed list
+                let mut entity_id = unsafe { *self.data.get_unchecked(node) };
+                entity_id.set_index(node as u64);
+
+                return entity_id;
+            }
+        }
+
+        let index = self.fresh_index() + self.reserved_len.fetch_add(1, Ordering::Relaxed);
+        self.assert_local_fresh(index, 1);
+
+        EntityId::new(index as u64)
+    }
+    /// Makes all reserved ids alive.
+    pub(crate) fn materialize_reserved(&mut self) {
+        let cursor = core::mem::replace(self.reserved_cursor.get_mut(), CURSOR_START);
+
+        if let (true, Some((new, old))) = (cursor != CURSOR_START, self.list) {
+            let mut node = old;
+
+            loop {
+                // SAFE node is part of the removed list
+                let entity_id = unsafe { self.data.get_unchecked_mut(node) };
+                let next = entity_id.uindex();
+                entity_id.set_index(node as u64);
+
+                if node == new {
+                    self.list = None;
+                    break;
+                } else if next + 1 == cursor {
+                    self.list = Some((new, next));
+                    break;
+                }
+
+                node = next;
+            }
+        }
+
+        let reserved_len = core::mem::take(self.reserved_len.get_mut());
+        if reserved_len > 0 {
+            let fresh_index = self.fresh_index();
+            self.pad_to(fresh_index);
+            self.data.extend(
+                (fresh_index as u64..(fresh_index + reserved_len) as u64).map(EntityId::new),
+            );
+        }
+    }
     pub(crate) fn generate(&mut self) -> EntityId {
+        self.materialize_reserved();
+
         if let Some((new, ref mut old)) = self.list {
             let old_index = *old;
 
@@ -151,29 +283,47 @@ impl Entities {
                 *self.data.get_unchecked(old_index)
             }
         } else {
-            let entity_id = EntityId::new(self.data.len() as u64);
+            let fresh_index = self.fresh_index();
+            self.assert_local_fresh(fresh_index, 1);
+            self.pad_to(fresh_index);
+
+            let entity_id = EntityId::new(fresh_index as u64);
             self.data.push(entity_id);
             entity_id
         }
     }
     pub(crate) fn bulk_generate(&mut self, count: usize) -> &[EntityId] {
+        self.materialize_reserved();
+
+        let fresh_index = self.fresh_index();
+        self.assert_local_fresh(fresh_index, count);
+        self.pad_to(fresh_index);
+
         self.data
-            .extend((self.data.len() as u64..(self.data.len() + count) as u64).map(EntityId::new));
+            .extend((fresh_index as u64..(fresh_index + count) as u64).map(EntityId::new));
 
         &self.data[self.data.len() - count..self.data.len()]
     }
     /// Deletes an entity, returns true if the entity was alive.  
     /// If the entity has components, they will not be deleted and still be accessible using this id.
     pub fn delete_unchecked(&mut self, entity_id: EntityId) -> bool {
+        self.materialize_reserved();
+
         if self.is_alive(entity_id) {
+            let wrap_generation = self.wrap_generation;
             // SAFE we checked for OOB
-            if unsafe {
-                self.data
-                    .get_unchecked_mut(entity_id.uindex())
-                    .bump_gen()
-                    .is_ok()
-            } {
-                if let Some((ref mut new, _)) = self.list {
+            if bump_gen(
+                unsafe { self.data.get_unchecked_mut(entity_id.uindex()) },
+                wrap_generation,
+            ) {
+                if !self.is_local(entity_id.uindex()) {
+                    // SAFE we checked for OOB
+                    unsafe {
+                        self.data
+                            .get_unchecked_mut(entity_id.uindex())
+                            .set_index(EntityId::max_index())
+                    };
+                } else if let Some((ref mut new, _)) = self.list {
                     // SAFE new is always in bound
                     unsafe {
                         self.data
@@ -196,6 +346,10 @@ impl Entities {
                 }
             }
 
+            if let Some(remote) = self.local_to_remote.remove(&entity_id) {
+                self.remote_to_local.remove(&remote);
+            }
+
             if let Some(on_deletion) = &mut self.on_deletion {
                 (on_deletion)(entity_id)
             }
@@ -273,6 +427,8 @@ impl Entities {
         let mut iter = component.into_iter();
         let len = iter.size_hint().0;
 
+        self.materialize_reserved();
+
         let entities_len = self.data.len();
         let new_entities = self.bulk_generate(len);
 
@@ -300,6 +456,8 @@ impl Entities {
     /// Does nothing if an entity with a greater generation is already at this index.  
     /// Returns `true` if the entity is successfully spawned.
     pub fn spawn(&mut self, entity: EntityId) -> bool {
+        self.materialize_reserved();
+
         if let Some(&old_entity) = self.data.get(entity.index() as usize) {
             if self.is_alive(old_entity) {
                 if old_entity.gen() <= entity.gen() {
@@ -309,69 +467,266 @@ impl Entities {
                 } else {
                     false
                 }
-            } else if let Some((new, old)) = self.list {
-                if old_entity.gen() <= entity.gen() + 1 {
-                    // pop from removed list
-                    if entity.uindex() == old {
-                        if new == old {
-                            self.list = None;
-                        } else {
-                            self.list = Some((new, self.data[entity.uindex()].uindex()));
-                        }
-                    } else {
-                        let mut current_index = old;
-
-                        while self.data[current_index].index() != entity.index()
-                            && self.data[current_index].uindex() != new
-                        {
-                            current_index = self.data[current_index].uindex();
-                        }
-
-                        if self.data[current_index].uindex() == new {
-                            self.data[current_index].set_index(EntityId::max_index());
-                            self.list = Some((current_index, old));
-                        } else {
-                            let next_index = self.data[self.data[current_index].uindex()].index();
-                            self.data[current_index].set_index(next_index);
-                        }
-                    }
-
-                    self.data[entity.uindex()] = entity;
+            } else if old_entity.gen() <= entity.gen() + 1 {
+                self.unlink(entity.uindex());
+                self.data[entity.uindex()] = entity;
 
-                    true
-                } else {
-                    false
-                }
+                true
             } else {
                 false
             }
         } else {
             let old_len = self.data.len();
-            self.data.resize(entity.uindex() + 1, EntityId::new(0));
+            self.data.resize(
+                entity.uindex() + 1,
+                EntityId::new_from_parts(EntityId::max_index(), 0),
+            );
+
+            // add to removed list
+            for index in old_len..entity.uindex() {
+                if self.is_local(index) {
+                    self.push_removed(index);
+                }
+            }
+
+            self.data[entity.uindex()] = entity;
+
+            true
+        }
+    }
+    /// Makes the given entity alive, like [`spawn`](Entities::spawn), but reports conflicts.\
+    /// Meant to replicate entities created by another `World`, their index has to be outside of the [local range](Entities::set_local_range).
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{error, EntitiesViewMut, EntityId, World};
+    ///
+    /// let world = World::new();
+    /// let mut entities = world.borrow::<EntitiesViewMut>().unwrap();
+    ///
+    /// entities.set_local_range(1000..2000);
+    ///
+    /// let remote = EntityId::new_from_index_and_gen(5, 0);
+    /// assert_eq!(entities.try_spawn(remote), Ok(()));
+    /// assert_eq!(entities.try_spawn(remote), Err(error::Spawn::AliveEntity(remote)));
+    ///
+    /// let local = entities.add_entity((), ());
+    /// assert_eq!(entities.try_spawn(local), Err(error::Spawn::LocalIndex));
+    /// ```
+    pub fn try_spawn(&mut self, entity: EntityId) -> Result<(), error::Spawn> {
+        self.materialize_reserved();
+
+        if self.local_range.is_some() && self.is_local(entity.uindex()) {
+            return Err(error::Spawn::LocalIndex);
+        }
+
+        if let Some(&old_entity) = self.data.get(entity.uindex()) {
+            if old_entity.index() == entity.index() {
+                return Err(error::Spawn::AliveEntity(old_entity));
+            } else if old_entity.gen() > entity.gen() + 1 {
+                return Err(error::Spawn::OutdatedGeneration);
+            }
+        }
+
+        self.spawn(entity);
+
+        Ok(())
+    }
+    /// Restricts the indices used by [`add_entity`](Entities::add_entity) and [`reserve_entity`](Entities::reserve_entity) to `range`.\
+    /// Indices outside of it are only used by [`spawn`](Entities::spawn) and [`try_spawn`](Entities::try_spawn),
+    /// two `World`s with disjoint ranges can then replicate each other's entities without conflict.
+    ///
+    /// Removed entities outside of `range` are not reused by this `Entities` anymore.
+    ///
+    /// `Entities` stores an id for each index up to the highest one used.
+    /// Once a local entity is created, the indices below `range` take 8 bytes each, `range.start` is limited to 2<sup>24</sup>.
+    ///
+    /// ### Panics
+    ///
+    /// - `range` is empty.
+    /// - `range.start` is greater than 2<sup>24</sup>.
+    /// - Creating an entity when all indices of `range` are used.
+    pub fn set_local_range(&mut self, range: Range<u64>) {
+        assert!(!range.is_empty(), "The local entity range can't be empty.");
+        assert!(
+            range.start <= MAX_LOCAL_RANGE_START,
+            "The local entity range can't start after {}.",
+            MAX_LOCAL_RANGE_START
+        );
+
+        self.materialize_reserved();
+
+        self.local_range = Some(range);
+        self.relink_removed();
+    }
+    /// Returns the range set by [`set_local_range`](Entities::set_local_range).
+    pub fn local_range(&self) -> Option<Range<u64>> {
+        self.local_range.clone()
+    }
+    /// Maps `remote`, an entity of another `World`, to the local entity `local`.\
+    /// Returns the local entity `remote` was previously mapped to.
+    ///
+    /// The mapping is removed when `local` is deleted.
+    pub fn map_remote(&mut self, remote: EntityId, local: EntityId) -> Option<EntityId> {
+        let previous = self.remote_to_local.insert(remote, local);
+        if let Some(previous) = previous {
+            self.local_to_remote.remove(&previous);
+        }
+
+        if let Some(previous_remote) = self.local_to_remote.insert(local, remote) {
+            if previous_remote != remote {
+                self.remote_to_local.remove(&previous_remote);
+            }
+        }
+
+        previous
+    }
+    /// Removes the mapping of `remote` and returns the local entity it was mapped to.
+    pub fn unmap_remote(&mut self, remote: EntityId) -> Option<EntityId> {
+        let local = self.remote_to_local.remove(&remote)?;
+        self.local_to_remote.remove(&local);
 
-            if self.data.len() - old_len > 1 {
-                // add to removed list
-                if let Some((new, _)) = &mut self.list {
-                    self.data[*new].set_index(old_len as u64);
+        Some(local)
+    }
+    /// Returns the local entity `remote` is mapped to.
+    pub fn local_id(&self, remote: EntityId) -> Option<EntityId> {
+        self.remote_to_local.get(&remote).copied()
+    }
+    /// Returns the remote entity mapped to `local`.
+    pub fn remote_id(&self, local: EntityId) -> Option<EntityId> {
+        self.local_to_remote.get(&local).copied()
+    }
+    /// Returns the local entity `remote` is mapped to.\
+    /// If there is none, creates a new entity and maps `remote` to it.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, EntityId, World};
+    ///
+    /// let world = World::new();
+    /// let mut entities = world.borrow::<EntitiesViewMut>().unwrap();
+    ///
+    /// let remote = EntityId::new_from_index_and_gen(42, 3);
+    /// let local = entities.local_id_or_generate(remote);
+    ///
+    /// assert_eq!(entities.local_id_or_generate(remote), local);
+    /// assert_eq!(entities.remote_id(local), Some(remote));
+    ///
+    /// entities.delete_unchecked(local);
+    /// assert_eq!(entities.local_id(remote), None);
+    /// ```
+    pub fn local_id_or_generate(&mut self, remote: EntityId) -> EntityId {
+        if let Some(local) = self.local_id(remote) {
+            return local;
+        }
+
+        let local = self.generate();
+        self.map_remote(remote, local);
 
-                    *new = entity.uindex() - 1;
+        local
+    }
+    /// Returns the index of the next entity created past the end of `data`.
+    fn fresh_index(&self) -> usize {
+        match &self.local_range {
+            Some(range) => self.data.len().max(range.start as usize),
+            None => self.data.len(),
+        }
+    }
+    /// Fills `data` up to `index` with removed entities outside of the local range.
+    fn pad_to(&mut self, index: usize) {
+        if self.data.len() < index {
+            self.data
+                .resize(index, EntityId::new_from_parts(EntityId::max_index(), 0));
+        }
+    }
+    fn is_local(&self, index: usize) -> bool {
+        match &self.local_range {
+            Some(range) => range.contains(&(index as u64)),
+            None => true,
+        }
+    }
+    /// Panics if `index..index + count` goes past the local range.
+    #[track_caller]
+    fn assert_local_fresh(&self, index: usize, count: usize) {
+        if let Some(range) = &self.local_range {
+            assert!(
+                (index + count) as u64 <= range.end,
+                "The local entity range {:?} is full.",
+                range
+            );
+        }
+    }
+    /// Adds `index` at the end of the removed list.
+    fn push_removed(&mut self, index: usize) {
+        self.data[index].set_index(EntityId::max_index());
+
+        if let Some((new, _)) = &mut self.list {
+            self.data[*new].set_index(index as u64);
+            *new = index;
+        } else {
+            self.list = Some((index, index));
+        }
+    }
+    /// Removes `index` from the removed list if it's part of it.
+    fn unlink(&mut self, index: usize) {
+        let (new, old) = match self.list {
+            Some(list) => list,
+            None => return,
+        };
+
+        if index == old {
+            if new == old {
+                self.list = None;
+            } else {
+                self.list = Some((new, self.data[old].uindex()));
+            }
+
+            return;
+        }
+
+        let mut current_index = old;
+        while current_index != new {
+            let next_index = self.data[current_index].uindex();
+
+            if next_index == index {
+                if index == new {
+                    self.data[current_index].set_index(EntityId::max_index());
+                    self.list = Some((current_index, old));
                 } else {
-                    self.list = Some((entity.uindex() - 1, old_len));
+                    let after_index = self.data[index].index();
+                    self.data[current_index].set_index(after_index);
                 }
 
-                for (e, index) in self.data[old_len..entity.uindex() - 1]
-                    .iter_mut()
-                    .zip(old_len as u64 + 1..)
-                {
-                    e.set_index(index);
+                return;
+            }
+
+            current_index = next_index;
+        }
+    }
+    /// Rebuilds the removed list without the indices outside of the local range.
+    fn relink_removed(&mut self) {
+        let mut removed = Vec::new();
+
+        if let Some((new, old)) = self.list.take() {
+            let mut index = old;
+
+            loop {
+                removed.push(index);
+
+                if index == new {
+                    break;
                 }
 
-                self.data[entity.uindex() - 1].set_index(EntityId::max_index());
+                index = self.data[index].uindex();
             }
+        }
 
-            self.data[entity.uindex()] = entity;
-
-            true
+        for index in removed {
+            if self.is_local(index) {
+                self.push_removed(index);
+            } else {
+                self.data[index].set_index(EntityId::max_index());
+            }
         }
     }
 
@@ -384,10 +739,33 @@ impl Entities {
     pub fn take_on_deletion(&mut self) -> Option<Box<dyn FnMut(EntityId) + Send + Sync + 'static>> {
         self.on_deletion.take()
     }
+
+    /// When enabled, an index whose generation reached its maximum starts again at generation 0 instead of dying.\
+    /// Indices are never leaked but an old [`EntityId`] can match a new entity after a full generation wrap.
+    ///
+    /// Disabled by default.
+    pub fn set_generation_wrap(&mut self, wrap_generation: bool) {
+        self.wrap_generation = wrap_generation;
+    }
+}
+
+/// Bumps `entity_id`'s generation, wrapping it if `wrap_generation` is `true`.\
+/// Returns `false` if the entity is dead.
+fn bump_gen(entity_id: &mut EntityId, wrap_generation: bool) -> bool {
+    if entity_id.bump_gen().is_ok() {
+        true
+    } else if wrap_generation {
+        entity_id.wrap_gen();
+        true
+    } else {
+        false
+    }
 }
 
 impl Storage for Entities {
     fn clear(&mut self, _current: TrackingTimestamp) {
+        self.materialize_reserved();
+
         if self.data.is_empty() {
             return;
         }
@@ -397,7 +775,7 @@ impl Storage for Entities {
             let target = last_alive;
             let id_before_bump = *id;
 
-            if id.bump_gen().is_ok() {
+            if bump_gen(id, self.wrap_generation) {
                 last_alive = i as u64;
 
                 if let Some(on_deletion) = &mut self.on_deletion {
@@ -419,7 +797,17 @@ impl Storage for Entities {
             .rev()
             .position(|id| id.gen() < EntityId::max_gen())
             .unwrap();
-        self.list = Some((self.data.len() - end - 1, begin));
+        let new = self.data.len() - end - 1;
+        // the newest removed entity doesn't point to a next one
+        self.data[new].set_index(EntityId::max_index());
+        self.list = Some((new, begin));
+
+        if self.local_range.is_some() {
+            self.relink_removed();
+        }
+
+        self.remote_to_local.clear();
+        self.local_to_remote.clear();
     }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
         Some(StorageMemoryUsage {
@@ -484,6 +872,78 @@ fn entities() {
     assert_eq!(dead.gen(), 0);
 }
 
+#[test]
+fn generation_wrap() {
+    let mut entities = Entities::new();
+    entities.set_generation_wrap(true);
+
+    let key0 = entities.generate();
+    let last_key = EntityId::new_from_index_and_gen(0, EntityId::max_gen() - 1);
+    entities.data[0] = last_key;
+
+    assert!(entities.delete_unchecked(last_key));
+    assert!(!entities.is_alive(last_key));
+
+    let key1 = entities.generate();
+    assert_eq!(key1, key0);
+    assert!(entities.is_alive(key1));
+}
+
+#[test]
+fn reserve() {
+    let mut entities = Entities::new();
+
+    let key00 = entities.generate();
+    let key10 = entities.generate();
+    let key20 = entities.generate();
+    entities.delete_unchecked(key00);
+    entities.delete_unchecked(key10);
+    entities.delete_unchecked(key20);
+
+    let key01 = entities.reserve_entity();
+    let key11 = entities.reserve_entity();
+    assert_eq!((key01.index(), key01.gen()), (0, 1));
+    assert_eq!((key11.index(), key11.gen()), (1, 1));
+    assert!(!entities.is_alive(key01));
+
+    entities.materialize_reserved();
+    assert!(entities.is_alive(key01));
+    assert!(entities.is_alive(key11));
+
+    let key21 = entities.reserve_entity();
+    let key30 = entities.reserve_entity();
+    assert_eq!((key21.index(), key21.gen()), (2, 1));
+    assert_eq!((key30.index(), key30.gen()), (3, 0));
+
+    let key40 = entities.generate();
+    assert_eq!((key40.index(), key40.gen()), (4, 0));
+    assert!(entities.is_alive(key21));
+    assert!(entities.is_alive(key30));
+    assert_eq!(entities.list, None);
+}
+
+#[test]
+fn local_range() {
+    let mut entities = Entities::new();
+    entities.set_local_range(1000..2000);
+    assert!(entities.data.is_empty());
+
+    let key0 = entities.reserve_entity();
+    let key1 = entities.generate();
+    assert_eq!(key0.index(), 1000);
+    assert_eq!(key1.index(), 1001);
+    assert!(entities.is_alive(key0));
+    assert_eq!(entities.data.len(), 1002);
+
+    assert_eq!(entities.bulk_generate(2)[0].index(), 1002);
+}
+
+#[test]
+#[should_panic(expected = "The local entity range can't start after")]
+fn local_range_bound() {
+    Entities::new().set_local_range(1 << 32..1 << 33);
+}
+
 #[test]
 fn iterator() {
     let mut entities = Entities::new();
@@ -0,0 +1,178 @@
+use super::{Entities, MAX_LOCAL_RANGE_START};
+use crate::entity_id::EntityId;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize)]
+#[serde(rename = "Entities")]
+struct EntitiesRef<'a> {
+    data: &'a [EntityId],
+    list: Option<(usize, usize)>,
+    wrap_generation: bool,
+    local_range: Option<Range<u64>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Entities")]
+struct EntitiesOwned {
+    data: Vec<EntityId>,
+    list: Option<(usize, usize)>,
+    wrap_generation: bool,
+    local_range: Option<Range<u64>>,
+}
+
+/// Serializes all entities with their generation, the list of removed entities and the generation and local range settings.
+///
+/// Ids reserved with [`Entities::reserve_entity`] but not yet added are serialized as removed.\
+/// The remote mapping and the [`Entities::on_deletion`] callback are not serialized.
+impl Serialize for Entities {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EntitiesRef {
+            data: &self.data,
+            list: self.list,
+            wrap_generation: self.wrap_generation,
+            local_range: self.local_range.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Entities {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let owned = EntitiesOwned::deserialize(deserializer)?;
+
+        if owned
+            .local_range
+            .as_ref()
+            .is_some_and(|local_range| local_range.is_empty())
+        {
+            return Err(de::Error::custom("the local entity range can't be empty"));
+        }
+
+        if owned
+            .local_range
+            .as_ref()
+            .is_some_and(|local_range| local_range.start > MAX_LOCAL_RANGE_START)
+        {
+            return Err(de::Error::custom(format_args!(
+                "the local entity range can't start after {}",
+                MAX_LOCAL_RANGE_START
+            )));
+        }
+
+        if let Some((new, old)) = owned.list {
+            validate_removed_list(&owned.data, new, old, owned.local_range.as_ref())
+                .map_err(de::Error::custom)?;
+        }
+
+        let mut entities = Entities::new();
+        entities.data = owned.data;
+        entities.list = owned.list;
+        entities.wrap_generation = owned.wrap_generation;
+        entities.local_range = owned.local_range;
+
+        Ok(entities)
+    }
+}
+
+/// Walks the removed list from `old` to `new`.\
+/// `Entities` follows it without bound checks, every link has to point to a removed entity and the list can't loop.
+fn validate_removed_list(
+    data: &[EntityId],
+    new: usize,
+    old: usize,
+    local_range: Option<&Range<u64>>,
+) -> Result<(), &'static str> {
+    let mut visited = vec![false; data.len()];
+    let mut index = old;
+
+    loop {
+        let entity = match data.get(index) {
+            Some(entity) => *entity,
+            None => return Err("the removed entities list points outside of the entities"),
+        };
+
+        if visited[index] {
+            return Err("the removed entities list contains a cycle");
+        }
+        visited[index] = true;
+
+        // an alive entity is stored at its own index
+        if entity.uindex() == index {
+            return Err("the removed entities list contains an alive entity");
+        }
+
+        if local_range.is_some_and(|local_range| !local_range.contains(&(index as u64))) {
+            return Err("the removed entities list contains an entity outside of the local range");
+        }
+
+        if index == new {
+            return Ok(());
+        }
+
+        index = entity.uindex();
+    }
+}
+
+#[test]
+fn roundtrip() {
+    let mut entities = Entities::new();
+
+    let entity0 = entities.generate();
+    let entity1 = entities.generate();
+    let entity2 = entities.generate();
+    entities.delete_unchecked(entity1);
+    entities.delete_unchecked(entity0);
+
+    let json = serde_json::to_string(&entities).unwrap();
+    let mut entities: Entities = serde_json::from_str(&json).unwrap();
+
+    assert!(!entities.is_alive(entity0));
+    assert!(!entities.is_alive(entity1));
+    assert!(entities.is_alive(entity2));
+
+    // the removed list is preserved, indices are recycled in the same order
+    let recycled1 = entities.generate();
+    let recycled0 = entities.generate();
+    assert_eq!(recycled1.index(), entity1.index());
+    assert_eq!(recycled1.gen(), 1);
+    assert_eq!(recycled0.index(), entity0.index());
+    assert_eq!(entities.generate().index(), 3);
+
+    // a cleared `Entities` only has removed entities
+    crate::storage::Storage::clear(&mut entities, crate::tracking::TrackingTimestamp::new(0));
+    let json = serde_json::to_string(&entities).unwrap();
+    let entities: Entities = serde_json::from_str(&json).unwrap();
+    assert!(!entities.is_alive(EntityId::new_from_parts(3, 1)));
+
+    assert!(serde_json::from_str::<Entities>(
+        r#"{"data":[],"list":[0,0],"wrap_generation":false,"local_range":null}"#
+    )
+    .is_err());
+}
+
+#[test]
+fn corrupt_removed_list() {
+    // link out of bounds
+    assert!(serde_json::from_str::<Entities>(
+        r#"{"data":[{"index":999,"gen":1},{"index":1,"gen":0}],"list":[1,0],"wrap_generation":false,"local_range":null}"#
+    )
+    .is_err());
+    // alive entity in the list
+    assert!(serde_json::from_str::<Entities>(
+        r#"{"data":[{"index":1,"gen":1},{"index":1,"gen":0}],"list":[1,0],"wrap_generation":false,"local_range":null}"#
+    )
+    .is_err());
+    // cycle that never reaches the newest removed entity
+    assert!(serde_json::from_str::<Entities>(
+        r#"{"data":[{"index":1,"gen":1},{"index":0,"gen":1},{"index":4,"gen":1}],"list":[2,0],"wrap_generation":false,"local_range":null}"#
+    )
+    .is_err());
+    // removed entity outside of the local range
+    assert!(serde_json::from_str::<Entities>(
+        r#"{"data":[{"index":4,"gen":1},{"index":1,"gen":0}],"list":[0,0],"wrap_generation":false,"local_range":{"start":1,"end":2}}"#
+    )
+    .is_err());
+}
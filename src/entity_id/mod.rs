@@ -1,17 +1,29 @@
 #[cfg(feature = "serde1")]
 mod serde;
 
+#[cfg(feature = "compact_id")]
+use core::num::NonZeroU32;
+#[cfg(not(feature = "compact_id"))]
 use core::num::NonZeroU64;
 
+#[cfg(not(feature = "compact_id"))]
+type Inner = u64;
+#[cfg(not(feature = "compact_id"))]
+type NonZeroInner = NonZeroU64;
+#[cfg(feature = "compact_id")]
+type Inner = u32;
+#[cfg(feature = "compact_id")]
+type NonZeroInner = NonZeroU32;
+
 /// Handle to an entity.
-// the id is 64 bits long
-// <- 46 index -> <- 16 gen -> <- 2 meta ->
+///
+/// By default the id is 64 bits long: 48 bits of index and 16 bits of generation.\
+/// With the `compact_id` feature it is 32 bits long: 24 bits of index and 8 bits of generation.
+// <- index -> <- gen ->
 // a generation of !0 is used as a dead entity
-//
-// inserted and modified component are flagged using metadata
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
-pub struct EntityId(pub(super) NonZeroU64);
+pub struct EntityId(pub(super) NonZeroInner);
 
 /// Allows [`EntityId`] to be stored in collections requiring [`Default`], like `TinyVec`.
 impl Default for EntityId {
@@ -22,17 +34,20 @@ impl Default for EntityId {
 
 impl EntityId {
     // Number of bits used by the generation
-    const GEN_LEN: u64 = 16;
-    const INDEX_LEN: u64 = 64 - Self::GEN_LEN;
-    const INDEX_MASK: u64 = !(!0 << Self::INDEX_LEN);
-    const GEN_MASK: u64 = !(!0 >> Self::GEN_LEN);
-    const MAX_GEN: u16 = u16::MAX - 1;
+    #[cfg(not(feature = "compact_id"))]
+    const GEN_LEN: u32 = 16;
+    #[cfg(feature = "compact_id")]
+    const GEN_LEN: u32 = 8;
+    const INDEX_LEN: u32 = Inner::BITS - Self::GEN_LEN;
+    const INDEX_MASK: Inner = !(!0 << Self::INDEX_LEN);
+    const GEN_MASK: Inner = !(!0 >> Self::GEN_LEN);
+    const MAX_GEN: u16 = ((1 << Self::GEN_LEN) - 2) as u16;
 
     /// Returns the index part of the `EntityId`.  
     /// ⚠️ You shouldn't use it to index a storage.
     #[inline]
     pub fn index(self) -> u64 {
-        (self.0.get() & Self::INDEX_MASK) - 1
+        to_u64((self.0.get() & Self::INDEX_MASK) - 1)
     }
     /// Returns the index part of the `EntityId` as an usize.  
     /// ⚠️ You shouldn't use it to index a storage.
@@ -43,10 +58,11 @@ impl EntityId {
     /// Modify the index.
     #[inline]
     pub(crate) fn set_index(&mut self, index: u64) {
-        assert!(index < Self::INDEX_MASK);
+        assert!(index < to_u64(Self::INDEX_MASK));
         // SAFE never zero
-        self.0 =
-            unsafe { NonZeroU64::new_unchecked((index + 1) | (self.0.get() & !Self::INDEX_MASK)) }
+        self.0 = unsafe {
+            NonZeroInner::new_unchecked(from_u64(index + 1) | (self.0.get() & !Self::INDEX_MASK))
+        }
     }
     /// Returns the generation part of the `EntityId`.
     #[inline]
@@ -59,9 +75,9 @@ impl EntityId {
         if self.gen() < Self::MAX_GEN - 1 {
             // SAFE never zero
             self.0 = unsafe {
-                NonZeroU64::new_unchecked(
+                NonZeroInner::new_unchecked(
                     (self.0.get() & !Self::GEN_MASK)
-                        | (((self.gen() + 1) as u64) << Self::INDEX_LEN),
+                        | (Inner::from(self.gen() + 1) << Self::INDEX_LEN),
                 )
             };
             Ok(())
@@ -69,20 +85,26 @@ impl EntityId {
             Err(())
         }
     }
+    /// Resets the generation to 0.
+    #[inline]
+    pub(super) fn wrap_gen(&mut self) {
+        // SAFE the index part is never zero
+        self.0 = unsafe { NonZeroInner::new_unchecked(self.0.get() & !Self::GEN_MASK) };
+    }
     /// Make a new `EntityId` with the given index.
     #[inline]
     pub(crate) fn new(index: u64) -> Self {
-        assert!(index < Self::INDEX_MASK);
+        assert!(index < to_u64(Self::INDEX_MASK));
         // SAFE never zero
-        EntityId(unsafe { NonZeroU64::new_unchecked(index + 1) })
+        EntityId(unsafe { NonZeroInner::new_unchecked(from_u64(index + 1)) })
     }
     #[inline]
     pub(crate) const fn new_from_parts(index: u64, gen: u16) -> Self {
-        assert!(index < Self::INDEX_MASK);
+        assert!(index < to_u64(Self::INDEX_MASK));
         assert!(gen <= Self::max_gen());
 
         EntityId(unsafe {
-            NonZeroU64::new_unchecked((index + 1) | (gen as u64) << Self::INDEX_LEN)
+            NonZeroInner::new_unchecked(from_u64(index + 1) | (gen as Inner) << Self::INDEX_LEN)
         })
     }
     /// Build a new `EntityId` with the given index and generation.
@@ -92,13 +114,13 @@ impl EntityId {
     }
     #[cfg(test)]
     pub(crate) fn zero() -> Self {
-        EntityId(NonZeroU64::new(1).unwrap())
+        EntityId(NonZeroInner::new(1).unwrap())
     }
     /// Returns a dead `EntityId`, it can be used as a null entity.
     #[inline]
     pub fn dead() -> Self {
         // SAFE not zero
-        EntityId(unsafe { NonZeroU64::new_unchecked(!0) })
+        EntityId(unsafe { NonZeroInner::new_unchecked(!0) })
     }
     #[inline]
     pub(crate) fn bucket(self) -> usize {
@@ -110,7 +132,7 @@ impl EntityId {
     }
     #[inline]
     pub(crate) fn max_index() -> u64 {
-        Self::INDEX_MASK - 1
+        to_u64(Self::INDEX_MASK - 1)
     }
     /// Maximum generation of a valid [`EntityId`].
     /// A dead id will be above that.
@@ -125,7 +147,7 @@ impl EntityId {
     #[inline]
     pub(crate) fn copy_index(&mut self, other: EntityId) {
         unsafe {
-            self.0 = NonZeroU64::new_unchecked(
+            self.0 = NonZeroInner::new_unchecked(
                 (self.0.get() & !Self::INDEX_MASK) | (other.0.get() & Self::INDEX_MASK),
             );
         }
@@ -133,7 +155,7 @@ impl EntityId {
     #[inline]
     pub(crate) fn copy_gen(&mut self, other: EntityId) {
         unsafe {
-            self.0 = NonZeroU64::new_unchecked(
+            self.0 = NonZeroInner::new_unchecked(
                 (self.0.get() & !Self::GEN_MASK) | (other.0.get() & Self::GEN_MASK),
             );
         }
@@ -141,21 +163,39 @@ impl EntityId {
     #[inline]
     pub(crate) fn copy_index_gen(&mut self, other: EntityId) {
         unsafe {
-            self.0 = NonZeroU64::new_unchecked(self.0.get() | other.0.get());
+            self.0 = NonZeroInner::new_unchecked(self.0.get() | other.0.get());
         }
     }
     /// Returns `EntityId`'s inner representation.
     #[inline]
     pub fn inner(self) -> u64 {
-        self.0.get()
+        to_u64(self.0.get())
     }
-    /// Build an `EntityId` from its inner representation.
+    /// Build an `EntityId` from its inner representation.\
+    /// Returns `None` if `inner` is zero or doesn't fit in the `EntityId` layout.
     #[inline]
     pub fn from_inner(inner: u64) -> Option<EntityId> {
-        Some(EntityId(NonZeroU64::new(inner)?))
+        if inner > to_u64(Inner::MAX) {
+            return None;
+        }
+
+        Some(EntityId(NonZeroInner::new(from_u64(inner))?))
     }
 }
 
+#[allow(clippy::unnecessary_cast, trivial_numeric_casts)]
+#[inline]
+const fn to_u64(inner: Inner) -> u64 {
+    inner as u64
+}
+
+/// `value` has to fit in `Inner`.
+#[allow(clippy::unnecessary_cast, trivial_numeric_casts)]
+#[inline]
+const fn from_u64(value: u64) -> Inner {
+    value as Inner
+}
+
 impl core::fmt::Debug for EntityId {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if *self == EntityId::dead() {
@@ -1,6 +1,5 @@
-use super::EntityId;
+use super::{EntityId, Inner, NonZeroInner};
 use core::fmt;
-use core::num::NonZeroU64;
 use serde::{
     de::{self, MapAccess, SeqAccess, Visitor},
     ser::SerializeStruct,
@@ -124,9 +123,11 @@ impl<'de> Deserialize<'de> for EntityId {
         if deserializer.is_human_readable() {
             deserializer.deserialize_struct("EntityId", FIELDS, EntityIdVisitor)
         } else {
-            let non_zero: u64 = Deserialize::deserialize(deserializer)?;
+            let non_zero: Inner = Deserialize::deserialize(deserializer)?;
 
-            Ok(EntityId(unsafe { NonZeroU64::new_unchecked(non_zero + 1) }))
+            Ok(EntityId(unsafe {
+                NonZeroInner::new_unchecked(non_zero + 1)
+            }))
         }
     }
 }
@@ -143,7 +144,10 @@ fn serde_json() {
 #[test]
 fn bincode() {
     let bytes = bincode::serialize(&EntityId::new_from_parts(10, 2)).unwrap();
+    #[cfg(not(feature = "compact_id"))]
     assert_eq!(&[10, 0, 0, 0, 0, 0, 2, 0][..], &bytes);
+    #[cfg(feature = "compact_id")]
+    assert_eq!(&[10, 0, 0, 2][..], &bytes);
 
     let entity = bincode::deserialize::<EntityId>(&bytes).unwrap();
     assert_eq!(entity, EntityId::new_from_parts(10, 2));
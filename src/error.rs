@@ -1,11 +1,14 @@
 //! All error types.
 
+#[cfg(feature = "borrow_diagnostics")]
+use crate::atomic_refcell::BorrowHolder;
 use crate::info::TypeInfo;
 use crate::scheduler::Label;
 use crate::storage::StorageId;
 use crate::{entity_id::EntityId, tracking::tracking_fmt};
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::{Debug, Display, Formatter};
 #[cfg(feature = "std")]
@@ -59,6 +62,10 @@ pub enum GetStorage {
         name: Option<&'static str>,
         id: StorageId,
         borrow: Borrow,
+        /// Holders of the storage when the borrow failed.
+        #[cfg(feature = "borrow_diagnostics")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "borrow_diagnostics")))]
+        holders: Vec<BorrowHolder>,
     },
     #[allow(missing_docs)]
     Entities(Borrow),
@@ -94,6 +101,31 @@ impl GetStorage {
     }
 }
 
+impl GetStorage {
+    /// Returns `true` if the error comes from a borrow that will eventually be released.
+    #[cfg(feature = "std")]
+    pub(crate) fn is_borrow_conflict(&self) -> bool {
+        match self {
+            GetStorage::AllStoragesBorrow(borrow)
+            | GetStorage::StorageBorrow { borrow, .. }
+            | GetStorage::Entities(borrow) => matches!(
+                borrow,
+                Borrow::Unique | Borrow::Shared | Borrow::MultipleThreads
+            ),
+            _ => false,
+        }
+    }
+    /// Returns who held the storage when a [`StorageBorrow`](GetStorage::StorageBorrow) error occurred.
+    #[cfg(feature = "borrow_diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "borrow_diagnostics")))]
+    pub fn holders(&self) -> &[BorrowHolder] {
+        match self {
+            GetStorage::StorageBorrow { holders, .. } => holders,
+            _ => &[],
+        }
+    }
+}
+
 impl PartialEq for GetStorage {
     fn eq(&self, other: &GetStorage) -> bool {
         match (self, other) {
@@ -103,11 +135,13 @@ impl PartialEq for GetStorage {
                     name: l_name,
                     id: l_id,
                     borrow: l_borrow,
+                    ..
                 },
                 GetStorage::StorageBorrow {
                     name: r_name,
                     id: r_id,
                     borrow: r_borrow,
+                    ..
                 },
             ) => l_name == r_name && l_id == r_id && l_borrow == r_borrow,
             (GetStorage::Entities(l0), GetStorage::Entities(r0)) => l0 == r0,
@@ -153,24 +187,47 @@ impl Debug for GetStorage {
                 },
                 _ => unreachable!(),
             },
-            GetStorage::StorageBorrow {name, id, borrow} => if let Some(name) = name {
-                match borrow {
-                    Borrow::Unique => f.write_fmt(format_args!("Cannot mutably borrow {} storage while it's already borrowed.", name)),
-                    Borrow::Shared => {
-                        f.write_fmt(format_args!("Cannot immutably borrow {} storage while it's already mutably borrowed.", name))
-                    },
-                    Borrow::MultipleThreads => f.write_fmt(format_args!("Cannot borrow {} storage from multiple thread at the same time because it's !Sync.", name)),
-                    Borrow::WrongThread => f.write_fmt(format_args!("Cannot borrow {} storage from other thread than the one it was created in because it's !Send and !Sync.", name)),
-                }
-            } else {
-                match borrow {
-                    Borrow::Unique => f.write_fmt(format_args!("Cannot mutably borrow {:?} storage while it's already borrowed.", id)),
-                    Borrow::Shared => {
-                        f.write_fmt(format_args!("Cannot immutably borrow {:?} storage while it's already mutably borrowed.", id))
-                    },
-                    Borrow::MultipleThreads => f.write_fmt(format_args!("Cannot borrow {:?} storage from multiple thread at the same time because it's !Sync.", id)),
-                    Borrow::WrongThread => f.write_fmt(format_args!("Cannot borrow {:?} storage from other thread than the one it was created in because it's !Send and !Sync.", id)),
+            GetStorage::StorageBorrow {
+                name, id, borrow, ..
+            } => {
+                if let Some(name) = name {
+                    match borrow {
+                        Borrow::Unique => f.write_fmt(format_args!("Cannot mutably borrow {} storage while it's already borrowed.", name)),
+                        Borrow::Shared => {
+                            f.write_fmt(format_args!("Cannot immutably borrow {} storage while it's already mutably borrowed.", name))
+                        },
+                        Borrow::MultipleThreads => f.write_fmt(format_args!("Cannot borrow {} storage from multiple thread at the same time because it's !Sync.", name)),
+                        Borrow::WrongThread => f.write_fmt(format_args!("Cannot borrow {} storage from other thread than the one it was created in because it's !Send and !Sync.", name)),
+                    }
+                } else {
+                    match borrow {
+                        Borrow::Unique => f.write_fmt(format_args!("Cannot mutably borrow {:?} storage while it's already borrowed.", id)),
+                        Borrow::Shared => {
+                            f.write_fmt(format_args!("Cannot immutably borrow {:?} storage while it's already mutably borrowed.", id))
+                        },
+                        Borrow::MultipleThreads => f.write_fmt(format_args!("Cannot borrow {:?} storage from multiple thread at the same time because it's !Sync.", id)),
+                        Borrow::WrongThread => f.write_fmt(format_args!("Cannot borrow {:?} storage from other thread than the one it was created in because it's !Send and !Sync.", id)),
+                    }
+                }?;
+
+                #[cfg(feature = "borrow_diagnostics")]
+                let holders = self.holders();
+                #[cfg(feature = "borrow_diagnostics")]
+                if !holders.is_empty() {
+                    f.write_str(" Held by: ")?;
+
+                    for (i, holder) in holders.iter().enumerate() {
+                        if i != 0 {
+                            f.write_str(", ")?;
+                        }
+
+                        Display::fmt(holder, f)?;
+                    }
+
+                    f.write_str(".")?;
                 }
+
+                Ok(())
             }
             GetStorage::Entities(borrow) => match borrow {
                 Borrow::Unique => f.write_str("Cannot mutably borrow Entities storage while it's already borrowed."),
@@ -386,6 +443,88 @@ impl Display for AddWorkload {
     }
 }
 
+/// Error returned by [`World::add_plugin`].
+///
+/// [`World::add_plugin`]: crate::World::add_plugin()
+pub enum AddPlugin {
+    /// A plugin of the same type was already added to the `World`.
+    AlreadyAdded(&'static str),
+    /// A plugin required by this one is not present in the `World`.
+    MissingDependency {
+        #[allow(missing_docs)]
+        plugin: &'static str,
+        #[allow(missing_docs)]
+        dependency: &'static str,
+    },
+    /// Error returned by the plugin's [`build`](crate::Plugin::build).
+    #[cfg(feature = "std")]
+    Custom(Box<dyn Error + Send + Sync>),
+    /// Error returned by the plugin's [`build`](crate::Plugin::build).
+    #[cfg(not(feature = "std"))]
+    Custom(Box<dyn core::any::Any + Send>),
+}
+
+impl AddPlugin {
+    #[cfg(feature = "std")]
+    #[allow(missing_docs)]
+    pub fn from_custom<E: Into<Box<dyn Error + Send + Sync>>>(error: E) -> AddPlugin {
+        AddPlugin::Custom(error.into())
+    }
+    #[cfg(not(feature = "std"))]
+    #[allow(missing_docs)]
+    pub fn from_custom<E: core::any::Any + Send>(error: E) -> AddPlugin {
+        AddPlugin::Custom(Box::new(error))
+    }
+}
+
+impl PartialEq for AddPlugin {
+    fn eq(&self, other: &AddPlugin) -> bool {
+        match (self, other) {
+            (AddPlugin::AlreadyAdded(l_plugin), AddPlugin::AlreadyAdded(r_plugin)) => {
+                l_plugin == r_plugin
+            }
+            (
+                AddPlugin::MissingDependency {
+                    plugin: l_plugin,
+                    dependency: l_dependency,
+                },
+                AddPlugin::MissingDependency {
+                    plugin: r_plugin,
+                    dependency: r_dependency,
+                },
+            ) => l_plugin == r_plugin && l_dependency == r_dependency,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for AddPlugin {}
+
+impl Debug for AddPlugin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            AddPlugin::AlreadyAdded(plugin) => {
+                f.write_fmt(format_args!("{} plugin was already added.", plugin))
+            }
+            AddPlugin::MissingDependency { plugin, dependency } => f.write_fmt(format_args!(
+                "{} plugin requires {} plugin to be added first.",
+                plugin, dependency
+            )),
+            AddPlugin::Custom(err) => f.write_fmt(format_args!(
+                "plugin failed to build with a custom error, {:?}.",
+                err
+            )),
+        }
+    }
+}
+
+impl Display for AddPlugin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
 /// Trying to set the default workload to a non existent one will result in this error.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum SetDefaultWorkload {
@@ -601,6 +740,44 @@ impl Display for InvalidSystem {
     }
 }
 
+/// Error returned by [`SystemRegistry::load_workload`].
+///
+/// [`SystemRegistry::load_workload`]: crate::SystemRegistry::load_workload()
+#[derive(Clone, PartialEq, Eq)]
+pub enum LoadWorkload {
+    /// No system is registered under this name.
+    MissingSystem(String),
+    /// No run condition is registered under this name.
+    MissingRunIf(String),
+    /// The system or run condition registered under this name is invalid.
+    InvalidSystem(String, InvalidSystem),
+}
+
+#[cfg(feature = "std")]
+impl Error for LoadWorkload {}
+
+impl Debug for LoadWorkload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            LoadWorkload::MissingSystem(name) => {
+                f.write_fmt(format_args!("No system registered as \"{}\".", name))
+            }
+            LoadWorkload::MissingRunIf(name) => {
+                f.write_fmt(format_args!("No run_if registered as \"{}\".", name))
+            }
+            LoadWorkload::InvalidSystem(name, err) => {
+                f.write_fmt(format_args!("\"{}\" is invalid: {:?}", name, err))
+            }
+        }
+    }
+}
+
+impl Display for LoadWorkload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
 /// Error returned by [`World::remove_unique`] and [`AllStorages::remove_unique`].
 ///
 /// [`World::remove_unique`]: crate::World::remove_unique()
@@ -762,6 +939,51 @@ impl Display for CustomStorageView {
     }
 }
 
+/// Error returned by [`World::add_dynamic_storage`].
+///
+/// [`World::add_dynamic_storage`]: crate::World::add_dynamic_storage()
+pub enum AddDynamicStorage {
+    #[allow(missing_docs)]
+    CustomStorageView(CustomStorageView),
+    /// The storage already exists with a different layout, drop or clone function.
+    DifferentInfo(Cow<'static, str>),
+}
+
+impl From<CustomStorageView> for AddDynamicStorage {
+    fn from(custom_storage_view: CustomStorageView) -> AddDynamicStorage {
+        AddDynamicStorage::CustomStorageView(custom_storage_view)
+    }
+}
+
+impl From<GetStorage> for AddDynamicStorage {
+    fn from(get_storage: GetStorage) -> AddDynamicStorage {
+        AddDynamicStorage::CustomStorageView(CustomStorageView::GetStorage(get_storage))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for AddDynamicStorage {}
+
+impl Debug for AddDynamicStorage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            AddDynamicStorage::CustomStorageView(custom_storage_view) => {
+                Debug::fmt(custom_storage_view, f)
+            }
+            AddDynamicStorage::DifferentInfo(name) => f.write_fmt(format_args!(
+                "A storage for {:?} components already exists with a different description.",
+                name
+            )),
+        }
+    }
+}
+
+impl Display for AddDynamicStorage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
 /// Returned when requirements for a workload make it impossible to build a valid workload.
 #[derive(Clone, Eq)]
 pub enum ImpossibleRequirements {
@@ -857,3 +1079,43 @@ impl Display for GetComponent {
         Debug::fmt(self, f)
     }
 }
+
+/// Returned by [`Entities::try_spawn`], [`AllStorages::try_spawn`] and [`World::try_spawn`].
+///
+/// [`Entities::try_spawn`]: crate::Entities::try_spawn
+/// [`AllStorages::try_spawn`]: crate::AllStorages::try_spawn
+/// [`World::try_spawn`]: crate::World::try_spawn
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Spawn {
+    /// The index is part of the local range, only entities created by this `World` can use it.
+    LocalIndex,
+    /// An entity is already alive at this index.
+    AliveEntity(EntityId),
+    /// This index was already used by an entity with a greater generation.
+    OutdatedGeneration,
+}
+
+#[cfg(feature = "std")]
+impl Error for Spawn {}
+
+impl Debug for Spawn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Spawn::LocalIndex => {
+                f.write_str("Entity index is part of the local range, it can't be spawned.")
+            }
+            Spawn::AliveEntity(entity) => {
+                f.write_fmt(format_args!("{:?} is alive at this index.", entity))
+            }
+            Spawn::OutdatedGeneration => f.write_str(
+                "Entity generation is older than the one of the last entity at this index.",
+            ),
+        }
+    }
+}
+
+impl Display for Spawn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
@@ -0,0 +1,101 @@
+/// Runs the systems of a workload batch.
+///
+/// Workloads are split into batches of systems that can run at the same time.\
+/// For each batch, the [`World`](crate::World) hands the systems to its executor and waits for [`Executor::execute`] to return.
+///
+/// With the `parallel` feature, [`RayonExecutor`] is used by default and [`rayon::ThreadPool`] can be used as a local executor.\
+/// Without it, workloads run sequentially unless an executor is provided with [`WorldBuilder::with_executor`](crate::WorldBuilder::with_executor).
+///
+/// ### Limitations
+///
+/// Only systems are dispatched through the executor.\
+/// `par_iter` inside a system uses rayon's current pool: the [`ThreadPool`](rayon::ThreadPool) the system runs in, rayon's global pool when a custom executor runs it outside of rayon.
+///
+/// ### Example
+///
+/// ```
+/// use shipyard::{Component, Executor, View, Workload, World};
+///
+/// #[derive(Component)]
+/// struct U32(u32);
+///
+/// struct JobSystem;
+///
+/// impl Executor for JobSystem {
+///     fn execute(&self, local: &mut (dyn FnMut() + Send), tasks: &mut [&mut (dyn FnMut() + Send)]) {
+///         // Dispatch `tasks` to the engine's job system here
+///         for task in tasks {
+///             task();
+///         }
+///
+///         local();
+///     }
+/// }
+///
+/// let world = World::builder().with_executor(JobSystem).build();
+///
+/// Workload::new("")
+///     .with_system(|u32s: View<U32>| {})
+///     .with_system(|u32s: View<U32>| {})
+///     .add_to_world(&world)
+///     .unwrap();
+///
+/// world.run_default().unwrap();
+/// ```
+pub trait Executor: Send + Sync {
+    /// Runs `local` and all `tasks` then returns once they all completed.
+    ///
+    /// `local` has to run on the current thread, it can borrow `!Send` storages.\
+    /// `tasks` can run on any thread, in any order and at the same time.
+    fn execute(&self, local: &mut (dyn FnMut() + Send), tasks: &mut [&mut (dyn FnMut() + Send)]);
+}
+
+/// Runs all systems one after the other on the current thread.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct SequentialExecutor;
+
+impl Executor for SequentialExecutor {
+    fn execute(&self, local: &mut (dyn FnMut() + Send), tasks: &mut [&mut (dyn FnMut() + Send)]) {
+        for task in tasks {
+            task();
+        }
+
+        local();
+    }
+}
+
+/// Runs systems on rayon's global [`ThreadPool`](rayon::ThreadPool).\
+/// This is the default executor when the `parallel` feature is enabled.
+#[cfg(feature = "parallel")]
+#[derive(Default, Clone, Copy, Debug)]
+pub struct RayonExecutor;
+
+#[cfg(feature = "parallel")]
+impl Executor for RayonExecutor {
+    fn execute(&self, local: &mut (dyn FnMut() + Send), tasks: &mut [&mut (dyn FnMut() + Send)]) {
+        rayon::in_place_scope(|scope| {
+            for task in tasks {
+                scope.spawn(move |_| task());
+            }
+
+            local();
+        });
+    }
+}
+
+/// Runs the whole batch inside the [`ThreadPool`](rayon::ThreadPool), like [`WorldBuilder::with_local_thread_pool`](crate::WorldBuilder::with_local_thread_pool).
+///
+/// `local` runs on one of the pool's threads while the calling thread waits.
+/// With the `thread_local` feature, it can't borrow `!Send` storages.
+#[cfg(feature = "parallel")]
+impl Executor for rayon::ThreadPool {
+    fn execute(&self, local: &mut (dyn FnMut() + Send), tasks: &mut [&mut (dyn FnMut() + Send)]) {
+        self.scope(|scope| {
+            for task in tasks {
+                scope.spawn(move |_| task());
+            }
+
+            local();
+        });
+    }
+}
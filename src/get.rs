@@ -86,10 +86,13 @@ impl<'a, 'b, T: Component, TRACK> Get for &'b mut ViewMut<'a, T, TRACK> {
                 name: type_name::<T>(),
             })?;
 
+        self.sparse_set.flush_changes();
+
         let SparseSet {
             data,
             modification_data,
             is_tracking_modification,
+            change_log,
             ..
         } = self.sparse_set;
 
@@ -98,6 +101,7 @@ impl<'a, 'b, T: Component, TRACK> Get for &'b mut ViewMut<'a, T, TRACK> {
                 .then(|| unsafe { modification_data.get_unchecked_mut(index) }),
             current: self.current,
             data: unsafe { data.get_unchecked_mut(index) },
+            log: change_log.slot(index),
         })
     }
 }
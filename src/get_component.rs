@@ -6,7 +6,7 @@ use crate::borrow::{NonSend, NonSendSync, NonSync};
 use crate::component::Component;
 use crate::entity_id::EntityId;
 use crate::error;
-use crate::sparse_set::SparseSet;
+use crate::sparse_set::{LogSlot, SparseSet};
 use crate::tracking::TrackingTimestamp;
 use core::any::type_name;
 use core::ops::{Deref, DerefMut};
@@ -53,6 +53,7 @@ pub struct RefMut<'a, T> {
     inner: T,
     flag: Option<&'a mut TrackingTimestamp>,
     current: TrackingTimestamp,
+    log: Option<LogSlot<'a>>,
     all_borrow: Option<SharedBorrow<'a>>,
     borrow: ExclusiveBorrow<'a>,
 }
@@ -67,6 +68,7 @@ impl<'a, T> RefMut<'a, T> {
             inner: f(orig.inner),
             flag: orig.flag,
             current: orig.current,
+            log: orig.log,
             all_borrow: orig.all_borrow,
             borrow: orig.borrow,
         }
@@ -95,6 +97,10 @@ impl<'a, T> DerefMut for RefMut<'a, T> {
         if let Some(flag) = &mut self.flag {
             **flag = self.current;
         }
+        if let Some(log) = self.log.take() {
+            // SAFE the component was linked early when this `RefMut` was made
+            unsafe { log.log(core::ptr::null()) };
+        }
 
         &mut self.inner
     }
@@ -106,11 +112,27 @@ impl<'a, T> AsMut<T> for RefMut<'a, T> {
         if let Some(flag) = &mut self.flag {
             **flag = self.current;
         }
+        if let Some(log) = self.log.take() {
+            // SAFE the component was linked early when this `RefMut` was made
+            unsafe { log.log(core::ptr::null()) };
+        }
 
         &mut self.inner
     }
 }
 
+/// [`RefMut::map`] can turn the component into any type, it's linked to the change log before that.
+fn linked_early<'a, T>(mut log: Option<LogSlot<'a>>, component: &mut T) -> Option<LogSlot<'a>> {
+    if let Some(log) = &mut log {
+        let component: *const T = component;
+
+        // SAFE `component` is the one at the slot's index
+        unsafe { log.link_early(component.cast()) };
+    }
+
+    log
+}
+
 /// Trait used as bound for [`World::get`] and [`AllStorages::get`].
 ///
 /// [`World::get`]: crate::World::get
@@ -274,15 +296,21 @@ impl<T: Component + Send + Sync> GetComponent for &'_ mut T {
                 name: type_name::<T>(),
             })?;
 
+        sparse_set.flush_changes();
+
         let SparseSet {
             data,
             modification_data,
             is_tracking_modification,
+            change_log,
             ..
         } = sparse_set;
 
+        let component = unsafe { data.get_unchecked_mut(index) };
+
         Ok(RefMut {
-            inner: unsafe { data.get_unchecked_mut(index) },
+            log: linked_early(change_log.slot(index), component),
+            inner: component,
             flag: is_tracking_modification
                 .then(|| unsafe { modification_data.get_unchecked_mut(index) }),
             current,
@@ -315,15 +343,21 @@ impl<T: Component + Sync> GetComponent for NonSend<&'_ mut T> {
                 name: type_name::<T>(),
             })?;
 
+        sparse_set.flush_changes();
+
         let NonSend(SparseSet {
             data,
             modification_data,
             is_tracking_modification,
+            change_log,
             ..
         }) = sparse_set;
 
+        let component = unsafe { data.get_unchecked_mut(index) };
+
         Ok(RefMut {
-            inner: unsafe { data.get_unchecked_mut(index) },
+            log: linked_early(change_log.slot(index), component),
+            inner: component,
             flag: is_tracking_modification
                 .then(|| unsafe { modification_data.get_unchecked_mut(index) }),
             current,
@@ -356,15 +390,21 @@ impl<T: Component + Send> GetComponent for NonSync<&'_ mut T> {
                 name: type_name::<T>(),
             })?;
 
+        sparse_set.flush_changes();
+
         let NonSync(SparseSet {
             data,
             modification_data,
             is_tracking_modification,
+            change_log,
             ..
         }) = sparse_set;
 
+        let component = unsafe { data.get_unchecked_mut(index) };
+
         Ok(RefMut {
-            inner: unsafe { data.get_unchecked_mut(index) },
+            log: linked_early(change_log.slot(index), component),
+            inner: component,
             flag: is_tracking_modification
                 .then(|| unsafe { modification_data.get_unchecked_mut(index) }),
             current,
@@ -397,15 +437,21 @@ impl<T: Component> GetComponent for NonSendSync<&'_ mut T> {
                 name: type_name::<T>(),
             })?;
 
+        sparse_set.flush_changes();
+
         let NonSendSync(SparseSet {
             data,
             modification_data,
             is_tracking_modification,
+            change_log,
             ..
         }) = sparse_set;
 
+        let component = unsafe { data.get_unchecked_mut(index) };
+
         Ok(RefMut {
-            inner: unsafe { data.get_unchecked_mut(index) },
+            log: linked_early(change_log.slot(index), component),
+            inner: component,
             flag: is_tracking_modification
                 .then(|| unsafe { modification_data.get_unchecked_mut(index) }),
             current,
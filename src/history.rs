@@ -0,0 +1,227 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::sparse_set::{ChangeReader, SparseSet};
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Undo/redo history of the components registered with [`World::record_history`](crate::World::record_history).
+///
+/// Registered storages copy a component right before it's modified, removed or deleted.\
+/// On commit, the first copy of each entity becomes its before-image in the transaction,
+/// an entity without copy gained its component during the transaction.
+pub struct History {
+    recorders: Vec<Box<dyn Recorder>>,
+    /// Indices of the recorders taking part in each transaction.
+    undo: Vec<Vec<usize>>,
+    redo: Vec<Vec<usize>>,
+}
+
+impl History {
+    pub(crate) fn new() -> History {
+        History {
+            recorders: Vec::new(),
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Starts recording `T` components, does nothing if they are already recorded.
+    pub(crate) fn register<T: Component + Clone + Send + Sync>(
+        &mut self,
+        sparse_set: &mut SparseSet<T>,
+    ) {
+        let storage_id = StorageId::of::<SparseSet<T>>();
+
+        if self
+            .recorders
+            .iter()
+            .any(|recorder| recorder.storage_id() == storage_id)
+        {
+            return;
+        }
+
+        self.recorders.push(Box::new(ComponentRecorder::<T> {
+            reader: sparse_set.add_image_reader(),
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }));
+    }
+
+    /// Groups the changes made since the last commit into a transaction.\
+    /// Returns `false` if there wasn't any change.
+    pub(crate) fn commit(&mut self, all_storages: &AllStorages) -> bool {
+        let transaction: Vec<usize> = self
+            .recorders
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, recorder)| recorder.commit(all_storages).then_some(index))
+            .collect();
+
+        if transaction.is_empty() {
+            return false;
+        }
+
+        for recorder in &mut self.recorders {
+            recorder.clear_redo();
+        }
+        self.redo.clear();
+        self.undo.push(transaction);
+
+        true
+    }
+
+    /// Reverts the last committed transaction.\
+    /// Returns `false` if there is nothing to undo.
+    pub(crate) fn undo(&mut self, all_storages: &AllStorages, current: TrackingTimestamp) -> bool {
+        match self.undo.pop() {
+            Some(transaction) => {
+                for &index in transaction.iter().rev() {
+                    self.recorders[index].undo(all_storages, current);
+                }
+
+                self.redo.push(transaction);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reapplies the last undone transaction.\
+    /// Returns `false` if there is nothing to redo.
+    pub(crate) fn redo(&mut self, all_storages: &AllStorages, current: TrackingTimestamp) -> bool {
+        match self.redo.pop() {
+            Some(transaction) => {
+                for &index in &transaction {
+                    self.recorders[index].redo(all_storages, current);
+                }
+
+                self.undo.push(transaction);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of transactions that can be undone.
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Returns the number of transactions that can be redone.
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+}
+
+impl Storage for History {
+    fn is_empty(&self) -> bool {
+        self.undo.is_empty() && self.redo.is_empty()
+    }
+}
+
+/// Type erased [`ComponentRecorder`].
+trait Recorder: Send + Sync {
+    fn storage_id(&self) -> StorageId;
+    /// Records the before-images of the components changed since the last commit.\
+    /// Returns `false` if there wasn't any change.
+    fn commit(&mut self, all_storages: &AllStorages) -> bool;
+    fn undo(&mut self, all_storages: &AllStorages, current: TrackingTimestamp);
+    fn redo(&mut self, all_storages: &AllStorages, current: TrackingTimestamp);
+    fn clear_redo(&mut self);
+}
+
+/// Before-images of a transaction, `None` when the entity didn't have the component.
+type Images<T> = Vec<(EntityId, Option<T>)>;
+
+struct ComponentRecorder<T: Component> {
+    reader: ChangeReader,
+    undo: Vec<Images<T>>,
+    redo: Vec<Images<T>>,
+}
+
+impl<T: Component + Clone + Send + Sync> ComponentRecorder<T> {
+    /// Swaps the components of `images` with the ones in the storage.\
+    /// Returns the images of the replaced components.
+    fn swap(
+        &mut self,
+        all_storages: &AllStorages,
+        images: Images<T>,
+        current: TrackingTimestamp,
+    ) -> Images<T> {
+        let (mut entities, mut sparse_set) = match (
+            all_storages.entities_mut(),
+            all_storages.custom_storage_mut::<SparseSet<T>>(),
+        ) {
+            (Ok(entities), Ok(sparse_set)) => (entities, sparse_set),
+            _ => return images,
+        };
+
+        let replaced = images
+            .into_iter()
+            .map(|(entity, image)| {
+                let replaced = match image {
+                    Some(component) => sparse_set
+                        .insert_spawned(&mut entities, entity, component, current)
+                        .flatten(),
+                    None => sparse_set.dyn_remove(entity, current),
+                };
+
+                (entity, replaced)
+            })
+            .collect();
+
+        // undo and redo don't start a new transaction
+        sparse_set.read_images(self.reader, |_, _| {});
+
+        replaced
+    }
+}
+
+impl<T: Component + Clone + Send + Sync> Recorder for ComponentRecorder<T> {
+    fn storage_id(&self) -> StorageId {
+        StorageId::of::<SparseSet<T>>()
+    }
+    fn commit(&mut self, all_storages: &AllStorages) -> bool {
+        let mut sparse_set = match all_storages.custom_storage_mut::<SparseSet<T>>() {
+            Ok(sparse_set) => sparse_set,
+            Err(_) => return false,
+        };
+
+        let mut images = Vec::new();
+        sparse_set.read_images(self.reader, |entity, image| images.push((entity, image)));
+
+        // the sort is stable, each entity keeps the image it had before its first change
+        images.sort_by_key(|&(entity, _)| entity);
+        images.dedup_by_key(|&mut (entity, _)| entity);
+        // inserted then removed during the transaction
+        images.retain(|(entity, image)| image.is_some() || sparse_set.contains(*entity));
+
+        if images.is_empty() {
+            false
+        } else {
+            self.undo.push(images);
+
+            true
+        }
+    }
+    fn undo(&mut self, all_storages: &AllStorages, current: TrackingTimestamp) {
+        if let Some(images) = self.undo.pop() {
+            let images = self.swap(all_storages, images, current);
+            self.redo.push(images);
+        }
+    }
+    fn redo(&mut self, all_storages: &AllStorages, current: TrackingTimestamp) {
+        if let Some(images) = self.redo.pop() {
+            let images = self.swap(all_storages, images, current);
+            self.undo.push(images);
+        }
+    }
+    fn clear_redo(&mut self) {
+        self.redo.clear();
+    }
+}
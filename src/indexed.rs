@@ -0,0 +1,267 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::atomic_refcell::{ARefMut, SharedBorrow};
+use crate::borrow::{Borrow, BorrowInfo, Mutability};
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::memory_usage::StorageMemoryUsage;
+use crate::scheduler::TypeInfo;
+use crate::sparse_set::{ChangeReader, SparseSet};
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::views::View;
+use crate::ShipHashMap;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::hash::{BuildHasherDefault, Hash};
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ops::Deref;
+
+/// Components that can be looked up by key with [`World::find_by`](crate::World::find_by).
+///
+/// ### Example
+///
+/// ```
+/// use shipyard::{Component, IndexKey, World};
+///
+/// #[derive(Component)]
+/// struct Name(String);
+///
+/// impl IndexKey for Name {
+///     type Key = String;
+///
+///     fn index_key(&self) -> String {
+///         self.0.clone()
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.add_index::<Name>();
+///
+/// let player = world.add_entity(Name("player".to_string()));
+///
+/// assert_eq!(
+///     world.find_by::<Name>(&"player".to_string()).unwrap(),
+///     Some(player)
+/// );
+/// ```
+pub trait IndexKey: Component {
+    /// Type used to look up entities.
+    type Key: Hash + Eq + Clone + Send + Sync + 'static;
+
+    /// Returns the key of this component.
+    fn index_key(&self) -> Self::Key;
+}
+
+/// Secondary index of `T` components, created by [`World::add_index`](crate::World::add_index).
+///
+/// The index reads the entities whose `T` component changed from the storage's change log,
+/// it only catches up when borrowed through an [`IndexedView`].
+pub struct Indexed<T: IndexKey> {
+    entities: ShipHashMap<T::Key, Vec<EntityId>>,
+    keys: ShipHashMap<EntityId, T::Key>,
+    reader: ChangeReader,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: IndexKey> Indexed<T> {
+    /// Indexes all components currently in `sparse_set`.
+    pub(crate) fn new(sparse_set: &mut SparseSet<T>) -> Indexed<T> {
+        let mut indexed = Indexed {
+            entities: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            keys: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            reader: sparse_set.add_change_reader(),
+            _phantom: PhantomData,
+        };
+
+        indexed.rebuild(sparse_set);
+
+        indexed
+    }
+
+    /// Applies the changes made to `sparse_set` since the last sync.
+    pub(crate) fn sync(&mut self, sparse_set: &SparseSet<T>) {
+        let synced = sparse_set.read_changes(self.reader, |entity, component| match component {
+            Some(component) => self.insert(entity, component.index_key()),
+            None => self.remove(entity),
+        });
+
+        if !synced {
+            self.rebuild(sparse_set);
+        }
+    }
+
+    /// Returns an entity with a `key` component.
+    pub fn find(&self, key: &T::Key) -> Option<EntityId> {
+        self.entities
+            .get(key)
+            .and_then(|entities| entities.first().copied())
+    }
+
+    /// Returns all entities with a `key` component.
+    pub fn find_all(&self, key: &T::Key) -> &[EntityId] {
+        self.entities.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if no entity is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    fn rebuild(&mut self, sparse_set: &SparseSet<T>) {
+        self.entities.clear();
+        self.keys.clear();
+
+        for (&entity, component) in sparse_set.dense.iter().zip(&sparse_set.data) {
+            self.insert(entity, component.index_key());
+        }
+    }
+
+    fn insert(&mut self, entity: EntityId, key: T::Key) {
+        if self.keys.get(&entity) == Some(&key) {
+            return;
+        }
+
+        self.remove(entity);
+
+        self.entities.entry(key.clone()).or_default().push(entity);
+        self.keys.insert(entity, key);
+    }
+
+    fn remove(&mut self, entity: EntityId) {
+        if let Some(key) = self.keys.remove(&entity) {
+            if let Some(entities) = self.entities.get_mut(&key) {
+                entities.retain(|&indexed| indexed != entity);
+
+                if entities.is_empty() {
+                    self.entities.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+impl<T: IndexKey> Storage for Indexed<T> {
+    #[inline]
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        self.remove(entity);
+    }
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        self.entities.clear();
+        self.keys.clear();
+    }
+    fn memory_usage(&self) -> Option<StorageMemoryUsage> {
+        let entry_size = size_of::<T::Key>() + size_of::<EntityId>();
+
+        Some(StorageMemoryUsage {
+            storage_name: type_name::<Self>().into(),
+            allocated_memory_bytes: size_of::<Self>()
+                + (self.entities.capacity() * (entry_size + size_of::<Vec<EntityId>>()))
+                + (self.keys.capacity() * entry_size),
+            used_memory_bytes: size_of::<Self>()
+                + (self.entities.len() * (entry_size + size_of::<Vec<EntityId>>()))
+                + (self.keys.len() * entry_size),
+            component_count: self.keys.len(),
+        })
+    }
+    fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// Shared view over `T` components that can also look them up by key, usable in systems.
+///
+/// Borrowing the view brings `T`'s [`Indexed`] up to date, see [`World::add_index`](crate::World::add_index).
+///
+/// ### Example
+///
+/// ```
+/// use shipyard::{Component, IndexKey, IndexedView, World};
+///
+/// #[derive(Component)]
+/// struct Name(String);
+///
+/// impl IndexKey for Name {
+///     type Key = String;
+///
+///     fn index_key(&self) -> String {
+///         self.0.clone()
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.add_index::<Name>();
+///
+/// let player = world.add_entity(Name("player".to_string()));
+///
+/// world.run(|names: IndexedView<Name>| {
+///     assert_eq!(names.find(&"player".to_string()), Some(player));
+///     assert_eq!(names[player].0, "player");
+/// });
+/// ```
+pub struct IndexedView<'v, T: IndexKey> {
+    view: View<'v, T>,
+    indexed: ARefMut<'v, &'v mut Indexed<T>>,
+}
+
+impl<T: IndexKey> IndexedView<'_, T> {
+    /// Returns an entity with a `key` component.
+    /// If multiple entities share the same key, any of them can be returned.
+    pub fn find(&self, key: &T::Key) -> Option<EntityId> {
+        self.indexed.find(key)
+    }
+
+    /// Returns all entities with a `key` component.
+    pub fn find_all(&self, key: &T::Key) -> &[EntityId] {
+        self.indexed.find_all(key)
+    }
+}
+
+impl<'v, T: IndexKey> Deref for IndexedView<'v, T> {
+    type Target = View<'v, T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+impl<T: IndexKey + Send + Sync> Borrow for IndexedView<'_, T> {
+    type View<'a> = IndexedView<'a, T>;
+
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        last_run: Option<TrackingTimestamp>,
+        current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        let view = View::<T>::borrow(all_storages, all_borrow, last_run, current)?;
+        let mut indexed = all_storages.custom_storage_mut::<Indexed<T>>()?;
+
+        indexed.sync(&view);
+
+        Ok(IndexedView { view, indexed })
+    }
+}
+
+// SAFE: `T`'s storage and index are recorded.
+unsafe impl<T: IndexKey + Send + Sync> BorrowInfo for IndexedView<'_, T> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        <View<'_, T>>::borrow_info(info);
+
+        info.push(TypeInfo {
+            name: type_name::<Indexed<T>>().into(),
+            mutability: Mutability::Exclusive,
+            storage_id: StorageId::of::<Indexed<T>>(),
+            thread_safe: true,
+            optional: false,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
@@ -86,6 +86,7 @@ impl<'tmp, T: Component> AbstractMut for FullRawWindowMut<'tmp, T> {
                 .then(|| &mut *self.modification_data.add(index)),
             current: self.current,
             data: &mut *self.data.add(index),
+            log: self.change_log.map(|change_log| change_log.slot(index)),
         }
     }
     #[inline]
@@ -96,6 +97,7 @@ impl<'tmp, T: Component> AbstractMut for FullRawWindowMut<'tmp, T> {
                 .then(|| &mut *self.modification_data.add(index)),
             current: self.current,
             data: &mut *self.data.add(index),
+            log: self.change_log.map(|change_log| change_log.slot(index)),
         }
     }
     #[inline]
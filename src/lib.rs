@@ -4,6 +4,8 @@
 //!
 //! ## Features
 //!
+//! - **borrow_diagnostics** &mdash; records who holds each storage borrow and reports it in borrow errors
+//! - **compact_id** &mdash; uses 32-bit [`EntityId`]s with 24 bits of index and 8 bits of generation
 //! - **parallel** *(default)* &mdash; enables workload threading and add parallel iterators
 //! - **proc** *(default)* &mdash; re-exports macros from `shipyard_proc`, mainly to derive `Component`
 //! - **serde1** &mdash; adds (de)serialization support with [serde](https://github.com/serde-rs/serde)
@@ -42,23 +44,36 @@ pub mod borrow;
 mod component;
 mod contains;
 mod delete;
+#[cfg(feature = "serde1")]
+mod delta;
+mod dynamic;
 mod entities;
 mod entity_id;
 pub mod error;
+mod executor;
 mod get;
 mod get_component;
+mod history;
+mod indexed;
 pub mod iter;
 mod iter_component;
 mod memory_usage;
 mod r#mut;
 mod not;
 mod or;
+#[cfg(feature = "serde1")]
+mod persist;
+mod plugin;
 mod public_transport;
+mod reactive;
+mod relation;
 mod remove;
 mod reserve;
 mod scheduler;
 mod seal;
+mod sorted;
 mod sparse_set;
+mod spatial_grid;
 mod storage;
 mod system;
 /// module related to storage tracking, like insertion or modification.
@@ -77,6 +92,8 @@ pub use all_storages::{
     TupleDeleteAny, TupleRetainStorage,
 };
 pub use atomic_refcell::{ARef, ARefMut};
+#[cfg(feature = "borrow_diagnostics")]
+pub use atomic_refcell::BorrowHolder;
 #[doc(hidden)]
 pub use atomic_refcell::{ExclusiveBorrow, SharedBorrow};
 #[doc(inline)]
@@ -86,31 +103,50 @@ pub use borrow::{
 pub use component::{Component, Unique};
 pub use contains::Contains;
 pub use delete::Delete;
+#[cfg(feature = "serde1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde1")))]
+pub use delta::{ComponentDelta, DeltaComponents};
+pub use dynamic::{DynamicComponentInfo, DynamicStorage, DynamicView, DynamicViewMut};
 pub use entities::Entities;
 pub use entity_id::EntityId;
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub use executor::RayonExecutor;
+pub use executor::{Executor, SequentialExecutor};
 pub use get::Get;
 pub use get_component::{GetComponent, Ref, RefMut};
+pub use history::History;
+pub use indexed::{IndexKey, Indexed, IndexedView};
 pub use iter::{IntoIter, IntoWithId};
 pub use iter_component::{IntoIterRef, IterComponent, IterRef};
 pub use memory_usage::StorageMemoryUsage;
 pub use not::Not;
 pub use or::{OneOfTwo, Or};
+#[cfg(feature = "serde1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde1")))]
+pub use persist::{Persistent, PersistentComponents};
+pub use plugin::{Plugin, PluginDependencies};
 pub use r#mut::Mut;
+pub use reactive::{Reactive, ReactiveChanges, ReactiveQuery};
+pub use relation::{CleanupPolicy, Relation, Relations};
 pub use remove::Remove;
 pub use reserve::{BulkEntityIter, BulkReserve};
 pub use scheduler::{
     info, AsLabel, IntoWorkload, IntoWorkloadSystem, IntoWorkloadTrySystem, Label,
-    ScheduledWorkload, SystemModificator, Workload, WorkloadModificator, WorkloadSystem,
+    ScheduledWorkload, SystemDescription, SystemModificator, SystemRegistry, Workload,
+    WorkloadDescription, WorkloadModificator, WorkloadSystem,
 };
 #[cfg(feature = "proc")]
 pub use shipyard_proc::{Borrow, BorrowInfo, Component, Label, Unique, WorldBorrow};
+pub use sorted::{SortKey, Sorted, SortedView};
 pub use sparse_set::{
     BulkAddEntity, SparseArray, SparseSet, SparseSetDrain, TupleAddComponent, TupleDelete,
     TupleRemove,
 };
+pub use spatial_grid::{SpatialGrid, SpatialGridView, SpatialPosition};
 pub use storage::{Storage, StorageId};
 #[doc(hidden)]
-pub use system::{AllSystem, Nothing, System};
+pub use system::{AllSystem, AsyncSystem, Nothing, System};
 pub use tracking::{
     DeletionTracking, Inserted, InsertedOrModified, InsertionTracking, ModificationTracking,
     Modified, RemovalOrDeletionTracking, RemovalTracking, Track, Tracking, TrackingTimestamp,
@@ -1,3 +1,4 @@
+use crate::sparse_set::LogSlot;
 use crate::tracking::TrackingTimestamp;
 
 /// Tracks component modification.
@@ -5,17 +6,26 @@ pub struct Mut<'a, T: ?Sized> {
     pub(crate) flag: Option<&'a mut TrackingTimestamp>,
     pub(crate) current: TrackingTimestamp,
     pub(crate) data: &'a mut T,
+    pub(crate) log: Option<LogSlot<'a>>,
 }
 
 impl<'a, T: ?Sized> Mut<'a, T> {
     /// Makes a new [`Mut`], the component will not be flagged if its modified inside `f`.
     ///
     /// This is an associated function that needs to be used as `Mut::map(...)`. A method would interfere with methods of the same name used through Deref.
-    pub fn map<U: ?Sized, F: FnOnce(&mut T) -> &mut U>(orig: Self, f: F) -> Mut<'a, U> {
+    pub fn map<U: ?Sized, F: FnOnce(&mut T) -> &mut U>(mut orig: Self, f: F) -> Mut<'a, U> {
+        if let Some(log) = &mut orig.log {
+            let component: *const T = orig.data;
+
+            // SAFE `orig` points to the component, `U` might only be a part of it
+            unsafe { log.link_early(component.cast()) };
+        }
+
         Mut {
             flag: orig.flag,
             current: orig.current,
             data: f(orig.data),
+            log: orig.log,
         }
     }
 }
@@ -35,6 +45,12 @@ impl<T: ?Sized> core::ops::DerefMut for Mut<'_, T> {
         if let Some(flag) = &mut self.flag {
             **flag = self.current;
         }
+        if let Some(log) = self.log.take() {
+            let component: *const T = self.data;
+
+            // SAFE a mapped `Mut` linked its component early
+            unsafe { log.log(component.cast()) };
+        }
 
         self.data
     }
@@ -53,6 +69,12 @@ impl<T: ?Sized> AsMut<T> for Mut<'_, T> {
         if let Some(flag) = &mut self.flag {
             **flag = self.current;
         }
+        if let Some(log) = self.log.take() {
+            let component: *const T = self.data;
+
+            // SAFE a mapped `Mut` linked its component early
+            unsafe { log.log(component.cast()) };
+        }
 
         self.data
     }
@@ -0,0 +1,507 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::component::Component;
+use crate::entities::Entities;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::sparse_set::SparseSet;
+use crate::tracking::TrackingTimestamp;
+use alloc::string::String;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use serde::ser::{self, SerializeSeq, SerializeStruct};
+use serde::{Deserializer, Serialize, Serializer};
+
+const FIELDS: &[&str] = &["entities", "components"];
+
+/// Component that can be saved with [`World::save`](crate::World::save) and loaded with [`World::load`](crate::World::load).
+///
+/// Saves identify components by [`Persistent::KEY`], `TypeId` isn't stable across builds.\
+/// Components are saved along with [`Persistent::VERSION`], the ones saved with an older version are loaded with [`Persistent::migrate`].
+///
+/// ### Example
+///
+/// ```
+/// use serde::{Deserialize, Deserializer, Serialize};
+/// use shipyard::{Component, Persistent};
+///
+/// #[derive(Deserialize)]
+/// struct PositionV0(f32, f32);
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// impl Persistent for Position {
+///     const KEY: &'static str = "position";
+///     const VERSION: u32 = 1;
+///
+///     fn migrate<'de, D: Deserializer<'de>>(version: u32, deserializer: D) -> Result<Self, D::Error> {
+///         match version {
+///             0 => {
+///                 let PositionV0(x, y) = PositionV0::deserialize(deserializer)?;
+///
+///                 Ok(Position { x, y, z: 0.0 })
+///             }
+///             _ => unreachable!(),
+///         }
+///     }
+/// }
+/// ```
+pub trait Persistent: Component + Send + Sync + Serialize + DeserializeOwned {
+    /// Stable key identifying the component in saves.
+    const KEY: &'static str;
+    /// Version written in saves.
+    const VERSION: u32 = 0;
+
+    /// Deserializes a component saved with `version`, an older version than [`Persistent::VERSION`].
+    ///
+    /// Fails by default.
+    fn migrate<'de, D: Deserializer<'de>>(version: u32, deserializer: D) -> Result<Self, D::Error> {
+        let _ = deserializer;
+
+        Err(de::Error::custom(format_args!(
+            "no migration from version {} of `{}`",
+            version,
+            Self::KEY
+        )))
+    }
+}
+
+/// Components saved with [`World::save`](crate::World::save) and loaded with [`World::load`](crate::World::load).
+///
+/// Implemented for [`Persistent`] components and tuples of them.
+pub trait PersistentComponents {
+    /// Number of components.
+    const LEN: usize;
+
+    /// Serializes a `(key, version, components)` entry for each component.
+    #[allow(missing_docs)]
+    fn save<S: SerializeSeq>(all_storages: &AllStorages, seq: &mut S) -> Result<(), S::Error>;
+    /// Deserializes the components of the entry if `key` matches one of the components.\
+    /// Returns `false` if no component matches.
+    #[allow(missing_docs)]
+    fn load<'de, Access: SeqAccess<'de>>(
+        key: &str,
+        version: u32,
+        seq: &mut Access,
+        all_storages: &AllStorages,
+        current: TrackingTimestamp,
+    ) -> Result<bool, Access::Error>;
+}
+
+impl<T: Persistent> PersistentComponents for T {
+    const LEN: usize = 1;
+
+    fn save<S: SerializeSeq>(all_storages: &AllStorages, seq: &mut S) -> Result<(), S::Error> {
+        match all_storages.custom_storage::<SparseSet<T>>() {
+            Ok(sparse_set) => {
+                seq.serialize_element(&(T::KEY, T::VERSION, SaveSparseSet(&sparse_set)))
+            }
+            Err(error::GetStorage::MissingStorage { .. }) => {
+                seq.serialize_element(&(T::KEY, T::VERSION, &[(); 0][..]))
+            }
+            Err(err) => Err(ser::Error::custom(err)),
+        }
+    }
+    fn load<'de, Access: SeqAccess<'de>>(
+        key: &str,
+        version: u32,
+        seq: &mut Access,
+        all_storages: &AllStorages,
+        current: TrackingTimestamp,
+    ) -> Result<bool, Access::Error> {
+        if key != T::KEY {
+            return Ok(false);
+        }
+
+        if version > T::VERSION {
+            return Err(de::Error::custom(format_args!(
+                "`{}` was saved with version {}, more recent than {}",
+                T::KEY,
+                version,
+                T::VERSION
+            )));
+        }
+
+        seq.next_element_seed(LoadSparseSet::<T> {
+            version,
+            all_storages,
+            current,
+            _phantom: PhantomData,
+        })?
+        .ok_or_else(|| de::Error::invalid_length(2, &"a component entry"))?;
+
+        Ok(true)
+    }
+}
+
+macro_rules! impl_persistent_components {
+    ($(($type: ident, $index: tt))+) => {
+        impl<$($type: Persistent,)+> PersistentComponents for ($($type,)+) {
+            const LEN: usize = 0 $(+ <$type as PersistentComponents>::LEN)+;
+
+            fn save<S: SerializeSeq>(all_storages: &AllStorages, seq: &mut S) -> Result<(), S::Error> {
+                $(
+                    <$type as PersistentComponents>::save(all_storages, seq)?;
+                )+
+
+                Ok(())
+            }
+            fn load<'de, Access: SeqAccess<'de>>(
+                key: &str,
+                version: u32,
+                seq: &mut Access,
+                all_storages: &AllStorages,
+                current: TrackingTimestamp,
+            ) -> Result<bool, Access::Error> {
+                $(
+                    if <$type as PersistentComponents>::load(key, version, seq, all_storages, current)? {
+                        return Ok(true);
+                    }
+                )+
+
+                Ok(false)
+            }
+        }
+    };
+}
+
+macro_rules! persistent_components {
+    ($(($type: ident, $index: tt))*;($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_persistent_components![$(($type, $index))*];
+        persistent_components![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))*;) => {
+        impl_persistent_components![$(($type, $index))*];
+    }
+}
+
+persistent_components![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
+
+/// Serializes the alive entities and `C` components of `all_storages`.
+pub(crate) fn save<C: PersistentComponents, S: Serializer>(
+    all_storages: &AllStorages,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let entities = all_storages.entities().map_err(ser::Error::custom)?;
+
+    let mut ser_struct = serializer.serialize_struct("World", 2)?;
+    ser_struct.serialize_field(FIELDS[0], &SaveEntities(&entities))?;
+    ser_struct.serialize_field(FIELDS[1], &SaveComponents::<C>(all_storages, PhantomData))?;
+    ser_struct.end()
+}
+
+/// Adds the entities and `C` components serialized by [`save`] to `all_storages`.
+pub(crate) fn load<'de, C: PersistentComponents, D: Deserializer<'de>>(
+    all_storages: &AllStorages,
+    deserializer: D,
+) -> Result<(), D::Error> {
+    deserializer.deserialize_struct(
+        "World",
+        FIELDS,
+        LoadWorld::<C> {
+            all_storages,
+            current: all_storages.get_current(),
+            _phantom: PhantomData,
+        },
+    )
+}
+
+struct SaveEntities<'a>(&'a Entities);
+
+impl Serialize for SaveEntities<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // some formats need the length upfront and the iterator doesn't know it
+        let mut seq = serializer.serialize_seq(Some(self.0.iter().count()))?;
+        for entity in self.0.iter() {
+            seq.serialize_element(&entity)?;
+        }
+        seq.end()
+    }
+}
+
+struct SaveComponents<'a, C>(&'a AllStorages, PhantomData<C>);
+
+impl<C: PersistentComponents> Serialize for SaveComponents<'_, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(C::LEN))?;
+        C::save(self.0, &mut seq)?;
+        seq.end()
+    }
+}
+
+struct SaveSparseSet<'a, T: Component>(&'a SparseSet<T>);
+
+impl<T: Component + Serialize> Serialize for SaveSparseSet<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.dense.iter().zip(&self.0.data))
+    }
+}
+
+struct LoadWorld<'a, C> {
+    all_storages: &'a AllStorages,
+    current: TrackingTimestamp,
+    _phantom: PhantomData<C>,
+}
+
+impl<'de, C: PersistentComponents> Visitor<'de> for LoadWorld<'_, C> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("struct World")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        seq.next_element_seed(LoadEntities(self.all_storages))?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        seq.next_element_seed(LoadComponents::<C> {
+            all_storages: self.all_storages,
+            current: self.current,
+            _phantom: PhantomData,
+        })?
+        .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut entities = false;
+        let mut components = false;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "entities" if !entities => {
+                    map.next_value_seed(LoadEntities(self.all_storages))?;
+                    entities = true;
+                }
+                "components" if !components => {
+                    map.next_value_seed(LoadComponents::<C> {
+                        all_storages: self.all_storages,
+                        current: self.current,
+                        _phantom: PhantomData,
+                    })?;
+                    components = true;
+                }
+                "entities" | "components" => {
+                    return Err(de::Error::custom(format_args!("duplicate field `{}`", key)))
+                }
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            }
+        }
+
+        if !entities {
+            return Err(de::Error::missing_field(FIELDS[0]));
+        }
+        if !components {
+            return Err(de::Error::missing_field(FIELDS[1]));
+        }
+
+        Ok(())
+    }
+}
+
+struct LoadEntities<'a>(&'a AllStorages);
+
+impl<'de> DeserializeSeed<'de> for LoadEntities<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for LoadEntities<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a sequence of EntityId")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut entities = self.0.entities_mut().map_err(de::Error::custom)?;
+
+        while let Some(entity) = seq.next_element::<EntityId>()? {
+            entities.spawn(entity);
+        }
+
+        Ok(())
+    }
+}
+
+struct LoadComponents<'a, C> {
+    all_storages: &'a AllStorages,
+    current: TrackingTimestamp,
+    _phantom: PhantomData<C>,
+}
+
+impl<'de, C: PersistentComponents> DeserializeSeed<'de> for LoadComponents<'_, C> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, C: PersistentComponents> Visitor<'de> for LoadComponents<'_, C> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a sequence of component entries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while seq
+            .next_element_seed(LoadEntry::<C> {
+                all_storages: self.all_storages,
+                current: self.current,
+                _phantom: PhantomData,
+            })?
+            .is_some()
+        {}
+
+        Ok(())
+    }
+}
+
+/// Deserializes a `(key, version, components)` entry.
+struct LoadEntry<'a, C> {
+    all_storages: &'a AllStorages,
+    current: TrackingTimestamp,
+    _phantom: PhantomData<C>,
+}
+
+impl<'de, C: PersistentComponents> DeserializeSeed<'de> for LoadEntry<'_, C> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_tuple(3, self)
+    }
+}
+
+impl<'de, C: PersistentComponents> Visitor<'de> for LoadEntry<'_, C> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a component entry")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let key = seq
+            .next_element::<String>()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let version = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        // components that are not loaded are skipped when the format allows it
+        if !C::load(&key, version, &mut seq, self.all_storages, self.current)? {
+            seq.next_element::<IgnoredAny>()?
+                .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Deserializes `T` components saved with `version`.
+struct LoadSparseSet<'a, T> {
+    version: u32,
+    all_storages: &'a AllStorages,
+    current: TrackingTimestamp,
+    _phantom: PhantomData<T>,
+}
+
+impl<'de, T: Persistent> DeserializeSeed<'de> for LoadSparseSet<'_, T> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: Persistent> Visitor<'de> for LoadSparseSet<'_, T> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a sequence of (EntityId, component)")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut entities = self
+            .all_storages
+            .entities_mut()
+            .map_err(de::Error::custom)?;
+        let mut sparse_set = self
+            .all_storages
+            .custom_storage_or_insert_mut(SparseSet::<T>::new)
+            .map_err(de::Error::custom)?;
+
+        while let Some((entity, component)) = seq.next_element_seed(LoadComponent::<T> {
+            version: self.version,
+            _phantom: PhantomData,
+        })? {
+            sparse_set.insert_spawned(&mut entities, entity, component, self.current);
+        }
+
+        Ok(())
+    }
+}
+
+/// Deserializes an `(EntityId, T)` pair, migrating `T` if it was saved with an older version.
+struct LoadComponent<T> {
+    version: u32,
+    _phantom: PhantomData<T>,
+}
+
+impl<'de, T: Persistent> DeserializeSeed<'de> for LoadComponent<T> {
+    type Value = (EntityId, T);
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(EntityId, T), D::Error> {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'de, T: Persistent> Visitor<'de> for LoadComponent<T> {
+    type Value = (EntityId, T);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an (EntityId, component) pair")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(EntityId, T), A::Error> {
+        let entity = seq
+            .next_element::<EntityId>()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let component = seq
+            .next_element_seed(Migrate::<T> {
+                version: self.version,
+                _phantom: PhantomData,
+            })?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok((entity, component))
+    }
+}
+
+struct Migrate<T> {
+    version: u32,
+    _phantom: PhantomData<T>,
+}
+
+impl<'de, T: Persistent> DeserializeSeed<'de> for Migrate<T> {
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        if self.version == T::VERSION {
+            T::deserialize(deserializer)
+        } else {
+            T::migrate(self.version, deserializer)
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::error;
+use crate::type_id::TypeId;
+use crate::world::World;
+use alloc::vec::Vec;
+use core::any::type_name;
+
+/// Bundles everything a feature needs to work in a [`World`].
+///
+/// A plugin registers its uniques, workloads, storage tracking and custom storages in [`Plugin::build`].\
+/// Plugins are added with [`World::add_plugin`], each plugin type can only be added once.
+///
+/// ### Example
+///
+/// ```
+/// use shipyard::{error, Plugin, PluginDependencies, Unique, UniqueViewMut, Workload, World};
+///
+/// #[derive(Unique)]
+/// struct Gravity(f32);
+///
+/// #[derive(Unique)]
+/// struct Volume(f32);
+///
+/// struct PhysicsPlugin;
+///
+/// impl Plugin for PhysicsPlugin {
+///     fn build(&self, world: &mut World) -> Result<(), error::AddPlugin> {
+///         world.add_unique(Gravity(9.81));
+///
+///         Ok(())
+///     }
+/// }
+///
+/// struct AudioPlugin;
+///
+/// impl Plugin for AudioPlugin {
+///     fn dependencies(&self, dependencies: &mut PluginDependencies) {
+///         dependencies.add::<PhysicsPlugin>();
+///     }
+///
+///     fn build(&self, world: &mut World) -> Result<(), error::AddPlugin> {
+///         world.add_unique(Volume(1.0));
+///
+///         Workload::new("audio")
+///             .with_system(|mut volume: UniqueViewMut<Volume>| volume.0 *= 0.5)
+///             .add_to_world(world)
+///             .map_err(error::AddPlugin::from_custom)
+///     }
+/// }
+///
+/// let mut world = World::new();
+///
+/// world.add_plugin(PhysicsPlugin).unwrap();
+/// world.add_plugin(AudioPlugin).unwrap();
+///
+/// assert!(world.add_plugin(PhysicsPlugin).is_err());
+/// ```
+pub trait Plugin: 'static {
+    /// Registers the plugin's uniques, workloads, tracking and custom storages.\
+    /// Errors can be wrapped with [`AddPlugin::from_custom`](error::AddPlugin::from_custom).
+    fn build(&self, world: &mut World) -> Result<(), error::AddPlugin>;
+    /// Lists the plugins that have to be added to the `World` before this one.
+    #[allow(unused_variables)]
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {}
+    /// Returns the plugin's name.
+    fn name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+}
+
+/// List of plugins a [`Plugin`] depends on.
+#[derive(Default)]
+pub struct PluginDependencies {
+    pub(crate) plugins: Vec<(TypeId, &'static str)>,
+}
+
+impl PluginDependencies {
+    /// Requires `P` to be added to the `World` before the plugin.
+    pub fn add<P: Plugin>(&mut self) -> &mut PluginDependencies {
+        self.plugins.push((TypeId::of::<P>(), type_name::<P>()));
+        self
+    }
+}
+
+impl World {
+    /// Adds a plugin to the `World`.\
+    /// The plugin's dependencies have to already be present.
+    ///
+    /// The plugin is only added if its [`build`](Plugin::build) succeeds,
+    /// what it registered before failing stays in the `World`.
+    ///
+    /// ### Errors
+    ///
+    /// - A plugin of the same type was already added.
+    /// - A dependency of the plugin is missing.
+    /// - The plugin's build failed.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{error, Plugin, Unique, UniqueView, World};
+    ///
+    /// #[derive(Unique)]
+    /// struct Config(u32);
+    ///
+    /// struct ConfigPlugin;
+    ///
+    /// impl Plugin for ConfigPlugin {
+    ///     fn build(&self, world: &mut World) -> Result<(), error::AddPlugin> {
+    ///         world.add_unique(Config(0));
+    ///
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_plugin(ConfigPlugin).unwrap();
+    ///
+    /// assert_eq!(world.borrow::<UniqueView<Config>>().unwrap().0, 0);
+    /// ```
+    pub fn add_plugin<P: Plugin>(&mut self, plugin: P) -> Result<(), error::AddPlugin> {
+        let type_id = TypeId::of::<P>();
+
+        if self.plugins.iter().any(|(id, _)| *id == type_id) {
+            return Err(error::AddPlugin::AlreadyAdded(plugin.name()));
+        }
+
+        let mut dependencies = PluginDependencies::default();
+        plugin.dependencies(&mut dependencies);
+
+        for (dependency_id, dependency_name) in dependencies.plugins {
+            if !self.plugins.iter().any(|(id, _)| *id == dependency_id) {
+                return Err(error::AddPlugin::MissingDependency {
+                    plugin: plugin.name(),
+                    dependency: dependency_name,
+                });
+            }
+        }
+
+        plugin.build(self)?;
+
+        self.plugins.push((type_id, plugin.name()));
+
+        Ok(())
+    }
+    /// Returns `true` if a plugin of type `P` was added to the `World`.
+    pub fn contains_plugin<P: Plugin>(&self) -> bool {
+        let type_id = TypeId::of::<P>();
+
+        self.plugins.iter().any(|(id, _)| *id == type_id)
+    }
+    /// Returns the names of all plugins added to the `World`, in insertion order.
+    pub fn plugins(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.plugins.iter().map(|(_, name)| *name)
+    }
+}
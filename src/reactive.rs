@@ -0,0 +1,471 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::memory_usage::StorageMemoryUsage;
+use crate::not::Not;
+use crate::sparse_set::SparseSet;
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::{ShipHashMap, ShipHashSet};
+use alloc::vec::Vec;
+use core::hash::BuildHasherDefault;
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+/// Component signature of a [`Reactive`] query.
+///
+/// Implemented for components, [`Not`] of components and tuples of both.\
+/// A signature needs at least one component that isn't a [`Not`].
+pub trait ReactiveQuery: 'static {
+    /// Number of components that aren't a [`Not`].
+    #[allow(missing_docs)]
+    fn included_count() -> usize;
+    /// Enables the tracking needed to follow the signature and registers the `reactive` set as one of its readers.
+    #[allow(missing_docs)]
+    fn track(all_storages: &mut AllStorages, reactive: StorageId, current: TrackingTimestamp);
+    /// Adds all entities that could match the signature.
+    #[allow(missing_docs)]
+    fn candidates(
+        all_storages: &AllStorages,
+        candidates: &mut Vec<EntityId>,
+    ) -> Result<(), error::GetStorage>;
+    /// Adds the entities whose components were added or removed between `last` and `current`.
+    #[allow(missing_docs)]
+    fn changed(
+        all_storages: &AllStorages,
+        last: TrackingTimestamp,
+        current: TrackingTimestamp,
+        changed: &mut Vec<EntityId>,
+    ) -> Result<(), error::GetStorage>;
+    /// Unsets `matches` for the entities that don't match the signature.
+    #[allow(missing_docs)]
+    fn filter(
+        all_storages: &AllStorages,
+        entities: &[EntityId],
+        matches: &mut [bool],
+    ) -> Result<(), error::GetStorage>;
+    /// Records that the `reactive` set saw all changes up to `last_sync`.\
+    /// Clears the removal and deletion tracking enabled by reactive sets once all of them saw it.
+    #[allow(missing_docs)]
+    fn clear_seen(
+        all_storages: &AllStorages,
+        reactive: StorageId,
+        last_sync: TrackingTimestamp,
+    ) -> Result<(), error::GetStorage>;
+}
+
+impl<T: Component + Send + Sync> ReactiveQuery for T {
+    fn included_count() -> usize {
+        1
+    }
+    fn track(all_storages: &mut AllStorages, reactive: StorageId, current: TrackingTimestamp) {
+        let sparse_set = all_storages
+            .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<T>>(), SparseSet::<T>::new);
+        let enables_removal = !sparse_set.is_tracking_removal();
+        let enables_deletion = !sparse_set.is_tracking_deletion();
+        sparse_set
+            .track_insertion()
+            .track_removal()
+            .track_deletion();
+
+        let readers = all_storages
+            .exclusive_storage_or_insert_mut(
+                StorageId::of::<ReactiveReaders>(),
+                ReactiveReaders::new,
+            )
+            .components
+            .entry(StorageId::of::<SparseSet<T>>())
+            .or_insert_with(|| ComponentReaders {
+                owns_removal: enables_removal,
+                owns_deletion: enables_deletion,
+                last_syncs: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            });
+        readers.last_syncs.insert(reactive, current);
+    }
+    fn candidates(
+        all_storages: &AllStorages,
+        candidates: &mut Vec<EntityId>,
+    ) -> Result<(), error::GetStorage> {
+        let sparse_set = all_storages.custom_storage::<SparseSet<T>>()?;
+
+        candidates.extend_from_slice(&sparse_set.dense);
+
+        Ok(())
+    }
+    fn changed(
+        all_storages: &AllStorages,
+        last: TrackingTimestamp,
+        current: TrackingTimestamp,
+        changed: &mut Vec<EntityId>,
+    ) -> Result<(), error::GetStorage> {
+        let sparse_set = all_storages.custom_storage::<SparseSet<T>>()?;
+
+        let mut removed = Vec::new();
+        sparse_set.for_each_change_within(
+            last,
+            current,
+            |entity| removed.push(entity),
+            |entity, _| changed.push(entity),
+        );
+        changed.extend(removed);
+
+        Ok(())
+    }
+    fn filter(
+        all_storages: &AllStorages,
+        entities: &[EntityId],
+        matches: &mut [bool],
+    ) -> Result<(), error::GetStorage> {
+        let sparse_set = all_storages.custom_storage::<SparseSet<T>>()?;
+
+        for (&entity, matches) in entities.iter().zip(matches) {
+            *matches &= sparse_set.contains(entity);
+        }
+
+        Ok(())
+    }
+    fn clear_seen(
+        all_storages: &AllStorages,
+        reactive: StorageId,
+        last_sync: TrackingTimestamp,
+    ) -> Result<(), error::GetStorage> {
+        let mut readers = all_storages.custom_storage_mut::<ReactiveReaders>()?;
+        let readers = match readers.components.get_mut(&StorageId::of::<SparseSet<T>>()) {
+            Some(readers) => readers,
+            None => return Ok(()),
+        };
+
+        readers.last_syncs.insert(reactive, last_sync);
+
+        if !readers.owns_removal && !readers.owns_deletion {
+            return Ok(());
+        }
+
+        let oldest = readers
+            .last_syncs
+            .values()
+            .copied()
+            .fold(last_sync, |oldest, last_sync| {
+                if last_sync.is_older_than(oldest) {
+                    last_sync
+                } else {
+                    oldest
+                }
+            });
+
+        // the tracking is cleared at a later sync if the storage is currently borrowed
+        if let Ok(mut sparse_set) = all_storages.custom_storage_mut::<SparseSet<T>>() {
+            if readers.owns_removal {
+                sparse_set.clear_all_removed_older_than_timestamp(oldest);
+            }
+            if readers.owns_deletion {
+                sparse_set.clear_all_deleted_older_than_timestamp(oldest);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Component + Send + Sync> ReactiveQuery for Not<T> {
+    fn included_count() -> usize {
+        0
+    }
+    fn track(all_storages: &mut AllStorages, reactive: StorageId, current: TrackingTimestamp) {
+        T::track(all_storages, reactive, current);
+    }
+    fn candidates(
+        _all_storages: &AllStorages,
+        _candidates: &mut Vec<EntityId>,
+    ) -> Result<(), error::GetStorage> {
+        Ok(())
+    }
+    fn changed(
+        all_storages: &AllStorages,
+        last: TrackingTimestamp,
+        current: TrackingTimestamp,
+        changed: &mut Vec<EntityId>,
+    ) -> Result<(), error::GetStorage> {
+        T::changed(all_storages, last, current, changed)
+    }
+    fn filter(
+        all_storages: &AllStorages,
+        entities: &[EntityId],
+        matches: &mut [bool],
+    ) -> Result<(), error::GetStorage> {
+        let sparse_set = all_storages.custom_storage::<SparseSet<T>>()?;
+
+        for (&entity, matches) in entities.iter().zip(matches) {
+            *matches &= !sparse_set.contains(entity);
+        }
+
+        Ok(())
+    }
+    fn clear_seen(
+        all_storages: &AllStorages,
+        reactive: StorageId,
+        last_sync: TrackingTimestamp,
+    ) -> Result<(), error::GetStorage> {
+        T::clear_seen(all_storages, reactive, last_sync)
+    }
+}
+
+macro_rules! impl_reactive_query {
+    ($($type: ident)+) => {
+        impl<$($type: ReactiveQuery,)+> ReactiveQuery for ($($type,)+) {
+            fn included_count() -> usize {
+                0 $(+ $type::included_count())+
+            }
+            fn track(all_storages: &mut AllStorages, reactive: StorageId, current: TrackingTimestamp) {
+                $(
+                    $type::track(all_storages, reactive, current);
+                )+
+            }
+            fn candidates(
+                all_storages: &AllStorages,
+                candidates: &mut Vec<EntityId>,
+            ) -> Result<(), error::GetStorage> {
+                $(
+                    $type::candidates(all_storages, candidates)?;
+                )+
+
+                Ok(())
+            }
+            fn changed(
+                all_storages: &AllStorages,
+                last: TrackingTimestamp,
+                current: TrackingTimestamp,
+                changed: &mut Vec<EntityId>,
+            ) -> Result<(), error::GetStorage> {
+                $(
+                    $type::changed(all_storages, last, current, changed)?;
+                )+
+
+                Ok(())
+            }
+            fn filter(
+                all_storages: &AllStorages,
+                entities: &[EntityId],
+                matches: &mut [bool],
+            ) -> Result<(), error::GetStorage> {
+                $(
+                    $type::filter(all_storages, entities, matches)?;
+                )+
+
+                Ok(())
+            }
+            fn clear_seen(
+                all_storages: &AllStorages,
+                reactive: StorageId,
+                last_sync: TrackingTimestamp,
+            ) -> Result<(), error::GetStorage> {
+                $(
+                    $type::clear_seen(all_storages, reactive, last_sync)?;
+                )+
+
+                Ok(())
+            }
+        }
+    };
+}
+
+macro_rules! reactive_query {
+    ($($type: ident)*; $type1: ident $($queue_type: ident)*) => {
+        impl_reactive_query![$($type)* $type1];
+        reactive_query![$($type)* $type1; $($queue_type)*];
+    };
+    ($($type: ident)*;) => {}
+}
+
+reactive_query![; A B C D E F G H I J];
+
+/// Entities that started or stopped matching a [`Reactive`] query, returned by [`World::reactive_changes`](crate::World::reactive_changes).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReactiveChanges {
+    /// Entities matching the query that didn't match at the previous check.
+    pub entered: Vec<EntityId>,
+    /// Entities that matched the query at the previous check and don't anymore.
+    pub exited: Vec<EntityId>,
+}
+
+/// Set of entities matching `Q`, created by [`World::add_reactive`](crate::World::add_reactive).
+///
+/// On access, entities that gained or lost one of `Q`'s components since the previous access are matched against `Q` again,
+/// which is what fills [`ReactiveChanges`]. The other entities aren't looked at.
+///
+/// Removal and deletion tracking enabled by a set is cleared once all sets using the component have seen it.
+pub struct Reactive<Q: ReactiveQuery> {
+    entities: Vec<EntityId>,
+    indices: ShipHashMap<EntityId, usize>,
+    entered: ShipHashSet<EntityId>,
+    exited: ShipHashSet<EntityId>,
+    last_sync: TrackingTimestamp,
+    _phantom: PhantomData<fn() -> Q>,
+}
+
+impl<Q: ReactiveQuery> Reactive<Q> {
+    /// Adds all entities currently matching `Q`, they're reported as entered.
+    pub(crate) fn new(
+        all_storages: &AllStorages,
+        current: TrackingTimestamp,
+    ) -> Result<Reactive<Q>, error::GetStorage> {
+        let mut reactive = Reactive {
+            entities: Vec::new(),
+            indices: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            entered: ShipHashSet::with_hasher(BuildHasherDefault::default()),
+            exited: ShipHashSet::with_hasher(BuildHasherDefault::default()),
+            last_sync: current,
+            _phantom: PhantomData,
+        };
+
+        let mut candidates = Vec::new();
+        Q::candidates(all_storages, &mut candidates)?;
+        reactive.update(all_storages, candidates)?;
+
+        Ok(reactive)
+    }
+
+    /// Applies all changes made to `Q`'s components since the last sync.
+    pub(crate) fn sync(
+        &mut self,
+        all_storages: &AllStorages,
+        current: TrackingTimestamp,
+    ) -> Result<(), error::GetStorage> {
+        let mut changed = Vec::new();
+        Q::changed(all_storages, self.last_sync, current, &mut changed)?;
+        self.update(all_storages, changed)?;
+
+        self.last_sync = current;
+
+        Q::clear_seen(all_storages, StorageId::of::<Self>(), current)
+    }
+
+    /// Returns the entered and exited entities since the last call.
+    pub(crate) fn take_changes(&mut self) -> ReactiveChanges {
+        ReactiveChanges {
+            entered: self.entered.drain().collect(),
+            exited: self.exited.drain().collect(),
+        }
+    }
+
+    /// Returns an iterator over the matching entities.
+    pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, EntityId>> {
+        self.entities.iter().copied()
+    }
+
+    /// Returns `true` if `entity` matches the query.
+    pub fn contains(&self, entity: EntityId) -> bool {
+        self.indices.contains_key(&entity)
+    }
+
+    /// Returns the number of matching entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity matches the query.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn update(
+        &mut self,
+        all_storages: &AllStorages,
+        mut candidates: Vec<EntityId>,
+    ) -> Result<(), error::GetStorage> {
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut matches = alloc::vec![true; candidates.len()];
+        Q::filter(all_storages, &candidates, &mut matches)?;
+
+        for (entity, matches) in candidates.into_iter().zip(matches) {
+            match (matches, self.contains(entity)) {
+                (true, false) => self.enter(entity),
+                (false, true) => self.exit(entity),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn enter(&mut self, entity: EntityId) {
+        self.indices.insert(entity, self.entities.len());
+        self.entities.push(entity);
+
+        if !self.exited.remove(&entity) {
+            self.entered.insert(entity);
+        }
+    }
+
+    fn exit(&mut self, entity: EntityId) {
+        if let Some(index) = self.indices.remove(&entity) {
+            self.entities.swap_remove(index);
+
+            if let Some(&moved) = self.entities.get(index) {
+                self.indices.insert(moved, index);
+            }
+
+            if !self.entered.remove(&entity) {
+                self.exited.insert(entity);
+            }
+        }
+    }
+}
+
+impl<Q: ReactiveQuery> Storage for Reactive<Q> {
+    #[inline]
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        self.exit(entity);
+    }
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        while let Some(&entity) = self.entities.last() {
+            self.exit(entity);
+        }
+    }
+    fn memory_usage(&self) -> Option<StorageMemoryUsage> {
+        let entity_size = size_of::<EntityId>();
+        let index_size = size_of::<(EntityId, usize)>();
+
+        Some(StorageMemoryUsage {
+            storage_name: core::any::type_name::<Self>().into(),
+            allocated_memory_bytes: size_of::<Self>()
+                + (self.entities.capacity() * entity_size)
+                + (self.indices.capacity() * index_size)
+                + ((self.entered.capacity() + self.exited.capacity()) * entity_size),
+            used_memory_bytes: size_of::<Self>()
+                + (self.entities.len() * entity_size)
+                + (self.indices.len() * index_size)
+                + ((self.entered.len() + self.exited.len()) * entity_size),
+            component_count: self.entities.len(),
+        })
+    }
+    fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+/// Last sync of the reactive sets reading each component storage.
+pub(crate) struct ReactiveReaders {
+    components: ShipHashMap<StorageId, ComponentReaders>,
+}
+
+struct ComponentReaders {
+    /// The removal tracking was enabled by a reactive set, it can be cleared once all of them saw it.
+    owns_removal: bool,
+    /// The deletion tracking was enabled by a reactive set, it can be cleared once all of them saw it.
+    owns_deletion: bool,
+    last_syncs: ShipHashMap<StorageId, TrackingTimestamp>,
+}
+
+impl ReactiveReaders {
+    fn new() -> ReactiveReaders {
+        ReactiveReaders {
+            components: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+        }
+    }
+}
+
+impl Storage for ReactiveReaders {}
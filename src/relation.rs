@@ -0,0 +1,211 @@
+use crate::entity_id::EntityId;
+use crate::memory_usage::StorageMemoryUsage;
+use crate::storage::Storage;
+use crate::tracking::TrackingTimestamp;
+use crate::ShipHashMap;
+use alloc::vec::Vec;
+use core::hash::BuildHasherDefault;
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+/// What happens to a relation when its target is deleted.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CleanupPolicy {
+    /// The relation is removed, its source is left untouched.
+    RemoveRelation,
+    /// The source of the relation is deleted too.
+    DeleteSource,
+}
+
+/// Kind of relation between two entities, stored in [`Relations`].
+///
+/// An entity can have multiple relations of the same kind, each to a different target.
+///
+/// ### Example
+///
+/// ```
+/// use shipyard::{CleanupPolicy, EntitiesView, Relation, World};
+///
+/// struct ChildOf;
+///
+/// impl Relation for ChildOf {
+///     const CLEANUP: CleanupPolicy = CleanupPolicy::DeleteSource;
+/// }
+///
+/// let mut world = World::new();
+///
+/// let parent = world.add_entity(());
+/// let child = world.add_entity(());
+///
+/// world.add_relation::<ChildOf>(child, parent);
+/// assert_eq!(world.relation_sources::<ChildOf>(parent).unwrap(), vec![child]);
+///
+/// world.delete_entity(parent);
+/// assert!(!world.borrow::<EntitiesView>().unwrap().is_alive(child));
+/// ```
+pub trait Relation: Send + Sync + 'static {
+    /// Applied to the relations pointing to an entity when it is deleted.
+    const CLEANUP: CleanupPolicy = CleanupPolicy::RemoveRelation;
+}
+
+/// Storage of all `R` relations, both from source to targets and from target to sources.
+///
+/// Relations are removed when their source is deleted or stripped.
+/// When their target is deleted, `R`'s [`CleanupPolicy`] applies.
+pub struct Relations<R: Relation> {
+    targets: ShipHashMap<EntityId, Vec<EntityId>>,
+    sources: ShipHashMap<EntityId, Vec<EntityId>>,
+    len: usize,
+    _phantom: PhantomData<fn() -> R>,
+}
+
+impl<R: Relation> Default for Relations<R> {
+    fn default() -> Self {
+        Relations {
+            targets: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            sources: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            len: 0,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<R: Relation> Relations<R> {
+    /// Creates an empty storage.
+    pub fn new() -> Relations<R> {
+        Relations::default()
+    }
+    /// Adds a relation from `source` to `target`.\
+    /// Returns `false` if it was already present.
+    pub fn insert(&mut self, source: EntityId, target: EntityId) -> bool {
+        let targets = self.targets.entry(source).or_default();
+
+        if targets.contains(&target) {
+            return false;
+        }
+
+        targets.push(target);
+        self.sources.entry(target).or_default().push(source);
+        self.len += 1;
+
+        true
+    }
+    /// Removes the relation from `source` to `target`.\
+    /// Returns `false` if it wasn't present.
+    pub fn remove(&mut self, source: EntityId, target: EntityId) -> bool {
+        if remove_from(&mut self.targets, source, target) {
+            remove_from(&mut self.sources, target, source);
+            self.len -= 1;
+
+            true
+        } else {
+            false
+        }
+    }
+    /// Returns `true` if there is a relation from `source` to `target`.
+    pub fn contains(&self, source: EntityId, target: EntityId) -> bool {
+        self.targets(source).contains(&target)
+    }
+    /// Returns the targets of all relations starting from `source`.
+    pub fn targets(&self, source: EntityId) -> &[EntityId] {
+        self.targets.get(&source).map_or(&[], Vec::as_slice)
+    }
+    /// Returns the sources of all relations pointing to `target`.
+    pub fn sources(&self, target: EntityId) -> &[EntityId] {
+        self.sources.get(&target).map_or(&[], Vec::as_slice)
+    }
+    /// Removes all relations starting from `source`.
+    pub fn remove_source(&mut self, source: EntityId) {
+        if let Some(targets) = self.targets.remove(&source) {
+            self.len -= targets.len();
+
+            for target in targets {
+                remove_from(&mut self.sources, target, source);
+            }
+        }
+    }
+    /// Removes all relations pointing to `target` and returns their sources.
+    pub fn remove_target(&mut self, target: EntityId) -> Vec<EntityId> {
+        let sources = self.sources.remove(&target).unwrap_or_default();
+
+        self.len -= sources.len();
+
+        for &source in &sources {
+            remove_from(&mut self.targets, source, target);
+        }
+
+        sources
+    }
+    /// Returns the number of relations.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if there is no relation.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Removes `value` from the list of `key` and returns `true` if it was present.
+fn remove_from(
+    map: &mut ShipHashMap<EntityId, Vec<EntityId>>,
+    key: EntityId,
+    value: EntityId,
+) -> bool {
+    if let Some(values) = map.get_mut(&key) {
+        if let Some(index) = values.iter().position(|&other| other == value) {
+            values.swap_remove(index);
+
+            if values.is_empty() {
+                map.remove(&key);
+            }
+
+            return true;
+        }
+    }
+
+    false
+}
+
+impl<R: Relation> Storage for Relations<R> {
+    #[inline]
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        self.remove_source(entity);
+    }
+    fn delete_entity(
+        &mut self,
+        entity: EntityId,
+        _current: TrackingTimestamp,
+        cascade: &mut Vec<EntityId>,
+    ) {
+        self.remove_source(entity);
+
+        let sources = self.remove_target(entity);
+
+        if R::CLEANUP == CleanupPolicy::DeleteSource {
+            cascade.extend(sources);
+        }
+    }
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        self.targets.clear();
+        self.sources.clear();
+        self.len = 0;
+    }
+    fn memory_usage(&self) -> Option<StorageMemoryUsage> {
+        let entry_size = size_of::<EntityId>() + size_of::<Vec<EntityId>>();
+
+        Some(StorageMemoryUsage {
+            storage_name: core::any::type_name::<Self>().into(),
+            allocated_memory_bytes: size_of::<Self>()
+                + ((self.targets.capacity() + self.sources.capacity()) * entry_size)
+                + (2 * self.len * size_of::<EntityId>()),
+            used_memory_bytes: size_of::<Self>()
+                + ((self.targets.len() + self.sources.len()) * entry_size)
+                + (2 * self.len * size_of::<EntityId>()),
+            component_count: self.len,
+        })
+    }
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
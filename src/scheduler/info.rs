@@ -127,6 +127,9 @@ pub struct TypeInfo {
     pub storage_id: StorageId,
     #[allow(missing_docs)]
     pub thread_safe: bool,
+    /// The storage doesn't have to be present in the `World`, for example when borrowed through an `Option`.
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub optional: bool,
 }
 
 impl PartialEq for TypeInfo {
@@ -165,6 +168,7 @@ impl core::fmt::Debug for TypeInfo {
             .field("name", &self.name)
             .field("mutability", &self.mutability)
             .field("thread_safe", &self.thread_safe)
+            .field("optional", &self.optional)
             .finish()
     }
 }
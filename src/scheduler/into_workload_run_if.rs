@@ -56,6 +56,7 @@ macro_rules! impl_into_workload_run_if {
                     storage_id: StorageId::of::<AllStorages>(),
                     mutability: Mutability::Exclusive,
                     thread_safe: true,
+                    optional: false,
                 }) && borrows.len() > 1
                 {
                     return Err(error::InvalidSystem::AllStorages);
@@ -141,6 +142,7 @@ macro_rules! impl_into_workload_run_if {
                     storage_id: StorageId::of::<AllStorages>(),
                     mutability: Mutability::Exclusive,
                     thread_safe: true,
+                    optional: false,
                 }) && borrows.len() > 1
                 {
                     return Err(error::InvalidSystem::AllStorages);
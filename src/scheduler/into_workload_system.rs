@@ -116,6 +116,7 @@ macro_rules! impl_into_workload_system {
                     storage_id: StorageId::of::<AllStorages>(),
                     mutability: Mutability::Exclusive,
                     thread_safe: true,
+                    optional: false,
                 }) && borrows.len() > 1
                 {
                     return Err(error::InvalidSystem::AllStorages);
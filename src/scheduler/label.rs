@@ -29,11 +29,7 @@ macro_rules! impl_label {
                     self
                 }
                 fn dyn_eq(&self, other: &dyn Label) -> bool {
-                    if let Some(other) = other.as_any().downcast_ref::<Self>() {
-                        self == other
-                    } else {
-                        false
-                    }
+                    str_label(other) == Some(&**self)
                 }
                 fn dyn_hash(&self, mut state: &mut dyn Hasher) {
                     Self::hash(self, &mut state);
@@ -73,6 +69,19 @@ impl Label for TypeId {
 
 impl_label![&'static str, String, Cow<'static, str>];
 
+/// Returns the text of string labels, `&'static str`, `String` and `Cow<'static, str>` labels are equal when their text is.
+fn str_label(label: &dyn Label) -> Option<&str> {
+    let label = label.as_any();
+
+    if let Some(label) = label.downcast_ref::<&'static str>() {
+        Some(label)
+    } else if let Some(label) = label.downcast_ref::<String>() {
+        Some(label)
+    } else {
+        label.downcast_ref::<Cow<'static, str>>().map(|label| &**label)
+    }
+}
+
 impl Label for Box<dyn Label> {
     fn as_any(&self) -> &dyn Any {
         (**self).as_any()
@@ -100,7 +109,12 @@ impl Clone for Box<dyn Label> {
 
 impl Hash for dyn Label {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.as_any().type_id().hash(state);
+        // all string types hash the same so they can be used interchangeably
+        if str_label(self).is_some() {
+            core::any::TypeId::of::<str>().hash(state);
+        } else {
+            self.as_any().type_id().hash(state);
+        }
         self.dyn_hash(state);
     }
 }
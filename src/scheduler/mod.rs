@@ -4,6 +4,7 @@ mod into_workload_run_if;
 mod into_workload_system;
 mod into_workload_try_system;
 mod label;
+mod registry;
 mod system;
 mod system_modificator;
 mod workload;
@@ -18,6 +19,7 @@ pub use into_workload::IntoWorkload;
 pub use into_workload_system::IntoWorkloadSystem;
 pub use into_workload_try_system::IntoWorkloadTrySystem;
 pub use label::{AsLabel, Label};
+pub use registry::{SystemDescription, SystemRegistry, WorkloadDescription};
 pub use system::WorkloadSystem;
 pub use system_modificator::SystemModificator;
 pub use workload::{ScheduledWorkload, Workload};
@@ -40,6 +42,8 @@ pub(super) struct Batches {
     pub(super) sequential: Vec<usize>,
     pub(super) sequential_run_if:
         Vec<Option<Box<dyn Fn(&World) -> Result<bool, error::Run> + Send + Sync>>>,
+    /// Tags of each system, in the same order as `sequential`
+    pub(super) sequential_tags: Vec<Vec<Box<dyn Label>>>,
     pub(super) run_if: Option<Box<dyn WorkloadRunIfFn>>,
 }
 
@@ -0,0 +1,207 @@
+use crate::error;
+use crate::scheduler::into_workload_run_if::IntoRunIf;
+use crate::scheduler::system::RunIf;
+use crate::scheduler::{IntoWorkloadSystem, SystemModificator, Workload, WorkloadSystem};
+use crate::ShipHashMap;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::BuildHasherDefault;
+
+type SystemFactory = Box<dyn Fn() -> Result<WorkloadSystem, error::InvalidSystem> + Send + Sync>;
+type RunIfFactory = Box<dyn Fn() -> Result<RunIf, error::InvalidSystem> + Send + Sync>;
+
+/// Maps names to systems and run conditions to build [`Workload`]s from a [`WorkloadDescription`].
+///
+/// Systems are tagged with their registered name, other systems can use it in `before_all`/`after_all`.\
+/// Names and tags coming from a description are `String` labels, they match `&'static str` labels with the same text.
+///
+/// ### Example
+/// ```
+/// use shipyard::{
+///     SystemDescription, SystemRegistry, Unique, UniqueView, UniqueViewMut, World,
+///     WorkloadDescription,
+/// };
+///
+/// #[derive(Unique)]
+/// struct Score(u32);
+///
+/// let mut registry = SystemRegistry::new();
+/// registry
+///     .register("double", |mut score: UniqueViewMut<Score>| score.0 *= 2)
+///     .register("increment", |mut score: UniqueViewMut<Score>| score.0 += 1)
+///     .register_run_if("always", || true);
+///
+/// let description = WorkloadDescription {
+///     name: "game".to_string(),
+///     systems: vec![
+///         SystemDescription {
+///             name: "double".to_string(),
+///             after_all: vec!["increment".to_string()],
+///             run_if: Some("always".to_string()),
+///             ..Default::default()
+///         },
+///         SystemDescription {
+///             name: "increment".to_string(),
+///             ..Default::default()
+///         },
+///     ],
+/// };
+///
+/// let world = World::new();
+/// world.add_unique(Score(1));
+///
+/// registry
+///     .load_workload(&description)
+///     .unwrap()
+///     .add_to_world(&world)
+///     .unwrap();
+///
+/// world.run_workload("game").unwrap();
+///
+/// assert_eq!(world.borrow::<UniqueView<Score>>().unwrap().0, 4);
+/// ```
+pub struct SystemRegistry {
+    systems: ShipHashMap<String, SystemFactory>,
+    run_ifs: ShipHashMap<String, RunIfFactory>,
+}
+
+impl Default for SystemRegistry {
+    fn default() -> Self {
+        SystemRegistry {
+            systems: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            run_ifs: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+        }
+    }
+}
+
+impl SystemRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> SystemRegistry {
+        SystemRegistry::default()
+    }
+    /// Registers `system` under `name`.\
+    /// If a system was already registered with this name, it is replaced.
+    pub fn register<B, R, S>(&mut self, name: impl Into<String>, system: S) -> &mut SystemRegistry
+    where
+        S: IntoWorkloadSystem<B, R> + Clone + Send + Sync + 'static,
+    {
+        self.systems.insert(
+            name.into(),
+            Box::new(move || system.clone().into_workload_system()),
+        );
+
+        self
+    }
+    /// Registers `run_if` under `name`.\
+    /// If a run condition was already registered with this name, it is replaced.
+    pub fn register_run_if<B, Run>(
+        &mut self,
+        name: impl Into<String>,
+        run_if: Run,
+    ) -> &mut SystemRegistry
+    where
+        Run: IntoRunIf<B> + Clone + Send + Sync + 'static,
+    {
+        self.run_ifs.insert(
+            name.into(),
+            Box::new(move || run_if.clone().into_workload_run_if()),
+        );
+
+        self
+    }
+    /// Returns `true` if a system is registered under `name`.
+    pub fn contains_system(&self, name: &str) -> bool {
+        self.systems.contains_key(name)
+    }
+    /// Returns `true` if a run condition is registered under `name`.
+    pub fn contains_run_if(&self, name: &str) -> bool {
+        self.run_ifs.contains_key(name)
+    }
+    /// Creates a [`Workload`] following `description`.
+    ///
+    /// Systems are added in the order of the description.
+    ///
+    /// ### Errors
+    ///
+    /// - A system is not registered.
+    /// - A run condition is not registered.
+    /// - A registered system or run condition is invalid.
+    pub fn load_workload(
+        &self,
+        description: &WorkloadDescription,
+    ) -> Result<Workload, error::LoadWorkload> {
+        let mut workload = Workload::new(description.name.clone());
+
+        for system_description in &description.systems {
+            let factory = self.systems.get(&system_description.name).ok_or_else(|| {
+                error::LoadWorkload::MissingSystem(system_description.name.clone())
+            })?;
+
+            let mut system = factory()
+                .map_err(|err| {
+                    error::LoadWorkload::InvalidSystem(system_description.name.clone(), err)
+                })?
+                .display_name(system_description.name.clone())
+                .tag(system_description.name.clone());
+
+            for tag in &system_description.tags {
+                system = system.tag(tag.clone());
+            }
+            for before in &system_description.before_all {
+                system = system.before_all(before.clone());
+            }
+            for after in &system_description.after_all {
+                system = system.after_all(after.clone());
+            }
+
+            if let Some(run_if_name) = &system_description.run_if {
+                let factory = self
+                    .run_ifs
+                    .get(run_if_name)
+                    .ok_or_else(|| error::LoadWorkload::MissingRunIf(run_if_name.clone()))?;
+
+                let run_if = factory()
+                    .map_err(|err| error::LoadWorkload::InvalidSystem(run_if_name.clone(), err))?;
+
+                system = system.run_if(run_if);
+            }
+
+            workload = workload.with_system(system);
+        }
+
+        Ok(workload)
+    }
+}
+
+/// Data driven description of a [`Workload`], used by [`SystemRegistry::load_workload`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkloadDescription {
+    #[allow(missing_docs)]
+    pub name: String,
+    /// Systems of the workload, in order.
+    pub systems: Vec<SystemDescription>,
+}
+
+/// Data driven description of a system, used by [`SystemRegistry::load_workload`].
+///
+/// All names refer to the names given to [`SystemRegistry::register`] and [`SystemRegistry::register_run_if`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemDescription {
+    #[allow(missing_docs)]
+    pub name: String,
+    /// Additional tags, the system is always tagged with its name.
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub tags: Vec<String>,
+    /// Systems or tags this system has to run before.
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub before_all: Vec<String>,
+    /// Systems or tags this system has to run after.
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub after_all: Vec<String>,
+    /// Name of the run condition.
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub run_if: Option<String>,
+}
@@ -64,6 +64,7 @@ impl ScheduledWorkload {
             &self.system_names,
             &self.workloads[&self.name],
             &self.name,
+            None,
         )
     }
 
@@ -464,7 +465,7 @@ impl Workload {
 
         for enable_tracking_fn in &tracking_to_enable {
             (enable_tracking_fn)(&all_storages).map_err(|err| match err {
-                | error::GetStorage::StorageBorrow { name, id, borrow } => {
+                | error::GetStorage::StorageBorrow { name, id, borrow, .. } => {
                     error::AddWorkload::TrackingStorageBorrow { name, id, borrow }
                 },
                 | _ => unreachable!(),
@@ -556,7 +557,9 @@ fn check_uniques_in_systems(
     } = system;
 
     for type_info in borrow_constraints {
-        if type_info.name.starts_with(unique_name) && !storages.contains_key(&type_info.storage_id)
+        if !type_info.optional
+            && type_info.name.starts_with(unique_name)
+            && !storages.contains_key(&type_info.storage_id)
         {
             return Some(Err(error::UniquePresence::Unique(type_info.clone())));
         }
@@ -626,6 +629,7 @@ fn create_workload(
                 display_name,
                 borrow_constraints,
                 run_if,
+                tags,
                 ..
             },
         ) = collected_systems.pop().unwrap();
@@ -653,6 +657,7 @@ fn create_workload(
 
         batches.sequential.push(system_index);
         batches.sequential_run_if.push(run_if);
+        batches.sequential_tags.push(tags);
 
         let batch_info = BatchInfo {
             systems: (
@@ -916,6 +921,11 @@ fn create_workload(
         )?;
     }
 
+    batches.sequential_tags = seq_system_index_map
+        .iter()
+        .map(|&index| collected_tags[index].clone())
+        .collect();
+
     for (i, &index) in seq_system_index_map.iter().enumerate() {
         let mut require_in_workload = collected_require_in_workload[index].to_vec();
         let mut require_before = collected_before[index].to_vec();
@@ -1690,6 +1700,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1721,6 +1732,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1755,6 +1767,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1789,6 +1802,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1823,6 +1837,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1847,6 +1862,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1889,6 +1905,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1, 2],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1922,6 +1939,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1945,6 +1963,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 0],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1969,6 +1988,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -1992,6 +2012,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -2037,6 +2058,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 0],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -2063,6 +2085,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -2086,6 +2109,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -2109,6 +2133,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -2135,6 +2160,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -2169,6 +2195,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -2194,6 +2221,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -2239,6 +2267,7 @@ mod tests {
                 parallel_run_if: Vec::new(),
                 sequential: vec![0, 1, 2, 3],
                 sequential_run_if: Vec::new(),
+                sequential_tags: Vec::new(),
                 run_if: None,
             })
         );
@@ -2553,6 +2582,7 @@ mod tests {
             parallel_run_if: Vec::new(),
             sequential: vec![0, 1, 2],
             sequential_run_if: Vec::new(),
+            sequential_tags: Vec::new(),
             run_if: None,
         });
     }
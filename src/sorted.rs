@@ -0,0 +1,277 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::atomic_refcell::{ARefMut, SharedBorrow};
+use crate::borrow::{Borrow, BorrowInfo, Mutability};
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::memory_usage::StorageMemoryUsage;
+use crate::scheduler::TypeInfo;
+use crate::sparse_set::{ChangeReader, SparseSet};
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::views::View;
+use crate::ShipHashMap;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::hash::BuildHasherDefault;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ops::{Bound, Deref, RangeBounds};
+
+/// Components that can be iterated in order with [`World::range_by`](crate::World::range_by).
+///
+/// ### Example
+///
+/// ```
+/// use shipyard::{Component, SortKey, World};
+///
+/// #[derive(Component)]
+/// struct ZIndex(i32);
+///
+/// impl SortKey for ZIndex {
+///     type Key = i32;
+///
+///     fn sort_key(&self) -> i32 {
+///         self.0
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.add_sorted::<ZIndex>();
+///
+/// let top = world.add_entity(ZIndex(10));
+/// let bottom = world.add_entity(ZIndex(-3));
+/// let middle = world.add_entity(ZIndex(0));
+///
+/// assert_eq!(world.range_by::<ZIndex>(..).unwrap(), vec![bottom, middle, top]);
+/// assert_eq!(world.range_by::<ZIndex>(-5..5).unwrap(), vec![bottom, middle]);
+/// ```
+pub trait SortKey: Component {
+    /// Type used to order entities.
+    type Key: Ord + Clone + Send + Sync + 'static;
+
+    /// Returns the key of this component.
+    fn sort_key(&self) -> Self::Key;
+}
+
+/// Entities with a `T` component ordered by [`SortKey::sort_key`], created by [`World::add_sorted`](crate::World::add_sorted).
+///
+/// Entities with the same key are ordered by [`EntityId`].\
+/// Borrowing a [`SortedView`] moves each entity whose key changed since the previous borrow
+/// to its new position with a binary search, the rest of the order isn't sorted again.
+pub struct Sorted<T: SortKey> {
+    entries: Vec<(T::Key, EntityId)>,
+    keys: ShipHashMap<EntityId, T::Key>,
+    reader: ChangeReader,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: SortKey> Sorted<T> {
+    /// Sorts all components currently in `sparse_set`.
+    pub(crate) fn new(sparse_set: &mut SparseSet<T>) -> Sorted<T> {
+        let mut sorted = Sorted {
+            entries: Vec::new(),
+            keys: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            reader: sparse_set.add_change_reader(),
+            _phantom: PhantomData,
+        };
+
+        sorted.rebuild(sparse_set);
+
+        sorted
+    }
+
+    /// Moves the entities whose key changed since the last sync.
+    pub(crate) fn sync(&mut self, sparse_set: &SparseSet<T>) {
+        let synced = sparse_set.read_changes(self.reader, |entity, component| match component {
+            Some(component) => self.insert(entity, component.sort_key()),
+            None => self.remove(entity),
+        });
+
+        if !synced {
+            self.rebuild(sparse_set);
+        }
+    }
+
+    /// Returns the entities whose key is within `range`, in order.
+    pub(crate) fn range<R: RangeBounds<T::Key>>(&self, range: R) -> &[(T::Key, EntityId)] {
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.entries.partition_point(|(key, _)| key < start),
+            Bound::Excluded(start) => self.entries.partition_point(|(key, _)| key <= start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.entries.partition_point(|(key, _)| key <= end),
+            Bound::Excluded(end) => self.entries.partition_point(|(key, _)| key < end),
+            Bound::Unbounded => self.entries.len(),
+        };
+
+        &self.entries[start..end.max(start)]
+    }
+
+    /// Returns the number of sorted entities.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no entity is sorted.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn rebuild(&mut self, sparse_set: &SparseSet<T>) {
+        self.entries.clear();
+        self.keys.clear();
+
+        for (&entity, component) in sparse_set.dense.iter().zip(&sparse_set.data) {
+            let key = component.sort_key();
+
+            self.keys.insert(entity, key.clone());
+            self.entries.push((key, entity));
+        }
+
+        self.entries.sort_unstable();
+    }
+
+    fn insert(&mut self, entity: EntityId, key: T::Key) {
+        if self.keys.get(&entity) == Some(&key) {
+            return;
+        }
+
+        self.remove(entity);
+
+        let entry = (key, entity);
+        let index = self
+            .entries
+            .binary_search(&entry)
+            .unwrap_or_else(|index| index);
+
+        self.keys.insert(entity, entry.0.clone());
+        self.entries.insert(index, entry);
+    }
+
+    fn remove(&mut self, entity: EntityId) {
+        if let Some(key) = self.keys.remove(&entity) {
+            if let Ok(index) = self.entries.binary_search(&(key, entity)) {
+                self.entries.remove(index);
+            }
+        }
+    }
+}
+
+impl<T: SortKey> Storage for Sorted<T> {
+    #[inline]
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        self.remove(entity);
+    }
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        self.entries.clear();
+        self.keys.clear();
+    }
+    fn memory_usage(&self) -> Option<StorageMemoryUsage> {
+        let entry_size = size_of::<T::Key>() + size_of::<EntityId>();
+
+        Some(StorageMemoryUsage {
+            storage_name: type_name::<Self>().into(),
+            allocated_memory_bytes: size_of::<Self>()
+                + (self.entries.capacity() * size_of::<(T::Key, EntityId)>())
+                + (self.keys.capacity() * entry_size),
+            used_memory_bytes: size_of::<Self>()
+                + (self.entries.len() * size_of::<(T::Key, EntityId)>())
+                + (self.keys.len() * entry_size),
+            component_count: self.entries.len(),
+        })
+    }
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Shared view over `T` components that can also go through them in [`SortKey`] order, usable in systems.
+///
+/// Borrowing the view brings `T`'s [`Sorted`] order up to date, see [`World::add_sorted`](crate::World::add_sorted).
+///
+/// ### Example
+///
+/// ```
+/// use shipyard::{Component, SortKey, SortedView, World};
+///
+/// #[derive(Component)]
+/// struct ZIndex(i32);
+///
+/// impl SortKey for ZIndex {
+///     type Key = i32;
+///
+///     fn sort_key(&self) -> i32 {
+///         self.0
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.add_sorted::<ZIndex>();
+///
+/// let top = world.add_entity(ZIndex(10));
+/// let bottom = world.add_entity(ZIndex(-3));
+///
+/// world.run(|z_indices: SortedView<ZIndex>| {
+///     assert_eq!(z_indices.range(..).collect::<Vec<_>>(), vec![bottom, top]);
+///     assert_eq!(z_indices[top].0, 10);
+/// });
+/// ```
+pub struct SortedView<'v, T: SortKey> {
+    view: View<'v, T>,
+    sorted: ARefMut<'v, &'v mut Sorted<T>>,
+}
+
+impl<T: SortKey> SortedView<'_, T> {
+    /// Returns the entities whose key is within `range`, ordered by key.
+    pub fn range<R: RangeBounds<T::Key>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = EntityId> + ExactSizeIterator + '_ {
+        self.sorted.range(range).iter().map(|&(_, entity)| entity)
+    }
+}
+
+impl<'v, T: SortKey> Deref for SortedView<'v, T> {
+    type Target = View<'v, T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+impl<T: SortKey + Send + Sync> Borrow for SortedView<'_, T> {
+    type View<'a> = SortedView<'a, T>;
+
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        last_run: Option<TrackingTimestamp>,
+        current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        let view = View::<T>::borrow(all_storages, all_borrow, last_run, current)?;
+        let mut sorted = all_storages.custom_storage_mut::<Sorted<T>>()?;
+
+        sorted.sync(&view);
+
+        Ok(SortedView { view, sorted })
+    }
+}
+
+// SAFE: `T`'s storage and order are recorded.
+unsafe impl<T: SortKey + Send + Sync> BorrowInfo for SortedView<'_, T> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        <View<'_, T>>::borrow_info(info);
+
+        info.push(TypeInfo {
+            name: type_name::<Sorted<T>>().into(),
+            mutability: Mutability::Exclusive,
+            storage_id: StorageId::of::<Sorted<T>>(),
+            thread_safe: true,
+            optional: false,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
@@ -1,6 +1,5 @@
 use crate::all_storages::{AllStorages, CustomStorageAccess};
 use crate::component::Component;
-use crate::entities::Entities;
 use crate::entity_id::EntityId;
 use crate::reserve::BulkEntityIter;
 use crate::sparse_set::SparseSet;
@@ -44,7 +43,7 @@ impl BulkInsert for () {
         let iter = iter.into_iter();
         let len = iter.size_hint().0;
 
-        let entities = all_storages.exclusive_storage_mut::<Entities>().unwrap();
+        let entities = all_storages.exclusive_entities_mut();
         let entities_len = entities.data.len();
 
         entities.bulk_generate(len);
@@ -103,10 +102,11 @@ impl<T: Send + Sync + Component> BulkInsert for T {
                 *sparse.get_mut_unchecked(entity) = EntityId::new((old_len + i) as u64);
             }
         }
+        sparse_set.log_pushed(old_len);
 
         drop((entities, sparse_set));
 
-        let entities = all_storages.exclusive_storage_mut::<Entities>().unwrap();
+        let entities = all_storages.exclusive_entities_mut();
 
         BulkEntityIter {
             iter: entities.data[entities_len..].iter().copied(),
@@ -182,6 +182,7 @@ macro_rules! impl_bulk_insert {
                         *sparse.get_mut_unchecked(entity) = EntityId::new((old_len + i) as u64);
                     }
                 }
+                $sparse_set1.log_pushed(old_len);
                 $(
                     let old_len = $sparse_set.dense.len() - new_entities_count;
                     let SparseSet { sparse, dense, .. } = &mut *$sparse_set;
@@ -192,11 +193,12 @@ macro_rules! impl_bulk_insert {
                             *sparse.get_mut_unchecked(entity) = EntityId::new((old_len + i) as u64);
                         }
                     }
+                    $sparse_set.log_pushed(old_len);
                 )*
 
                 drop((entities, $sparse_set1, $($sparse_set),*));
 
-                let entities = all_storages.exclusive_storage_mut::<Entities>().unwrap();
+                let entities = all_storages.exclusive_entities_mut();
 
                 BulkEntityIter {
                     iter: entities.data[entities_len..].iter().copied(),
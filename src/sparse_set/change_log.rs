@@ -0,0 +1,443 @@
+use crate::entity_id::EntityId;
+use alloc::vec::Vec;
+use core::mem::size_of;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Link of a component that isn't in the pending list.
+const UNLINKED: usize = usize::MAX;
+/// Link of the first component pushed to the pending list.
+const END: usize = usize::MAX - 1;
+/// Cursor of a reader that has to start over from the storage's content.
+const STALE: usize = usize::MAX;
+/// Number of changes kept before the log is trimmed for the first time.
+const MIN_TRIM: usize = 64;
+
+/// Position of a consumer in a storage's [`ChangeLog`].
+#[derive(Clone, Copy)]
+pub(crate) struct ChangeReader(usize);
+
+/// Entities whose component was inserted, modified, removed or deleted, in the order of the changes.
+///
+/// Changes are only logged while the log has readers, each reader has its own cursor.
+/// Entries all readers went past are dropped, a reader falling behind by more changes
+/// than the storage has components is sent back to the storage's content instead.
+///
+/// [`Mut`](crate::Mut) only borrows its component, so modifications can't be pushed to `entities` directly.
+/// The first modification of a component links its index into a lock-free pending list,
+/// the list is moved to `entities` the next time the storage is borrowed exclusively.
+///
+/// A single reader can also ask for the component as it was before each change.
+/// This reader is never sent back to the storage's content, the log keeps its entries until it reads them.
+pub(crate) struct ChangeLog<T> {
+    entities: Vec<EntityId>,
+    /// Component before the change of the entry at the same position in `entities`, empty without image reader.
+    images: Vec<Option<T>>,
+    /// Number of entries dropped from the front of `entities`.
+    dropped: usize,
+    cursors: Vec<AtomicUsize>,
+    image_reader: Option<ChangeReader>,
+    /// Clones a `T` into an `Option<T>`, set by the image reader.
+    capture: Option<Capture>,
+    /// `entities` length triggering the next trim.
+    trim_at: usize,
+    pending: Pending,
+    /// Next index in the pending list, for each component index.
+    links: Vec<usize>,
+    /// `false` for components linked by [`Mut::map`](crate::Mut::map) or [`RefMut`](crate::RefMut) but not modified yet.
+    modified: Vec<bool>,
+    /// Image of each component in the pending list, empty without image reader.
+    pending_images: Vec<Option<T>>,
+}
+
+/// Type erased [`capture`], the storage doesn't require `T: Clone`.
+type Capture = unsafe fn(*const (), *mut ());
+
+/// # Safety
+///
+/// `src` has to point to a `T` and `dst` to an `Option<T>`.
+unsafe fn capture<T: Clone>(src: *const (), dst: *mut ()) {
+    *dst.cast::<Option<T>>() = Some((*src.cast::<T>()).clone());
+}
+
+/// Head of the pending list, shared by all [`Mut`](crate::Mut) of a storage.
+// The storage's borrow synchronizes the readers with the `Mut`s, the atomics only have to be atomic.
+pub(crate) struct Pending {
+    head: AtomicUsize,
+    len: AtomicUsize,
+}
+
+impl Pending {
+    /// Pushes `index` to the list and returns the index it has to link to.
+    #[inline]
+    fn push(&self, index: usize) -> usize {
+        self.len.fetch_add(1, Ordering::Relaxed);
+        self.head.swap(index, Ordering::Relaxed)
+    }
+}
+
+impl<T> ChangeLog<T> {
+    pub(crate) fn new() -> ChangeLog<T> {
+        ChangeLog {
+            entities: Vec::new(),
+            images: Vec::new(),
+            dropped: 0,
+            cursors: Vec::new(),
+            image_reader: None,
+            capture: None,
+            trim_at: MIN_TRIM,
+            pending: Pending {
+                head: AtomicUsize::new(END),
+                len: AtomicUsize::new(0),
+            },
+            links: Vec::new(),
+            modified: Vec::new(),
+            pending_images: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if changes are logged.
+    #[inline]
+    pub(crate) fn is_active(&self) -> bool {
+        !self.cursors.is_empty()
+    }
+
+    /// Adds a reader starting after the changes already logged.
+    pub(crate) fn add_reader(&mut self, dense: &[EntityId]) -> ChangeReader {
+        self.flush(dense);
+
+        self.cursors.push(AtomicUsize::new(self.end()));
+
+        ChangeReader(self.cursors.len() - 1)
+    }
+
+    /// Adds the reader of the components' before-images.
+    ///
+    /// ### Panics
+    ///
+    /// - The log already has an image reader.
+    pub(crate) fn add_image_reader(&mut self, dense: &[EntityId]) -> ChangeReader
+    where
+        T: Clone,
+    {
+        assert!(self.image_reader.is_none());
+
+        let reader = self.add_reader(dense);
+
+        self.images.resize_with(self.entities.len(), || None);
+        self.pending_images.resize_with(dense.len(), || None);
+        self.capture = Some(capture::<T>);
+        self.image_reader = Some(reader);
+
+        reader
+    }
+
+    /// Returns a copy of `component` if the log has an image reader.
+    #[inline]
+    pub(crate) fn capture(&self, component: &T) -> Option<T> {
+        self.capture.and_then(|capture| {
+            let mut image = None;
+            let src: *const T = component;
+            let dst: *mut Option<T> = &mut image;
+
+            // SAFE `capture` was made for `T`
+            unsafe { capture(src.cast(), dst.cast()) };
+
+            image
+        })
+    }
+
+    /// Moves the pending list to the log and makes room for a link per component.\
+    /// Has to be called before handing out [`Mut`](crate::Mut) and before `dense` is modified.
+    #[inline]
+    pub(crate) fn flush(&mut self, dense: &[EntityId]) {
+        if !self.is_active() {
+            return;
+        }
+
+        if *self.pending.len.get_mut() != 0 {
+            *self.pending.len.get_mut() = 0;
+
+            let start = self.entities.len();
+            let mut index = core::mem::replace(self.pending.head.get_mut(), END);
+            while index != END {
+                // the entry stays to keep the readers' positions valid
+                let entity = if core::mem::replace(&mut self.modified[index], false) {
+                    dense[index]
+                } else {
+                    EntityId::dead()
+                };
+
+                self.entities.push(entity);
+                if self.capture.is_some() {
+                    self.images.push(self.pending_images[index].take());
+                }
+
+                index = core::mem::replace(&mut self.links[index], UNLINKED);
+            }
+            // the list starts with the last modification
+            self.entities[start..].reverse();
+            if self.capture.is_some() {
+                self.images[start..].reverse();
+            }
+
+            if self.entities.len() >= self.trim_at {
+                self.trim(dense.len());
+            }
+        }
+
+        self.links.resize(dense.len(), UNLINKED);
+        self.modified.resize(dense.len(), false);
+        if self.capture.is_some() {
+            self.pending_images.resize_with(dense.len(), || None);
+        }
+    }
+
+    /// Logs a change of `entity`'s component, `dense` is the storage's content before the change.\
+    /// `image` is the component before the change, see [`ChangeLog::capture`].
+    #[inline]
+    pub(crate) fn record(&mut self, entity: EntityId, image: Option<T>, dense: &[EntityId]) {
+        if !self.is_active() {
+            return;
+        }
+
+        self.flush(dense);
+        self.entities.push(entity);
+        if self.capture.is_some() {
+            self.images.push(image);
+        }
+
+        if self.entities.len() >= self.trim_at {
+            self.trim(dense.len());
+        }
+    }
+
+    /// Calls `f` with the entities changed since `reader`'s last read, an entity can be reported multiple times.\
+    /// Returns `false` if `reader` has to start over from the storage's content.
+    pub(crate) fn read(
+        &self,
+        reader: ChangeReader,
+        dense: &[EntityId],
+        mut f: impl FnMut(EntityId),
+    ) -> bool {
+        let cursor = match self.cursors.get(reader.0) {
+            Some(cursor) => cursor,
+            // the storage was replaced
+            None => return false,
+        };
+
+        let end = self.end();
+        let start = cursor.swap(end + self.pending.len.load(Ordering::Relaxed), Ordering::Relaxed);
+
+        if start == STALE {
+            return false;
+        }
+
+        // the pending list only grows after a flush, past `end` the reader already saw all of it
+        if start <= end {
+            for &entity in &self.entities[start - self.dropped..] {
+                if !entity.is_dead() {
+                    f(entity);
+                }
+            }
+
+            let mut index = self.pending.head.load(Ordering::Relaxed);
+            while index != END {
+                if self.modified[index] {
+                    f(dense[index]);
+                }
+                index = self.links[index];
+            }
+        }
+
+        true
+    }
+
+    /// Calls `f` with the entities changed since the image reader's last read and their component before each change.\
+    /// Returns `false` if `reader` isn't the image reader of this log.
+    pub(crate) fn read_images(
+        &mut self,
+        reader: ChangeReader,
+        dense: &[EntityId],
+        mut f: impl FnMut(EntityId, Option<T>),
+    ) -> bool {
+        if self.image_reader.map(|image_reader| image_reader.0) != Some(reader.0)
+            || reader.0 >= self.cursors.len()
+        {
+            return false;
+        }
+
+        self.flush(dense);
+
+        let end = self.end();
+        let start = core::mem::replace(self.cursors[reader.0].get_mut(), end);
+
+        for (&entity, image) in self.entities[start - self.dropped..]
+            .iter()
+            .zip(&mut self.images[start - self.dropped..])
+        {
+            if !entity.is_dead() {
+                f(entity, image.take());
+            }
+        }
+
+        true
+    }
+
+    /// Returns a [`LogSlot`] for the component at `index` if changes are logged.
+    #[inline]
+    pub(crate) fn slot(&mut self, index: usize) -> Option<LogSlot<'_>> {
+        self.as_raw().map(|raw| {
+            // SAFE the slot borrows `self` mutably
+            unsafe { raw.slot(index) }
+        })
+    }
+
+    /// Returns a [`RawChangeLog`] to hand out [`LogSlot`] from an iterator, if changes are logged.
+    #[inline]
+    pub(crate) fn as_raw(&mut self) -> Option<RawChangeLog> {
+        if self.is_active() {
+            Some(RawChangeLog {
+                pending: &self.pending,
+                links: self.links.as_mut_ptr(),
+                modified: self.modified.as_mut_ptr(),
+                images: self
+                    .capture
+                    .map(|capture| (capture, self.pending_images.as_mut_ptr().cast())),
+                image_size: size_of::<Option<T>>(),
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn reserved_memory(&self) -> usize {
+        self.entities.capacity() * size_of::<EntityId>()
+            + self.images.capacity() * size_of::<Option<T>>()
+            + self.cursors.capacity() * size_of::<AtomicUsize>()
+            + self.links.capacity() * size_of::<usize>()
+            + self.modified.capacity() * size_of::<bool>()
+            + self.pending_images.capacity() * size_of::<Option<T>>()
+    }
+
+    pub(crate) fn used_memory(&self) -> usize {
+        self.entities.len() * size_of::<EntityId>()
+            + self.images.len() * size_of::<Option<T>>()
+            + self.cursors.len() * size_of::<AtomicUsize>()
+            + self.links.len() * size_of::<usize>()
+            + self.modified.len() * size_of::<bool>()
+            + self.pending_images.len() * size_of::<Option<T>>()
+    }
+
+    /// Position after the last flushed change.
+    #[inline]
+    fn end(&self) -> usize {
+        self.dropped + self.entities.len()
+    }
+
+    /// Drops the entries all readers went past.
+    fn trim(&mut self, len: usize) {
+        let end = self.end();
+        // going through more changes than there are components is slower than starting over
+        let max_lag = len.max(MIN_TRIM);
+        let mut oldest = end;
+
+        for (index, cursor) in self.cursors.iter_mut().enumerate() {
+            let cursor = cursor.get_mut();
+            let is_image_reader = self.image_reader.map(|reader| reader.0) == Some(index);
+
+            if *cursor != STALE {
+                if end - *cursor > max_lag && !is_image_reader {
+                    *cursor = STALE;
+                } else {
+                    oldest = oldest.min(*cursor);
+                }
+            }
+        }
+
+        self.entities.drain(..oldest - self.dropped);
+        if self.capture.is_some() {
+            self.images.drain(..oldest - self.dropped);
+        }
+        self.dropped = oldest;
+        self.trim_at = (self.entities.len() * 2).max(MIN_TRIM);
+    }
+}
+
+/// Pending list entry of a component, held by [`Mut`](crate::Mut) until the component is modified.
+pub(crate) struct LogSlot<'a> {
+    pending: &'a Pending,
+    index: usize,
+    link: &'a mut usize,
+    modified: &'a mut bool,
+    /// Capture function and pointer to the component's pending image, with an image reader.
+    image: Option<(Capture, *mut ())>,
+}
+
+// SAFE the slot only gives access to its component's entry, the same way `Mut` gives access to the component
+unsafe impl Send for LogSlot<'_> {}
+unsafe impl Sync for LogSlot<'_> {}
+
+impl LogSlot<'_> {
+    /// Links the component before it's handed out under another type, keeping a copy for the image reader.\
+    /// It's only logged as modified if [`LogSlot::log`] is called later.
+    ///
+    /// # Safety
+    ///
+    /// `component` has to point to the storage's component at this slot.
+    #[inline]
+    pub(crate) unsafe fn link_early(&mut self, component: *const ()) {
+        if let Some((capture, image)) = self.image {
+            if *self.link == UNLINKED {
+                capture(component, image);
+                *self.link = self.pending.push(self.index);
+            }
+        }
+    }
+
+    /// Adds the component to the pending list, if it isn't there already.
+    ///
+    /// # Safety
+    ///
+    /// If [`LogSlot::link_early`] wasn't called, `component` has to point to the storage's component at this slot.
+    #[inline]
+    pub(crate) unsafe fn log(self, component: *const ()) {
+        if *self.link == UNLINKED {
+            if let Some((capture, image)) = self.image {
+                capture(component, image);
+            }
+            *self.link = self.pending.push(self.index);
+        }
+
+        *self.modified = true;
+    }
+}
+
+/// [`ChangeLog`] used by [`FullRawWindowMut`](super::FullRawWindowMut).
+#[derive(Clone, Copy)]
+pub(crate) struct RawChangeLog {
+    pending: *const Pending,
+    links: *mut usize,
+    modified: *mut bool,
+    images: Option<(Capture, *mut ())>,
+    /// Size of an `Option<T>`, to find a component's image.
+    image_size: usize,
+}
+
+impl RawChangeLog {
+    /// # Safety
+    ///
+    /// The [`ChangeLog`] has to outlive `'a` and `index` has to be in bound.\
+    /// Only one [`LogSlot`] can exist for a given `index`.
+    #[inline]
+    pub(crate) unsafe fn slot<'a>(self, index: usize) -> LogSlot<'a> {
+        LogSlot {
+            pending: &*self.pending,
+            index,
+            link: &mut *self.links.add(index),
+            modified: &mut *self.modified.add(index),
+            image: self.images.map(|(capture, images)| {
+                (capture, images.cast::<u8>().add(index * self.image_size).cast())
+            }),
+        }
+    }
+}
@@ -1,8 +1,11 @@
 mod add_component;
 mod bulk_add_entity;
+mod change_log;
 mod delete;
 mod drain;
 mod remove;
+#[cfg(feature = "serde1")]
+mod serde;
 mod sparse_array;
 mod window;
 
@@ -13,12 +16,16 @@ pub use drain::SparseSetDrain;
 pub use remove::TupleRemove;
 pub use sparse_array::SparseArray;
 
+pub(crate) use change_log::{ChangeReader, LogSlot};
 pub(crate) use window::{FullRawWindow, FullRawWindowMut};
 
+use change_log::{ChangeLog, RawChangeLog};
+
 use crate::all_storages::AllStorages;
 #[cfg(feature = "thread_local")]
 use crate::borrow::{NonSend, NonSendSync, NonSync};
 use crate::component::Component;
+use crate::entities::Entities;
 use crate::entity_id::EntityId;
 use crate::memory_usage::StorageMemoryUsage;
 use crate::r#mut::Mut;
@@ -28,6 +35,7 @@ use crate::{error, track};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::any::type_name;
+use core::mem::size_of;
 use core::{
     cmp::{Ord, Ordering},
     fmt,
@@ -58,6 +66,7 @@ pub struct SparseSet<T: Component> {
     pub(crate) is_tracking_modification: bool,
     pub(crate) is_tracking_deletion: bool,
     pub(crate) is_tracking_removal: bool,
+    pub(crate) change_log: ChangeLog<T>,
     #[allow(clippy::type_complexity)]
     on_insertion: Option<Box<dyn FnMut(EntityId, &T) + Send + Sync>>,
     #[allow(clippy::type_complexity)]
@@ -89,6 +98,7 @@ impl<T: Component> SparseSet<T> {
             is_tracking_modification: false,
             is_tracking_deletion: false,
             is_tracking_removal: false,
+            change_log: ChangeLog::new(),
             on_insertion: None,
             on_removal: None,
         }
@@ -200,6 +210,10 @@ impl<T: Component> SparseSet<T> {
         value: T,
         current: TrackingTimestamp,
     ) -> Option<T> {
+        if self.change_log.is_active() {
+            self.log_insertion(entity);
+        }
+
         self.sparse.allocate_at(entity);
 
         // at this point there can't be nothing at the sparse index
@@ -260,6 +274,22 @@ impl<T: Component> SparseSet<T> {
 
         old_component
     }
+    /// Brings `entity` back to life if needed and gives it `value`.\
+    /// Returns `None` without inserting anything when a more recent entity already uses its index,
+    /// otherwise the component `entity` had.
+    pub(crate) fn insert_spawned(
+        &mut self,
+        entities: &mut Entities,
+        entity: EntityId,
+        value: T,
+        current: TrackingTimestamp,
+    ) -> Option<Option<T>> {
+        if entities.spawn(entity) {
+            Some(self.insert(entity, value, current))
+        } else {
+            None
+        }
+    }
 }
 
 impl<T: Component> SparseSet<T> {
@@ -294,6 +324,8 @@ impl<T: Component> SparseSet<T> {
         let sparse_entity = self.sparse.get(entity)?;
 
         if entity.gen() >= sparse_entity.gen() {
+            self.log_component(sparse_entity.uindex());
+
             unsafe {
                 *self.sparse.get_mut_unchecked(entity) = EntityId::dead();
             }
@@ -493,6 +525,98 @@ impl<T: Component> SparseSet<T> {
             })
         });
 
+        self.apply_permutation(&transform);
+    }
+    /// Sorts the `SparseSet` with a comparator function, preserving the order of equal elements.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        let mut transform: Vec<usize> = (0..self.dense.len()).collect();
+
+        transform.sort_by(|&i, &j| {
+            // SAFE dense and data have the same length
+            compare(unsafe { self.data.get_unchecked(i) }, unsafe {
+                self.data.get_unchecked(j)
+            })
+        });
+
+        self.apply_permutation(&transform);
+    }
+    /// Sorts the `SparseSet` with a key extraction function, preserving the order of equal elements.
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        let mut transform: Vec<usize> = (0..self.dense.len()).collect();
+
+        // SAFE dense and data have the same length
+        transform.sort_by_key(|&i| f(unsafe { self.data.get_unchecked(i) }));
+
+        self.apply_permutation(&transform);
+    }
+    /// Sorts the `SparseSet` with a key extraction function, preserving the order of equal elements.\
+    /// `f` is called only once per component, useful when the key is expensive to compute.
+    pub fn sort_by_cached_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        let mut transform: Vec<usize> = (0..self.dense.len()).collect();
+
+        // SAFE dense and data have the same length
+        transform.sort_by_cached_key(|&i| f(unsafe { self.data.get_unchecked(i) }));
+
+        self.apply_permutation(&transform);
+    }
+    /// Reorders the `SparseSet` to follow `other`'s order.\
+    /// Components of entities present in both storages come first, in the same order as in `other`.
+    /// The other components follow, keeping their relative order.
+    ///
+    /// Iterating both storages together then walks their memory linearly.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct USIZE(usize);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let e0 = world.add_entity((U32(0), USIZE(0)));
+    /// let e1 = world.add_entity((U32(1), USIZE(1)));
+    ///
+    /// world.run(|mut u32s: ViewMut<U32>, mut usizes: ViewMut<USIZE>| {
+    ///     u32s.sort_unstable_by(|x, y| y.0.cmp(&x.0));
+    ///     usizes.sort_like(&u32s);
+    ///
+    ///     assert_eq!(usizes.id_at(0), Some(e1));
+    ///     assert_eq!(usizes.id_at(1), Some(e0));
+    /// });
+    /// ```
+    pub fn sort_like<U: Component>(&mut self, other: &SparseSet<U>) {
+        let mut transform = Vec::with_capacity(self.dense.len());
+        let mut placed = alloc::vec![false; self.dense.len()];
+
+        for &entity in &other.dense {
+            if let Some(index) = self.index_of(entity) {
+                transform.push(index);
+                placed[index] = true;
+            }
+        }
+
+        transform.extend(
+            placed
+                .iter()
+                .enumerate()
+                .filter(|(_, &placed)| !placed)
+                .map(|(index, _)| index),
+        );
+
+        self.apply_permutation(&transform);
+    }
+    /// Moves the component at `transform[i]` to index `i`, along with its entity and tracking data.
+    fn apply_permutation(&mut self, transform: &[usize]) {
+        // the pending changes are linked by index
+        self.flush_changes();
+
+        let is_tracking_insertion = self.is_tracking_insertion();
+        let is_tracking_modification = self.is_tracking_modification();
+
         let mut pos;
         for i in 0..transform.len() {
             // SAFE we're in bound
@@ -503,6 +627,12 @@ impl<T: Component> SparseSet<T> {
             }
             self.dense.swap(i, pos);
             self.data.swap(i, pos);
+            if is_tracking_insertion {
+                self.insertion_data.swap(i, pos);
+            }
+            if is_tracking_modification {
+                self.modification_data.swap(i, pos);
+            }
         }
 
         for (i, id) in self.dense.iter().enumerate() {
@@ -544,6 +674,7 @@ impl<T: Component> SparseSet<T> {
             if self.is_tracking_modification {
                 self.modification_data[a_index] = current;
             }
+            self.log_component(a_index);
 
             let a = unsafe { &mut *self.data.as_mut_ptr().add(a_index) };
             let b = unsafe { &*self.data.as_mut_ptr().add(b_index) };
@@ -587,6 +718,8 @@ impl<T: Component> SparseSet<T> {
                 self.modification_data[a_index] = current;
                 self.modification_data[b_index] = current;
             }
+            self.log_component(a_index);
+            self.log_component(b_index);
 
             let a = unsafe { &mut *self.data.as_mut_ptr().add(a_index) };
             let b = unsafe { &mut *self.data.as_mut_ptr().add(b_index) };
@@ -599,6 +732,8 @@ impl<T: Component> SparseSet<T> {
 
     /// Deletes all components in this storage.
     pub(crate) fn private_clear(&mut self, current: TrackingTimestamp) {
+        self.log_all();
+
         for &id in &self.dense {
             unsafe {
                 *self.sparse.get_mut_unchecked(id) = EntityId::dead();
@@ -606,6 +741,7 @@ impl<T: Component> SparseSet<T> {
         }
 
         self.insertion_data.clear();
+        self.modification_data.clear();
 
         let is_tracking_deletion = self.is_tracking_deletion();
 
@@ -622,6 +758,8 @@ impl<T: Component> SparseSet<T> {
 
     /// Creates a draining iterator that empties the storage and yields the removed items.
     pub(crate) fn private_drain(&mut self, current: TrackingTimestamp) -> SparseSetDrain<'_, T> {
+        self.log_all();
+
         if self.is_tracking_removal {
             self.removal_data
                 .extend(self.dense.iter().map(|&entity| (entity, current)));
@@ -676,11 +814,14 @@ impl<T: Component> SparseSet<T> {
         for i in 0..self.len() {
             let i = i - removed;
 
+            self.flush_changes();
+
             let eid = unsafe { *self.dense.get_unchecked(i) };
             let component = Mut {
                 flag: self.modification_data.get_mut(i),
                 current,
                 data: unsafe { self.data.get_unchecked_mut(i) },
+                log: self.change_log.slot(i),
             };
 
             if !f(eid, component) {
@@ -691,6 +832,151 @@ impl<T: Component> SparseSet<T> {
     }
 }
 
+impl<T: Component> SparseSet<T> {
+    /// Zero-sized components never allocate their `data` vector,
+    /// a storage of marker components only uses memory for its sparse and dense arrays and the tracking enabled.
+    pub(crate) fn private_memory_usage(&self, storage_name: &'static str) -> StorageMemoryUsage {
+        StorageMemoryUsage {
+            storage_name: storage_name.into(),
+            allocated_memory_bytes: self.sparse.reserved_memory()
+                + (self.dense.capacity() * size_of::<EntityId>())
+                + (self.data.capacity() * size_of::<T>())
+                + (self.insertion_data.capacity() * size_of::<TrackingTimestamp>())
+                + (self.modification_data.capacity() * size_of::<TrackingTimestamp>())
+                + (self.deletion_data.capacity() * size_of::<(EntityId, TrackingTimestamp, T)>())
+                + (self.removal_data.capacity() * size_of::<(EntityId, TrackingTimestamp)>())
+                + self.change_log.reserved_memory()
+                + size_of::<Self>(),
+            used_memory_bytes: self.sparse.used_memory()
+                + (self.dense.len() * size_of::<EntityId>())
+                + (self.data.len() * size_of::<T>())
+                + (self.insertion_data.len() * size_of::<TrackingTimestamp>())
+                + (self.modification_data.len() * size_of::<TrackingTimestamp>())
+                + (self.deletion_data.len() * size_of::<(EntityId, TrackingTimestamp, T)>())
+                + (self.removal_data.len() * size_of::<(EntityId, TrackingTimestamp)>())
+                + self.change_log.used_memory()
+                + size_of::<Self>(),
+            component_count: self.len(),
+        }
+    }
+
+    /// Walks the tracking data between `last` and `current`, used by storages derived from this one.
+    ///
+    /// `removed` is called first for components removed or deleted, then `changed` for the components inserted or modified.
+    pub(crate) fn for_each_change_within(
+        &self,
+        last: TrackingTimestamp,
+        current: TrackingTimestamp,
+        mut removed: impl FnMut(EntityId),
+        mut changed: impl FnMut(EntityId, &T),
+    ) {
+        for &(entity, timestamp) in &self.removal_data {
+            if timestamp.is_within(last, current) {
+                removed(entity);
+            }
+        }
+
+        for (entity, timestamp, _) in &self.deletion_data {
+            if timestamp.is_within(last, current) {
+                removed(*entity);
+            }
+        }
+
+        for (index, (&entity, component)) in self.dense.iter().zip(&self.data).enumerate() {
+            let inserted = self
+                .insertion_data
+                .get(index)
+                .is_some_and(|timestamp| timestamp.is_within(last, current));
+            let modified = self
+                .modification_data
+                .get(index)
+                .is_some_and(|timestamp| timestamp.is_within(last, current));
+
+            if inserted || modified {
+                changed(entity, component);
+            }
+        }
+    }
+}
+
+
+impl<T: Component> SparseSet<T> {
+    /// Starts logging the entities whose component changes, for a storage derived from this one.
+    pub(crate) fn add_change_reader(&mut self) -> ChangeReader {
+        self.change_log.add_reader(&self.dense)
+    }
+    /// Starts logging the entities whose component changes along with a copy of the component before the change.\
+    /// Only one reader per storage can read the copies.
+    pub(crate) fn add_image_reader(&mut self) -> ChangeReader
+    where
+        T: Clone,
+    {
+        self.change_log.add_image_reader(&self.dense)
+    }
+    /// Calls `f` with the entities whose component changed since `reader`'s last read and their current component.\
+    /// Returns `false` without calling `f` if `reader` fell too far behind and has to start over from the storage's content.
+    pub(crate) fn read_changes(
+        &self,
+        reader: ChangeReader,
+        mut f: impl FnMut(EntityId, Option<&T>),
+    ) -> bool {
+        self.change_log
+            .read(reader, &self.dense, |entity| f(entity, self.private_get(entity)))
+    }
+    /// Calls `f` with each change since `reader`'s last read, in order, and the component as it was before the change.\
+    /// Returns `false` if `reader` doesn't come from [`SparseSet::add_image_reader`].
+    pub(crate) fn read_images(
+        &mut self,
+        reader: ChangeReader,
+        f: impl FnMut(EntityId, Option<T>),
+    ) -> bool {
+        self.change_log.read_images(reader, &self.dense, f)
+    }
+    /// Moves the modifications made through [`Mut`] to the change log, before handing out new ones.
+    #[inline]
+    pub(crate) fn flush_changes(&mut self) {
+        self.change_log.flush(&self.dense);
+    }
+    /// Logs a change of the component at `index`, before it's modified or removed.
+    #[inline]
+    pub(crate) fn log_component(&mut self, index: usize) {
+        if self.change_log.is_active() {
+            let image = self.change_log.capture(&self.data[index]);
+            self.change_log.record(self.dense[index], image, &self.dense);
+        }
+    }
+    /// An insertion over an older entity also removes its component.
+    fn log_insertion(&mut self, entity: EntityId) {
+        match self.sparse.get(entity) {
+            Some(sparse_entity) if !sparse_entity.is_dead() => {
+                if entity.gen() > sparse_entity.gen() {
+                    self.log_component(sparse_entity.uindex());
+                    self.change_log.record(entity, None, &self.dense);
+                } else if entity.gen() == sparse_entity.gen() {
+                    self.log_component(sparse_entity.uindex());
+                }
+            }
+            _ => self.change_log.record(entity, None, &self.dense),
+        }
+    }
+    /// Logs the components pushed after `old_len` without going through `insert`.
+    fn log_pushed(&mut self, old_len: usize) {
+        if self.change_log.is_active() {
+            for i in old_len..self.dense.len() {
+                self.change_log.record(self.dense[i], None, &self.dense);
+            }
+        }
+    }
+    /// Logs all components before they're cleared.
+    fn log_all(&mut self) {
+        if self.change_log.is_active() {
+            for i in 0..self.dense.len() {
+                self.log_component(i);
+            }
+        }
+    }
+}
+
 impl<T: Ord + Component> SparseSet<T> {
     /// Sorts the `SparseSet`, but may not preserve the order of equal elements.
     pub fn sort_unstable(&mut self) {
@@ -708,26 +994,7 @@ impl<T: 'static + Component + Send + Sync> Storage for SparseSet<T> {
         self.private_clear(current);
     }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
-        Some(StorageMemoryUsage {
-            storage_name: type_name::<Self>().into(),
-            allocated_memory_bytes: self.sparse.reserved_memory()
-                + (self.dense.capacity() * core::mem::size_of::<EntityId>())
-                + (self.data.capacity() * core::mem::size_of::<T>())
-                + (self.insertion_data.capacity() * core::mem::size_of::<TrackingTimestamp>())
-                + (self.modification_data.capacity() * core::mem::size_of::<TrackingTimestamp>())
-                + (self.deletion_data.capacity() * core::mem::size_of::<(T, EntityId)>())
-                + (self.removal_data.capacity() * core::mem::size_of::<EntityId>())
-                + core::mem::size_of::<Self>(),
-            used_memory_bytes: self.sparse.used_memory()
-                + (self.dense.len() * core::mem::size_of::<EntityId>())
-                + (self.data.len() * core::mem::size_of::<T>())
-                + (self.insertion_data.len() * core::mem::size_of::<TrackingTimestamp>())
-                + (self.modification_data.len() * core::mem::size_of::<TrackingTimestamp>())
-                + (self.deletion_data.len() * core::mem::size_of::<(EntityId, T)>())
-                + (self.removal_data.len() * core::mem::size_of::<EntityId>())
-                + core::mem::size_of::<Self>(),
-            component_count: self.len(),
-        })
+        Some(self.private_memory_usage(type_name::<Self>()))
     }
     fn sparse_array(&self) -> Option<&SparseArray<EntityId, BUCKET_SIZE>> {
         Some(&self.sparse)
@@ -778,24 +1045,7 @@ impl<T: 'static + Component + Sync> Storage for NonSend<SparseSet<T>> {
         self.private_clear(current);
     }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
-        Some(StorageMemoryUsage {
-            storage_name: type_name::<Self>().into(),
-            allocated_memory_bytes: self.sparse.reserved_memory()
-                + (self.dense.capacity() * core::mem::size_of::<EntityId>())
-                + (self.data.capacity() * core::mem::size_of::<T>())
-                + (self.insertion_data.capacity() * core::mem::size_of::<u32>())
-                + (self.deletion_data.capacity() * core::mem::size_of::<(T, EntityId)>())
-                + (self.removal_data.capacity() * core::mem::size_of::<EntityId>())
-                + core::mem::size_of::<Self>(),
-            used_memory_bytes: self.sparse.used_memory()
-                + (self.dense.len() * core::mem::size_of::<EntityId>())
-                + (self.data.len() * core::mem::size_of::<T>())
-                + (self.insertion_data.len() * core::mem::size_of::<u32>())
-                + (self.deletion_data.len() * core::mem::size_of::<(EntityId, T)>())
-                + (self.removal_data.len() * core::mem::size_of::<EntityId>())
-                + core::mem::size_of::<Self>(),
-            component_count: self.len(),
-        })
+        Some(self.private_memory_usage(type_name::<Self>()))
     }
     fn sparse_array(&self) -> Option<&SparseArray<EntityId, BUCKET_SIZE>> {
         Some(&self.sparse)
@@ -846,24 +1096,7 @@ impl<T: 'static + Component + Send> Storage for NonSync<SparseSet<T>> {
         self.private_clear(current);
     }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
-        Some(StorageMemoryUsage {
-            storage_name: type_name::<Self>().into(),
-            allocated_memory_bytes: self.sparse.reserved_memory()
-                + (self.dense.capacity() * core::mem::size_of::<EntityId>())
-                + (self.data.capacity() * core::mem::size_of::<T>())
-                + (self.insertion_data.capacity() * core::mem::size_of::<u32>())
-                + (self.deletion_data.capacity() * core::mem::size_of::<(T, EntityId)>())
-                + (self.removal_data.capacity() * core::mem::size_of::<EntityId>())
-                + core::mem::size_of::<Self>(),
-            used_memory_bytes: self.sparse.used_memory()
-                + (self.dense.len() * core::mem::size_of::<EntityId>())
-                + (self.data.len() * core::mem::size_of::<T>())
-                + (self.insertion_data.len() * core::mem::size_of::<u32>())
-                + (self.deletion_data.len() * core::mem::size_of::<(EntityId, T)>())
-                + (self.removal_data.len() * core::mem::size_of::<EntityId>())
-                + core::mem::size_of::<Self>(),
-            component_count: self.len(),
-        })
+        Some(self.private_memory_usage(type_name::<Self>()))
     }
     fn sparse_array(&self) -> Option<&SparseArray<EntityId, BUCKET_SIZE>> {
         Some(&self.sparse)
@@ -914,24 +1147,7 @@ impl<T: 'static + Component> Storage for NonSendSync<SparseSet<T>> {
         self.private_clear(current);
     }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
-        Some(StorageMemoryUsage {
-            storage_name: type_name::<Self>().into(),
-            allocated_memory_bytes: self.sparse.reserved_memory()
-                + (self.dense.capacity() * core::mem::size_of::<EntityId>())
-                + (self.data.capacity() * core::mem::size_of::<T>())
-                + (self.insertion_data.capacity() * core::mem::size_of::<u32>())
-                + (self.deletion_data.capacity() * core::mem::size_of::<(T, EntityId)>())
-                + (self.removal_data.capacity() * core::mem::size_of::<EntityId>())
-                + core::mem::size_of::<Self>(),
-            used_memory_bytes: self.sparse.used_memory()
-                + (self.dense.len() * core::mem::size_of::<EntityId>())
-                + (self.data.len() * core::mem::size_of::<T>())
-                + (self.insertion_data.len() * core::mem::size_of::<u32>())
-                + (self.deletion_data.len() * core::mem::size_of::<(EntityId, T)>())
-                + (self.removal_data.len() * core::mem::size_of::<EntityId>())
-                + core::mem::size_of::<Self>(),
-            component_count: self.len(),
-        })
+        Some(self.private_memory_usage(type_name::<Self>()))
     }
     fn sparse_array(&self) -> Option<&SparseArray<EntityId, BUCKET_SIZE>> {
         Some(&self.sparse)
@@ -1302,6 +1518,110 @@ mod tests {
         }
     }
 
+    #[test]
+    fn stable_sort() {
+        let mut array = SparseSet::new();
+
+        for i in 0..10 {
+            array.insert(
+                EntityId::new_from_parts(i, 0),
+                I32(i as i32 % 3),
+                TrackingTimestamp::new(0),
+            );
+        }
+
+        array.sort_by_key(|i| i.0);
+
+        assert_eq!(
+            array.dense.iter().map(|id| id.index()).collect::<Vec<_>>(),
+            vec![0, 3, 6, 9, 1, 4, 7, 2, 5, 8]
+        );
+
+        array.sort_by(|x, y| y.cmp(x));
+
+        assert_eq!(
+            array.dense.iter().map(|id| id.index()).collect::<Vec<_>>(),
+            vec![2, 5, 8, 1, 4, 7, 0, 3, 6, 9]
+        );
+
+        let mut calls = 0;
+        array.sort_by_cached_key(|i| {
+            calls += 1;
+            i.0
+        });
+
+        assert_eq!(calls, 10);
+        assert_eq!(
+            array.dense.iter().map(|id| id.index()).collect::<Vec<_>>(),
+            vec![0, 3, 6, 9, 1, 4, 7, 2, 5, 8]
+        );
+        for i in 0..10 {
+            assert_eq!(
+                array.private_get(EntityId::new_from_parts(i, 0)),
+                Some(&I32(i as i32 % 3))
+            );
+        }
+    }
+
+    #[test]
+    fn sort_moves_tracking() {
+        let mut array = SparseSet::new();
+        array.track_insertion().track_modification();
+
+        for i in 0..5 {
+            array.insert(
+                EntityId::new_from_parts(i, 0),
+                I32(-(i as i32)),
+                TrackingTimestamp::new(i as u32),
+            );
+            array.modification_data[i as usize] = TrackingTimestamp::new(10 + i as u32);
+        }
+
+        array.sort_unstable();
+
+        for (index, entity) in array.dense.iter().enumerate() {
+            assert_eq!(array.insertion_data[index].get(), entity.index() as u32);
+            assert_eq!(
+                array.modification_data[index].get(),
+                10 + entity.index() as u32
+            );
+        }
+    }
+
+    #[test]
+    fn sort_like() {
+        let mut array = SparseSet::new();
+        let mut other = SparseSet::new();
+        array.track_insertion();
+
+        for i in 0..5 {
+            array.insert(
+                EntityId::new_from_parts(i, 0),
+                I32(i as i32),
+                TrackingTimestamp::new(i as u32),
+            );
+        }
+        for &i in &[3, 7, 1] {
+            other.insert(
+                EntityId::new_from_parts(i, 0),
+                STR("other"),
+                TrackingTimestamp::new(0),
+            );
+        }
+
+        array.sort_like(&other);
+
+        assert_eq!(
+            array.dense.iter().map(|id| id.index()).collect::<Vec<_>>(),
+            vec![3, 1, 0, 2, 4]
+        );
+        for (index, entity) in array.dense.iter().enumerate() {
+            assert_eq!(array.data[index], I32(entity.index() as i32));
+            assert_eq!(array.insertion_data[index].get(), entity.index() as u32);
+            assert_eq!(array.index_of(*entity), Some(index));
+        }
+    }
+
     #[test]
     fn debug() {
         let mut sparse_set = SparseSet::new();
@@ -1330,4 +1650,43 @@ mod tests {
         assert_eq!(sparse_set.insertion_data.len(), 1);
         assert_eq!(sparse_set.modification_data.len(), 1);
     }
+
+    #[test]
+    fn zero_sized_memory_usage() {
+        struct Tag;
+
+        impl Component for Tag {}
+
+        let mut sparse_set = SparseSet::new();
+
+        for index in 0..100 {
+            sparse_set.insert(EntityId::new(index), Tag, TrackingTimestamp::new(0));
+        }
+
+        let memory_usage = sparse_set.private_memory_usage("");
+
+        assert_eq!(memory_usage.component_count, 100);
+        assert_eq!(
+            memory_usage.used_memory_bytes,
+            sparse_set.sparse.used_memory()
+                + 100 * size_of::<EntityId>()
+                + size_of::<SparseSet<Tag>>()
+        );
+        assert_eq!(
+            memory_usage.allocated_memory_bytes,
+            sparse_set.sparse.reserved_memory()
+                + sparse_set.dense.capacity() * size_of::<EntityId>()
+                + size_of::<SparseSet<Tag>>()
+        );
+
+        sparse_set.track_insertion();
+        sparse_set.delete(EntityId::new(0), TrackingTimestamp::new(0));
+
+        assert_eq!(
+            sparse_set.private_memory_usage("").used_memory_bytes,
+            sparse_set.sparse.used_memory()
+                + 99 * (size_of::<EntityId>() + size_of::<TrackingTimestamp>())
+                + size_of::<SparseSet<Tag>>()
+        );
+    }
 }
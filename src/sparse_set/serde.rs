@@ -0,0 +1,187 @@
+use super::SparseSet;
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::tracking::TrackingTimestamp;
+use alloc::vec::Vec;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize)]
+#[serde(rename = "SparseSet")]
+struct SparseSetRef<'a, T> {
+    dense: &'a [EntityId],
+    data: &'a [T],
+    tracking: Option<TrackingRef<'a, T>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Tracking")]
+struct TrackingRef<'a, T> {
+    is_tracking_insertion: bool,
+    is_tracking_modification: bool,
+    is_tracking_deletion: bool,
+    is_tracking_removal: bool,
+    last_insert: TrackingTimestamp,
+    last_modified: TrackingTimestamp,
+    insertion_data: &'a [TrackingTimestamp],
+    modification_data: &'a [TrackingTimestamp],
+    deletion_data: &'a [(EntityId, TrackingTimestamp, T)],
+    removal_data: &'a [(EntityId, TrackingTimestamp)],
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "SparseSet")]
+struct SparseSetOwned<T> {
+    dense: Vec<EntityId>,
+    data: Vec<T>,
+    tracking: Option<TrackingOwned<T>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Tracking")]
+struct TrackingOwned<T> {
+    is_tracking_insertion: bool,
+    is_tracking_modification: bool,
+    is_tracking_deletion: bool,
+    is_tracking_removal: bool,
+    last_insert: TrackingTimestamp,
+    last_modified: TrackingTimestamp,
+    insertion_data: Vec<TrackingTimestamp>,
+    modification_data: Vec<TrackingTimestamp>,
+    deletion_data: Vec<(EntityId, TrackingTimestamp, T)>,
+    removal_data: Vec<(EntityId, TrackingTimestamp)>,
+}
+
+/// Serializes the entities and their components.\
+/// The tracking state is only serialized when some tracking is enabled, its timestamps are relative to the source [`World`](crate::World).
+///
+/// [`SparseSet::on_insertion`] and [`SparseSet::on_removal`] callbacks are not serialized.
+impl<T: Component + Serialize> Serialize for SparseSet<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let is_tracking = self.is_tracking_insertion
+            || self.is_tracking_modification
+            || self.is_tracking_deletion
+            || self.is_tracking_removal;
+
+        SparseSetRef {
+            dense: &self.dense,
+            data: &self.data,
+            tracking: is_tracking.then(|| TrackingRef {
+                is_tracking_insertion: self.is_tracking_insertion,
+                is_tracking_modification: self.is_tracking_modification,
+                is_tracking_deletion: self.is_tracking_deletion,
+                is_tracking_removal: self.is_tracking_removal,
+                last_insert: self.last_insert,
+                last_modified: self.last_modified,
+                insertion_data: &self.insertion_data,
+                modification_data: &self.modification_data,
+                deletion_data: &self.deletion_data,
+                removal_data: &self.removal_data,
+            }),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Component + Deserialize<'de>> Deserialize<'de> for SparseSet<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let owned = SparseSetOwned::<T>::deserialize(deserializer)?;
+
+        if owned.data.len() != owned.dense.len() {
+            return Err(de::Error::invalid_length(
+                owned.data.len(),
+                &"as many components as entities",
+            ));
+        }
+
+        let mut sparse_set = SparseSet::new();
+
+        for (index, &entity) in owned.dense.iter().enumerate() {
+            sparse_set.sparse.allocate_at(entity);
+
+            // at this point there can't be nothing at the sparse index
+            let sparse_entity = unsafe { sparse_set.sparse.get_mut_unchecked(entity) };
+
+            if !sparse_entity.is_dead() {
+                return Err(de::Error::custom(format_args!(
+                    "{:?} is present multiple times",
+                    entity
+                )));
+            }
+
+            *sparse_entity = EntityId::new_from_index_and_gen(index as u64, entity.gen());
+        }
+
+        sparse_set.dense = owned.dense;
+        sparse_set.data = owned.data;
+
+        if let Some(tracking) = owned.tracking {
+            if tracking.is_tracking_insertion
+                && tracking.insertion_data.len() != sparse_set.dense.len()
+            {
+                return Err(de::Error::invalid_length(
+                    tracking.insertion_data.len(),
+                    &"an insertion timestamp per entity",
+                ));
+            }
+            if tracking.is_tracking_modification
+                && tracking.modification_data.len() != sparse_set.dense.len()
+            {
+                return Err(de::Error::invalid_length(
+                    tracking.modification_data.len(),
+                    &"a modification timestamp per entity",
+                ));
+            }
+
+            sparse_set.is_tracking_insertion = tracking.is_tracking_insertion;
+            sparse_set.is_tracking_modification = tracking.is_tracking_modification;
+            sparse_set.is_tracking_deletion = tracking.is_tracking_deletion;
+            sparse_set.is_tracking_removal = tracking.is_tracking_removal;
+            sparse_set.last_insert = tracking.last_insert;
+            sparse_set.last_modified = tracking.last_modified;
+            sparse_set.insertion_data = tracking.insertion_data;
+            sparse_set.modification_data = tracking.modification_data;
+            sparse_set.deletion_data = tracking.deletion_data;
+            sparse_set.removal_data = tracking.removal_data;
+        }
+
+        Ok(sparse_set)
+    }
+}
+
+#[test]
+fn roundtrip() {
+    #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+    struct U32(u32);
+    impl Component for U32 {}
+
+    let mut sparse_set = SparseSet::new();
+    sparse_set.insert(
+        EntityId::new_from_parts(3, 1),
+        U32(3),
+        TrackingTimestamp::new(0),
+    );
+    sparse_set.insert(
+        EntityId::new_from_parts(0, 0),
+        U32(0),
+        TrackingTimestamp::new(0),
+    );
+
+    let json = serde_json::to_string(&sparse_set).unwrap();
+    let sparse_set: SparseSet<U32> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(sparse_set.len(), 2);
+    assert_eq!(
+        sparse_set.private_get(EntityId::new_from_parts(3, 1)),
+        Some(&U32(3))
+    );
+    assert_eq!(
+        sparse_set.private_get(EntityId::new_from_parts(0, 0)),
+        Some(&U32(0))
+    );
+    assert_eq!(sparse_set.private_get(EntityId::new_from_parts(3, 0)), None);
+
+    assert!(serde_json::from_str::<SparseSet<U32>>(
+        r#"{"dense":[{"index":0,"gen":0},{"index":0,"gen":0}],"data":[0,0],"tracking":null}"#
+    )
+    .is_err());
+}
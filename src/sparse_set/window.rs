@@ -1,3 +1,4 @@
+use super::RawChangeLog;
 use crate::atomic_refcell::{ExclusiveBorrow, SharedBorrow};
 use crate::component::Component;
 use crate::entity_id::EntityId;
@@ -172,6 +173,7 @@ pub struct FullRawWindowMut<'a, T> {
     pub(crate) last_modification: TrackingTimestamp,
     pub(crate) current: TrackingTimestamp,
     pub(crate) is_tracking_modification: bool,
+    pub(crate) change_log: Option<RawChangeLog>,
     _phantom: PhantomData<&'a mut T>,
 }
 
@@ -180,6 +182,8 @@ unsafe impl<T: Send + Component> Send for FullRawWindowMut<'_, T> {}
 impl<'w, T: Component> FullRawWindowMut<'w, T> {
     #[inline]
     pub(crate) fn new<TRACK>(view: &mut ViewMut<'_, T, TRACK>) -> Self {
+        view.sparse_set.flush_changes();
+
         let sparse_len = view.sparse.len();
         let sparse: *mut Option<Box<[EntityId; super::BUCKET_SIZE]>> = view.sparse.as_mut_ptr();
         let sparse = sparse as *mut *mut EntityId;
@@ -196,6 +200,7 @@ impl<'w, T: Component> FullRawWindowMut<'w, T> {
             last_modification: view.last_modification,
             current: view.current,
             is_tracking_modification: view.is_tracking_modification(),
+            change_log: view.sparse_set.change_log.as_raw(),
             _phantom: PhantomData,
         }
     }
@@ -213,6 +218,8 @@ impl<'w, T: Component> FullRawWindowMut<'w, T> {
             ..
         } = view;
 
+        sparse_set.flush_changes();
+
         let sparse_len = sparse_set.len();
         let sparse: *mut Option<Box<[EntityId; super::BUCKET_SIZE]>> =
             sparse_set.sparse.as_mut_ptr();
@@ -231,6 +238,7 @@ impl<'w, T: Component> FullRawWindowMut<'w, T> {
                 last_modification,
                 current,
                 is_tracking_modification: sparse_set.is_tracking_modification(),
+                change_log: sparse_set.change_log.as_raw(),
                 _phantom: PhantomData,
             },
             all_borrow,
@@ -292,6 +300,7 @@ impl<T: Component> Clone for FullRawWindowMut<'_, T> {
             last_modification: self.last_modification,
             current: self.current,
             is_tracking_modification: self.is_tracking_modification,
+            change_log: self.change_log,
             _phantom: PhantomData,
         }
     }
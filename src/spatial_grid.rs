@@ -0,0 +1,376 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::atomic_refcell::{ARefMut, SharedBorrow};
+use crate::borrow::{Borrow, BorrowInfo, Mutability};
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::memory_usage::StorageMemoryUsage;
+use crate::scheduler::TypeInfo;
+use crate::sparse_set::{ChangeReader, SparseSet};
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::views::View;
+use crate::ShipHashMap;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::hash::BuildHasherDefault;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ops::Deref;
+
+/// Components that can be queried by position with [`World::query_rect`](crate::World::query_rect) and [`World::query_radius`](crate::World::query_radius).
+///
+/// ### Example
+///
+/// ```
+/// use shipyard::{Component, SpatialPosition, World};
+///
+/// #[derive(Component)]
+/// struct Pos(f32, f32);
+///
+/// impl SpatialPosition for Pos {
+///     fn position(&self) -> [f32; 2] {
+///         [self.0, self.1]
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.add_spatial_grid::<Pos>(10.0);
+///
+/// let near = world.add_entity(Pos(1.0, 1.0));
+/// world.add_entity(Pos(50.0, 50.0));
+///
+/// assert_eq!(world.query_radius::<Pos>([0.0, 0.0], 5.0).unwrap(), vec![near]);
+/// ```
+pub trait SpatialPosition: Component {
+    /// Returns the position of this component.
+    fn position(&self) -> [f32; 2];
+}
+
+type Cell = (i32, i32);
+
+/// Uniform grid of `T` components, created by [`World::add_spatial_grid`](crate::World::add_spatial_grid).
+///
+/// Each borrow through a [`SpatialGridView`] moves the entities that moved, appeared or disappeared
+/// since the previous one, the others are left where they are.
+pub struct SpatialGrid<T: SpatialPosition> {
+    cell_size: f32,
+    cells: ShipHashMap<Cell, Vec<EntityId>>,
+    entity_cells: ShipHashMap<EntityId, Cell>,
+    reader: ChangeReader,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: SpatialPosition> SpatialGrid<T> {
+    /// Adds all components currently in `sparse_set` to the grid.
+    pub(crate) fn new(cell_size: f32, sparse_set: &mut SparseSet<T>) -> SpatialGrid<T> {
+        assert!(
+            cell_size > 0.0,
+            "SpatialGrid's cell size has to be greater than 0, got {}",
+            cell_size
+        );
+
+        let mut grid = SpatialGrid {
+            cell_size,
+            cells: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            entity_cells: ShipHashMap::with_hasher(BuildHasherDefault::default()),
+            reader: sparse_set.add_change_reader(),
+            _phantom: PhantomData,
+        };
+
+        grid.rebuild(sparse_set);
+
+        grid
+    }
+
+    /// Moves the entities whose component changed since the last sync.
+    pub(crate) fn sync(&mut self, sparse_set: &SparseSet<T>) {
+        let synced = sparse_set.read_changes(self.reader, |entity, component| match component {
+            Some(component) => self.insert(entity, self.cell_of(component.position())),
+            None => self.remove(entity),
+        });
+
+        if !synced {
+            self.rebuild(sparse_set);
+        }
+    }
+
+    /// Returns the entities whose position is within `min` and `max`, inclusive.
+    pub(crate) fn query_rect(
+        &self,
+        sparse_set: &SparseSet<T>,
+        min: [f32; 2],
+        max: [f32; 2],
+    ) -> Vec<EntityId> {
+        self.query(sparse_set, min, max, |[x, y]| {
+            x >= min[0] && x <= max[0] && y >= min[1] && y <= max[1]
+        })
+    }
+
+    /// Returns the entities whose position is at most `radius` away from `center`.
+    pub(crate) fn query_radius(
+        &self,
+        sparse_set: &SparseSet<T>,
+        center: [f32; 2],
+        radius: f32,
+    ) -> Vec<EntityId> {
+        let min = [center[0] - radius, center[1] - radius];
+        let max = [center[0] + radius, center[1] + radius];
+
+        self.query(sparse_set, min, max, |[x, y]| {
+            let dx = x - center[0];
+            let dy = y - center[1];
+
+            dx * dx + dy * dy <= radius * radius
+        })
+    }
+
+    /// Returns the size of the grid's cells.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Returns the number of entities in the grid.
+    pub fn len(&self) -> usize {
+        self.entity_cells.len()
+    }
+
+    /// Returns `true` if the grid doesn't contain any entity.
+    pub fn is_empty(&self) -> bool {
+        self.entity_cells.is_empty()
+    }
+
+    /// Visits all cells overlapping `min`..=`max` and keeps the entities whose position passes `filter`.
+    fn query(
+        &self,
+        sparse_set: &SparseSet<T>,
+        min: [f32; 2],
+        max: [f32; 2],
+        filter: impl Fn([f32; 2]) -> bool,
+    ) -> Vec<EntityId> {
+        let mut entities = Vec::new();
+
+        if min[0] > max[0] || min[1] > max[1] {
+            return entities;
+        }
+
+        let (min_x, min_y) = self.cell_of(min);
+        let (max_x, max_y) = self.cell_of(max);
+
+        // Large queries visit fewer buckets by going through the occupied cells directly
+        let cell_count =
+            (max_x as i64 - min_x as i64 + 1).saturating_mul(max_y as i64 - min_y as i64 + 1);
+
+        let mut visit = |cell_entities: &Vec<EntityId>| {
+            for &entity in cell_entities {
+                if let Some(component) = sparse_set.private_get(entity) {
+                    if filter(component.position()) {
+                        entities.push(entity);
+                    }
+                }
+            }
+        };
+
+        if cell_count as usize > self.cells.len() {
+            for (&(x, y), cell_entities) in &self.cells {
+                if x >= min_x && x <= max_x && y >= min_y && y <= max_y {
+                    visit(cell_entities);
+                }
+            }
+        } else {
+            for x in min_x..=max_x {
+                for y in min_y..=max_y {
+                    if let Some(cell_entities) = self.cells.get(&(x, y)) {
+                        visit(cell_entities);
+                    }
+                }
+            }
+        }
+
+        entities
+    }
+
+    fn rebuild(&mut self, sparse_set: &SparseSet<T>) {
+        self.cells.clear();
+        self.entity_cells.clear();
+
+        for (&entity, component) in sparse_set.dense.iter().zip(&sparse_set.data) {
+            self.insert(entity, self.cell_of(component.position()));
+        }
+    }
+
+    fn cell_of(&self, [x, y]: [f32; 2]) -> Cell {
+        (floor(x / self.cell_size), floor(y / self.cell_size))
+    }
+
+    fn insert(&mut self, entity: EntityId, cell: Cell) {
+        if self.entity_cells.get(&entity) == Some(&cell) {
+            return;
+        }
+
+        self.remove(entity);
+
+        self.cells.entry(cell).or_default().push(entity);
+        self.entity_cells.insert(entity, cell);
+    }
+
+    fn remove(&mut self, entity: EntityId) {
+        if let Some(cell) = self.entity_cells.remove(&entity) {
+            if let Some(entities) = self.cells.get_mut(&cell) {
+                if let Some(index) = entities.iter().position(|&other| other == entity) {
+                    entities.swap_remove(index);
+                }
+
+                if entities.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+}
+
+/// `f32::floor` isn't available in `core`.\
+/// Positions outside of `i32`'s range end up in the border cells.
+#[allow(clippy::cast_precision_loss)]
+fn floor(value: f32) -> i32 {
+    let truncated = value as i32;
+
+    if (truncated as f32) > value {
+        truncated.saturating_sub(1)
+    } else {
+        truncated
+    }
+}
+
+impl<T: SpatialPosition> Storage for SpatialGrid<T> {
+    #[inline]
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        self.remove(entity);
+    }
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        self.cells.clear();
+        self.entity_cells.clear();
+    }
+    fn memory_usage(&self) -> Option<StorageMemoryUsage> {
+        let entry_size = size_of::<Cell>() + size_of::<EntityId>();
+
+        Some(StorageMemoryUsage {
+            storage_name: type_name::<Self>().into(),
+            allocated_memory_bytes: size_of::<Self>()
+                + (self.cells.capacity() * (size_of::<Cell>() + size_of::<Vec<EntityId>>()))
+                + (self.entity_cells.capacity() * entry_size),
+            used_memory_bytes: size_of::<Self>()
+                + (self.cells.len() * (size_of::<Cell>() + size_of::<Vec<EntityId>>()))
+                + (self.entity_cells.len() * entry_size),
+            component_count: self.entity_cells.len(),
+        })
+    }
+    fn is_empty(&self) -> bool {
+        self.entity_cells.is_empty()
+    }
+}
+
+/// Shared view over `T` components with position queries, usable in systems.
+///
+/// Borrowing the view moves the entities of `T`'s [`SpatialGrid`] to their current cell,
+/// see [`World::add_spatial_grid`](crate::World::add_spatial_grid).
+///
+/// ### Example
+///
+/// ```
+/// use shipyard::{Component, SpatialGridView, SpatialPosition, World};
+///
+/// #[derive(Component)]
+/// struct Pos(f32, f32);
+///
+/// impl SpatialPosition for Pos {
+///     fn position(&self) -> [f32; 2] {
+///         [self.0, self.1]
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.add_spatial_grid::<Pos>(10.0);
+///
+/// let near = world.add_entity(Pos(1.0, 1.0));
+/// world.add_entity(Pos(50.0, 50.0));
+///
+/// world.run(|positions: SpatialGridView<Pos>| {
+///     assert_eq!(positions.query_rect([0.0, 0.0], [10.0, 10.0]), vec![near]);
+/// });
+/// ```
+pub struct SpatialGridView<'v, T: SpatialPosition> {
+    view: View<'v, T>,
+    grid: ARefMut<'v, &'v mut SpatialGrid<T>>,
+}
+
+impl<T: SpatialPosition> SpatialGridView<'_, T> {
+    /// Returns the entities whose position is within `min` and `max`, inclusive.
+    /// The order of the entities is unspecified.
+    pub fn query_rect(&self, min: [f32; 2], max: [f32; 2]) -> Vec<EntityId> {
+        self.grid.query_rect(&self.view, min, max)
+    }
+
+    /// Returns the entities whose position is at most `radius` away from `center`.
+    /// The order of the entities is unspecified.
+    pub fn query_radius(&self, center: [f32; 2], radius: f32) -> Vec<EntityId> {
+        self.grid.query_radius(&self.view, center, radius)
+    }
+}
+
+impl<'v, T: SpatialPosition> Deref for SpatialGridView<'v, T> {
+    type Target = View<'v, T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.view
+    }
+}
+
+impl<T: SpatialPosition + Send + Sync> Borrow for SpatialGridView<'_, T> {
+    type View<'a> = SpatialGridView<'a, T>;
+
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        last_run: Option<TrackingTimestamp>,
+        current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        let view = View::<T>::borrow(all_storages, all_borrow, last_run, current)?;
+        let mut grid = all_storages.custom_storage_mut::<SpatialGrid<T>>()?;
+
+        grid.sync(&view);
+
+        Ok(SpatialGridView { view, grid })
+    }
+}
+
+// SAFE: `T`'s storage and grid are recorded.
+unsafe impl<T: SpatialPosition + Send + Sync> BorrowInfo for SpatialGridView<'_, T> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        <View<'_, T>>::borrow_info(info);
+
+        info.push(TypeInfo {
+            name: type_name::<SpatialGrid<T>>().into(),
+            mutability: Mutability::Exclusive,
+            storage_id: StorageId::of::<SpatialGrid<T>>(),
+            thread_safe: true,
+            optional: false,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::floor;
+
+    #[test]
+    fn floor_negative() {
+        assert_eq!(floor(1.5), 1);
+        assert_eq!(floor(-1.5), -2);
+        assert_eq!(floor(-2.0), -2);
+        assert_eq!(floor(0.0), 0);
+        assert_eq!(floor(f32::NEG_INFINITY), i32::MIN);
+    }
+}
@@ -8,9 +8,10 @@ pub(crate) use sbox::SBox;
 use crate::all_storages::AllStorages;
 use crate::entity_id::EntityId;
 use crate::memory_usage::StorageMemoryUsage;
-use crate::sparse_set::SparseArray;
+use crate::sparse_set::{SparseArray, BUCKET_SIZE};
 use crate::tracking::TrackingTimestamp;
 use alloc::borrow::Cow;
+use alloc::vec::Vec;
 use core::any::Any;
 
 pub trait SizedAny {
@@ -43,6 +44,18 @@ pub trait Storage: SizedAny {
     #[inline]
     #[allow(unused_variables)]
     fn delete(&mut self, entity: EntityId, current: TrackingTimestamp) {}
+    /// Deletes an entity from this storage when the entity itself is deleted.\
+    /// Entities added to `cascade` are deleted after it.
+    #[inline]
+    #[allow(unused_variables)]
+    fn delete_entity(
+        &mut self,
+        entity: EntityId,
+        current: TrackingTimestamp,
+        cascade: &mut Vec<EntityId>,
+    ) {
+        self.delete(entity, current);
+    }
     /// Deletes all components of this storage.
     #[inline]
     #[allow(unused_variables)]
@@ -59,7 +72,7 @@ pub trait Storage: SizedAny {
     ///
     /// [`SparseSet`]: crate::sparse_set::SparseSet
     /// [`SparseArray`]: crate::sparse_set::SparseArray
-    fn sparse_array(&self) -> Option<&SparseArray<EntityId, 32>> {
+    fn sparse_array(&self) -> Option<&SparseArray<EntityId, BUCKET_SIZE>> {
         None
     }
     /// Returns `true` if the storage is empty.
@@ -1,7 +1,6 @@
-use crate::atomic_refcell::AtomicRefCell;
+use crate::atomic_refcell::{AtomicRefCell, WaitState};
 use crate::storage::Storage;
 use alloc::boxed::Box;
-#[cfg(feature = "thread_local")]
 use alloc::sync::Arc;
 
 /// Abstract away `T` from `AtomicRefCell<T>` to be able to store
@@ -53,6 +52,14 @@ impl SBox {
             value, thread_id,
         ))))
     }
+    /// Releasing a borrow of this storage wakes up the threads waiting on `wait_state`.
+    #[inline]
+    pub(crate) fn watched_by(self, wait_state: &Arc<WaitState>) -> Self {
+        // SAFE the pointer came from a `Box` and the storage isn't shared yet
+        unsafe { &mut *self.0 }.set_wait_state(wait_state.clone());
+
+        self
+    }
 }
 
 impl core::fmt::Debug for SBox {
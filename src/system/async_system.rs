@@ -0,0 +1,46 @@
+use crate::borrow::WorldBorrow;
+use crate::error;
+use crate::world::World;
+use core::future::Future;
+
+/// Trait bound encompassing all functions that can be used as asynchronous system.
+/// Same as `System` but the function returns a [`Future`] that can keep the views borrowed across await points.
+///
+/// `Borrow` are the storages borrowed.
+/// `Future` is the type returned by the system.
+pub trait AsyncSystem<'w, Borrow> {
+    /// The future returned by the system
+    type Future: Future + 'w;
+
+    #[allow(missing_docs)]
+    fn run(self, world: &'w World) -> Result<Self::Future, error::GetStorage>;
+}
+
+macro_rules! impl_async_system {
+    ($(($type: ident, $index: tt))+) => {
+        impl<'w, $($type: WorldBorrow,)+ Fut: Future + 'w, Func> AsyncSystem<'w, ($($type,)+)> for Func
+        where
+            Func: FnOnce($($type),+) -> Fut
+                + FnOnce($($type::WorldView<'w>),+) -> Fut
+        {
+            type Future = Fut;
+
+            fn run(self, world: &'w World) -> Result<Fut, error::GetStorage> {
+                let current = world.get_current();
+                Ok((self)($($type::world_borrow(world, None, current)?,)+))
+            }
+        }
+    }
+}
+
+macro_rules! async_system {
+    ($(($type: ident, $index: tt))*;($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_async_system![$(($type, $index))*];
+        async_system![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))*;) => {
+        impl_async_system![$(($type, $index))*];
+    }
+}
+
+async_system![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
@@ -1,6 +1,8 @@
 mod all_storages;
+mod async_system;
 
 pub use all_storages::AllSystem;
+pub use async_system::AsyncSystem;
 
 use crate::borrow::WorldBorrow;
 use crate::error;
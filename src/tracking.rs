@@ -213,6 +213,7 @@ pub(crate) fn map_deletion_data<T>(
 
 /// Timestamp used to clear tracking information.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
 pub struct TrackingTimestamp(u32);
 
 impl TrackingTimestamp {
@@ -4,6 +4,9 @@ use crate::storage::Storage;
 use crate::tracking::TrackingTimestamp;
 
 /// Unique storage.
+///
+/// With the `serde1` feature, the value is (de)serialized along with its tracking timestamps.
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
 pub struct UniqueStorage<T: Unique> {
     pub(crate) value: T,
     pub(crate) insert: TrackingTimestamp,
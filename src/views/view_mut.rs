@@ -232,10 +232,13 @@ impl<TRACK, T: Component> ViewMut<'_, T, TRACK> {
 
         let index = self.index_of(entity).unwrap();
 
+        self.sparse_set.flush_changes();
+
         let SparseSet {
             data,
             modification_data,
             is_tracking_modification,
+            change_log,
             ..
         } = self.sparse_set;
 
@@ -244,6 +247,7 @@ impl<TRACK, T: Component> ViewMut<'_, T, TRACK> {
                 .then(|| unsafe { modification_data.get_unchecked_mut(index) }),
             current: self.current,
             data: unsafe { data.get_unchecked_mut(index) },
+            log: change_log.slot(index),
         }
     }
     /// Retrieve `entity` component.
@@ -483,6 +487,8 @@ impl<'a, T: Component, TRACK> core::ops::IndexMut<EntityId> for ViewMut<'a, T, T
             })
             .unwrap();
 
+        self.sparse_set.log_component(index);
+
         let SparseSet {
             data,
             modification_data,
@@ -3,28 +3,50 @@ mod builder;
 pub use builder::WorldBuilder;
 
 use crate::all_storages::{AllStorages, CustomStorageAccess, TupleDeleteAny, TupleRetainStorage};
+#[cfg(feature = "std")]
+use crate::atomic_refcell::retry_blocking;
+use crate::atomic_refcell::WaitState;
+#[cfg(feature = "borrow_diagnostics")]
+use crate::atomic_refcell::BorrowHolder;
 use crate::atomic_refcell::{ARef, ARefMut, AtomicRefCell};
 use crate::borrow::WorldBorrow;
 use crate::component::{Component, Unique};
-use crate::entities::Entities;
+#[cfg(feature = "serde1")]
+use crate::delta::DeltaComponents;
+use crate::dynamic::{DynamicComponentInfo, DynamicStorage, DynamicView, DynamicViewMut};
 use crate::entity_id::EntityId;
 use crate::error;
+use crate::executor::Executor;
+#[cfg(feature = "parallel")]
+use crate::executor::RayonExecutor;
 use crate::get_component::GetComponent;
+use crate::history::History;
+use crate::indexed::{IndexKey, Indexed, IndexedView};
 use crate::info::WorkloadsInfo;
 use crate::iter_component::{IntoIterRef, IterComponent};
 use crate::memory_usage::WorldMemoryUsage;
+#[cfg(feature = "serde1")]
+use crate::persist::{self, PersistentComponents};
 use crate::r#mut::Mut;
+use crate::reactive::{Reactive, ReactiveChanges, ReactiveQuery};
+use crate::relation::{Relation, Relations};
 use crate::reserve::BulkEntityIter;
 use crate::scheduler::Label;
 use crate::scheduler::{AsLabel, Batches, Scheduler};
-use crate::sparse_set::{BulkAddEntity, TupleAddComponent, TupleDelete, TupleRemove};
+use crate::sorted::{SortKey, Sorted, SortedView};
+use crate::sparse_set::{BulkAddEntity, SparseSet, TupleAddComponent, TupleDelete, TupleRemove};
+use crate::spatial_grid::{SpatialGrid, SpatialGridView, SpatialPosition};
 use crate::storage::{Storage, StorageId};
-use crate::system::System;
+use crate::system::{AsyncSystem, System};
 use crate::tracking::{TrackingTimestamp, TupleTrack};
+use crate::type_id::TypeId;
 use crate::views::EntitiesViewMut;
 use alloc::boxed::Box;
 use alloc::format;
+use alloc::string::ToString;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::RangeBounds;
 use core::sync::atomic::AtomicU32;
 
 /// `World` contains all data this library will manipulate.
@@ -32,8 +54,11 @@ pub struct World {
     pub(crate) all_storages: AtomicRefCell<AllStorages>,
     pub(crate) scheduler: AtomicRefCell<Scheduler>,
     counter: Arc<AtomicU32>,
+    wait_state: Arc<WaitState>,
     #[cfg(feature = "parallel")]
     thread_pool: Option<rayon::ThreadPool>,
+    executor: Option<Box<dyn Executor>>,
+    pub(crate) plugins: Vec<(TypeId, &'static str)>,
 }
 
 #[cfg(feature = "std")]
@@ -41,18 +66,16 @@ impl Default for World {
     /// Creates an empty `World`.
     fn default() -> Self {
         let counter = Arc::new(AtomicU32::new(1));
+        let wait_state = Arc::new(WaitState::default());
         World {
-            #[cfg(not(feature = "thread_local"))]
-            all_storages: AtomicRefCell::new(AllStorages::new(counter.clone())),
-            #[cfg(feature = "thread_local")]
-            all_storages: AtomicRefCell::new_non_send(
-                AllStorages::new(counter.clone()),
-                Arc::new(crate::std_thread_id_generator),
-            ),
+            all_storages: AllStorages::new(counter.clone(), wait_state.clone()),
             scheduler: AtomicRefCell::new(Default::default()),
             counter,
+            wait_state,
             #[cfg(feature = "parallel")]
             thread_pool: None,
+            executor: None,
+            plugins: Vec::new(),
         }
     }
 }
@@ -68,6 +91,15 @@ impl World {
     pub fn remove_local_thread_pool(&mut self) -> Option<rayon::ThreadPool> {
         self.thread_pool.take()
     }
+    /// Sets the custom [`Executor`] used to run workloads.\
+    /// Replaces the previous one, if any.
+    pub fn set_executor(&mut self, executor: impl Executor + 'static) {
+        self.executor = Some(Box::new(executor));
+    }
+    /// Removes the custom [`Executor`].
+    pub fn remove_executor(&mut self) -> Option<Box<dyn Executor>> {
+        self.executor.take()
+    }
     /// Adds a new unique storage, unique storages store a single value.  
     /// To access a unique storage value, use [`UniqueView`] or [`UniqueViewMut`].  
     ///
@@ -369,7 +401,11 @@ let (entities, mut usizes) = world
     #[cfg_attr(feature = "thread_local", doc = "[NonSend]: crate::NonSend")]
     #[cfg_attr(feature = "thread_local", doc = "[NonSync]: crate::NonSync")]
     #[cfg_attr(feature = "thread_local", doc = "[NonSendSync]: crate::NonSendSync")]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub fn borrow<V: WorldBorrow>(&self) -> Result<V::WorldView<'_>, error::GetStorage> {
+        #[cfg(feature = "borrow_diagnostics")]
+        let _holder = BorrowHolder::enter_location();
+
         let current = self.get_current();
 
         V::world_borrow(self, None, current)
@@ -490,6 +526,8 @@ world.run_with_data(sys1, (EntityId::dead(), [0., 0.]));
         let system_span = tracing::info_span!("system", name = ?core::any::type_name::<S>());
         #[cfg(feature = "tracing")]
         let _system_span = system_span.enter();
+        #[cfg(feature = "borrow_diagnostics")]
+        let _holder = BorrowHolder::enter_location();
 
         system
             .run((data,), self)
@@ -616,12 +654,211 @@ let i = world.run(sys1);
         let system_span = tracing::info_span!("system", name = ?core::any::type_name::<S>());
         #[cfg(feature = "tracing")]
         let _system_span = system_span.enter();
+        #[cfg(feature = "borrow_diagnostics")]
+        let _holder = BorrowHolder::enter_location();
 
         system
             .run((), self)
             .map_err(error::Run::GetStorage)
             .unwrap()
     }
+    /// Borrows the requested storages like [`World::borrow`],
+    /// parks the thread while a storage is borrowed elsewhere instead of failing.\
+    /// Waits indefinitely if `timeout` is `None`.
+    ///
+    /// Borrows are all or nothing: the storages already borrowed are released before parking,
+    /// two threads waiting on each other's storages can't deadlock.\
+    /// The thread must not hold a conflicting view itself, it would wait until `timeout`.
+    ///
+    /// ### Borrows
+    ///
+    /// - Same as [`World::borrow`]
+    ///
+    /// ### Errors
+    ///
+    /// - Same as [`World::borrow`], borrow errors are only returned once `timeout` elapses.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, View, ViewMut, World};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// std::thread::scope(|scope| {
+    ///     let u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    ///
+    ///     scope.spawn(|| {
+    ///         world
+    ///             .borrow_blocking::<View<U32>>(Some(Duration::from_secs(10)))
+    ///             .unwrap();
+    ///     });
+    ///
+    ///     drop(u32s);
+    /// });
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
+    pub fn borrow_blocking<V: WorldBorrow>(
+        &self,
+        timeout: Option<core::time::Duration>,
+    ) -> Result<V::WorldView<'_>, error::GetStorage> {
+        #[cfg(feature = "borrow_diagnostics")]
+        let _holder = BorrowHolder::enter_location();
+
+        retry_blocking(
+            &self.wait_state,
+            timeout,
+            || V::world_borrow(self, None, self.get_current()),
+            error::GetStorage::is_borrow_conflict,
+        )
+    }
+    /// Borrows the requested storages like [`World::borrow_blocking`] then runs `system` like [`World::run`].\
+    /// `system` is cloned for each try, functions and closures that don't capture values are `Copy`.
+    ///
+    /// ### Borrows
+    ///
+    /// - Same as [`World::run`]
+    ///
+    /// ### Errors
+    ///
+    /// - Same as [`World::borrow_blocking`].
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, View, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// fn count(u32s: View<U32>) -> usize {
+    ///     u32s.len()
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_entity(U32(0));
+    ///
+    /// std::thread::scope(|scope| {
+    ///     let u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    ///
+    ///     let count = scope.spawn(|| world.run_blocking(count, None).unwrap());
+    ///
+    ///     drop(u32s);
+    ///
+    ///     assert_eq!(count.join().unwrap(), 1);
+    /// });
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
+    pub fn run_blocking<B, S: System<(), B> + Clone>(
+        &self,
+        system: S,
+        timeout: Option<core::time::Duration>,
+    ) -> Result<S::Return, error::GetStorage> {
+        #[cfg(feature = "tracing")]
+        let system_span = tracing::info_span!("system", name = ?core::any::type_name::<S>());
+        #[cfg(feature = "tracing")]
+        let _system_span = system_span.enter();
+        #[cfg(feature = "borrow_diagnostics")]
+        let _holder = BorrowHolder::enter_location();
+
+        retry_blocking(
+            &self.wait_state,
+            timeout,
+            || system.clone().run((), self),
+            error::GetStorage::is_borrow_conflict,
+        )
+    }
+    /// Borrows the requested storages and passes the future returned by `system` to `executor`.\
+    /// Evaluates to `executor`'s return value.
+    ///
+    /// The views stay borrowed until the future is dropped, they can be used across await points.\
+    /// `executor` can drive the future to completion (`block_on`) or hand it to a local executor
+    /// as long as the future doesn't outlive the `World` borrow.\
+    /// `system` has to be an `async fn`, closures can't return a future borrowing their arguments.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared) + storage (exclusive or shared) for as long as the future lives
+    ///
+    /// ### Panics
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - Storage borrow failed.
+    /// - Unique storage did not exist.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, IntoIter, View, World};
+    /// use std::future::Future;
+    /// use std::pin::pin;
+    /// use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// fn block_on<F: Future>(future: F) -> F::Output {
+    ///     fn noop_raw_waker() -> RawWaker {
+    ///         fn clone(_: *const ()) -> RawWaker {
+    ///             noop_raw_waker()
+    ///         }
+    ///         fn noop(_: *const ()) {}
+    ///
+    ///         static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    ///
+    ///         RawWaker::new(std::ptr::null(), &VTABLE)
+    ///     }
+    ///
+    ///     let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    ///     let mut context = Context::from_waker(&waker);
+    ///     let mut future = pin!(future);
+    ///
+    ///     loop {
+    ///         if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+    ///             return output;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// async fn load_next_frame() {}
+    ///
+    /// async fn sum(u32s: View<'_, U32>) -> u32 {
+    ///     load_next_frame().await;
+    ///
+    ///     u32s.iter().map(|u32| u32.0).sum()
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity(U32(1));
+    /// world.add_entity(U32(2));
+    ///
+    /// assert_eq!(world.run_async(sum, block_on), 3);
+    /// ```
+    ///
+    /// [`AllStorages`]: crate::AllStorages
+    #[track_caller]
+    pub fn run_async<'w, B, S: AsyncSystem<'w, B>, R>(
+        &'w self,
+        system: S,
+        executor: impl FnOnce(S::Future) -> R,
+    ) -> R {
+        #[cfg(feature = "tracing")]
+        let system_span = tracing::info_span!("system", name = ?core::any::type_name::<S>());
+        #[cfg(feature = "tracing")]
+        let _system_span = system_span.enter();
+        #[cfg(feature = "borrow_diagnostics")]
+        let _holder = BorrowHolder::enter_location();
+
+        let future = system.run(self).map_err(error::Run::GetStorage).unwrap();
+
+        executor(future)
+    }
     /// Modifies the current default workload to `name`.
     ///
     /// ### Borrows
@@ -687,8 +924,120 @@ let i = world.run(sys1);
             &scheduler.system_names,
             batches,
             &*label,
+            None,
+        )
+    }
+    /// Runs the systems of the `name` workload for which `filter` returns `true`.\
+    /// `filter` is called with the tags of each system, this includes the system itself and the workloads it's part of.
+    ///
+    /// Systems keep the order and batches they have in the full workload.
+    ///
+    /// ### Borrows
+    ///
+    /// - Scheduler (shared)
+    /// - Systems' borrow as they are executed
+    ///
+    /// ### Errors
+    ///
+    /// - Scheduler borrow failed.
+    /// - Workload did not exist.
+    /// - Storage borrow failed.
+    /// - User error returned by system.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AsLabel, SystemModificator, Unique, UniqueView, UniqueViewMut, Workload, World};
+    ///
+    /// #[derive(Unique)]
+    /// struct Frame(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.add_unique(Frame(0));
+    ///
+    /// Workload::new("update")
+    ///     .with_system((|mut frame: UniqueViewMut<Frame>| frame.0 += 1).tag("simulation"))
+    ///     .with_system((|mut frame: UniqueViewMut<Frame>| frame.0 += 10).tag("presentation"))
+    ///     .add_to_world(&world)
+    ///     .unwrap();
+    ///
+    /// let simulation = "simulation".as_label();
+    /// world
+    ///     .run_workload_filtered("update", |tags| tags.contains(&simulation))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(world.borrow::<UniqueView<Frame>>().unwrap().0, 1);
+    /// ```
+    pub fn run_workload_filtered<T>(
+        &self,
+        label: impl AsLabel<T>,
+        filter: impl Fn(&[Box<dyn Label>]) -> bool,
+    ) -> Result<(), error::RunWorkload> {
+        let scheduler = self
+            .scheduler
+            .borrow()
+            .map_err(|_| error::RunWorkload::Scheduler)?;
+
+        let label = label.as_label();
+        let batches = scheduler.workload(&*label)?;
+
+        self.run_batches(
+            &scheduler.systems,
+            &scheduler.system_names,
+            batches,
+            &*label,
+            Some(&filter),
         )
     }
+    /// Runs the systems of the `name` workload that have at least one of `tags`.
+    ///
+    /// Systems keep the order and batches they have in the full workload.
+    ///
+    /// ### Borrows
+    ///
+    /// - Scheduler (shared)
+    /// - Systems' borrow as they are executed
+    ///
+    /// ### Errors
+    ///
+    /// - Scheduler borrow failed.
+    /// - Workload did not exist.
+    /// - Storage borrow failed.
+    /// - User error returned by system.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{SystemModificator, Unique, UniqueView, UniqueViewMut, Workload, World};
+    ///
+    /// #[derive(Unique)]
+    /// struct Frame(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.add_unique(Frame(0));
+    ///
+    /// Workload::new("update")
+    ///     .with_system((|mut frame: UniqueViewMut<Frame>| frame.0 += 1).tag("simulation"))
+    ///     .with_system((|mut frame: UniqueViewMut<Frame>| frame.0 += 10).tag("presentation"))
+    ///     .add_to_world(&world)
+    ///     .unwrap();
+    ///
+    /// world.run_workload_with_tags("update", ["simulation"]).unwrap();
+    ///
+    /// assert_eq!(world.borrow::<UniqueView<Frame>>().unwrap().0, 1);
+    /// ```
+    pub fn run_workload_with_tags<T, U, L: AsLabel<U>>(
+        &self,
+        label: impl AsLabel<T>,
+        tags: impl IntoIterator<Item = L>,
+    ) -> Result<(), error::RunWorkload> {
+        let tags = tags
+            .into_iter()
+            .map(|tag| tag.as_label())
+            .collect::<Vec<_>>();
+
+        self.run_workload_filtered(label, |system_tags| {
+            system_tags.iter().any(|tag| tags.contains(tag))
+        })
+    }
     /// Returns `true` if the world contains the `name` workload.
     ///
     /// ### Borrows
@@ -723,6 +1072,7 @@ let i = world.run(sys1);
         system_names: &[Box<dyn Label>],
         batches: &Batches,
         workload_name: &dyn Label,
+        filter: Option<&dyn Fn(&[Box<dyn Label>]) -> bool>,
     ) -> Result<(), error::RunWorkload> {
         if let Some(run_if) = &batches.run_if {
             if !run_if
@@ -733,129 +1083,173 @@ let i = world.run(sys1);
             }
         }
 
+        // evaluated upfront so `filter` doesn't have to be `Sync` when systems run in a ThreadPool
+        let selected = filter.map(|filter| {
+            batches
+                .sequential_tags
+                .iter()
+                .map(|tags| (filter)(tags))
+                .collect::<Vec<_>>()
+        });
+        let selected = selected.as_deref();
+
+        if let Some(executor) = &self.executor {
+            return self.run_batches_parallel(
+                &**executor,
+                systems,
+                system_names,
+                batches,
+                workload_name,
+                selected,
+            );
+        }
+
         #[cfg(feature = "parallel")]
         {
-            self.run_batches_parallel(systems, system_names, batches, workload_name)
+            if let Some(thread_pool) = &self.thread_pool {
+                // the whole workload runs inside the local ThreadPool, `par_iter` included
+                thread_pool.scope(|_| {
+                    self.run_batches_parallel(
+                        &RayonExecutor,
+                        systems,
+                        system_names,
+                        batches,
+                        workload_name,
+                        selected,
+                    )
+                })
+            } else {
+                // Use non local ThreadPool
+                self.run_batches_parallel(
+                    &RayonExecutor,
+                    systems,
+                    system_names,
+                    batches,
+                    workload_name,
+                    selected,
+                )
+            }
         }
 
         #[cfg(not(feature = "parallel"))]
         {
-            self.run_batches_sequential(systems, system_names, batches, workload_name)
+            self.run_batches_sequential(systems, system_names, batches, workload_name, selected)
         }
     }
-    #[cfg(feature = "parallel")]
     #[allow(clippy::type_complexity)]
     pub(crate) fn run_batches_parallel(
         &self,
+        executor: &dyn Executor,
         systems: &[Box<dyn Fn(&World) -> Result<(), error::Run> + Send + Sync + 'static>],
         system_names: &[Box<dyn Label>],
         batches: &Batches,
         #[cfg_attr(not(feature = "tracing"), allow(unused))] workload_name: &dyn Label,
+        selected: Option<&[bool]>,
     ) -> Result<(), error::RunWorkload> {
         #[cfg(feature = "tracing")]
         let parent_span = tracing::info_span!("workload", name = ?workload_name);
         #[cfg(feature = "tracing")]
         let _parent_span = parent_span.enter();
 
-        let run_batch = || -> Result<(), error::RunWorkload> {
-            for (batch, batches_run_if) in batches.parallel.iter().zip(&batches.parallel_run_if) {
-                let mut result = Ok(());
-                let run_if = (
-                    if let Some(run_if_index) = batches_run_if.0 {
-                        if let Some(run_if) = &batches.sequential_run_if[run_if_index] {
+        let is_selected = |sequential_index: usize| match selected {
+            Some(selected) => selected[sequential_index],
+            None => true,
+        };
+
+        for (batch, batches_run_if) in batches.parallel.iter().zip(&batches.parallel_run_if) {
+            let run_if = (
+                if let Some(run_if_index) = batches_run_if.0 {
+                    if !is_selected(run_if_index) {
+                        false
+                    } else if let Some(run_if) = &batches.sequential_run_if[run_if_index] {
+                        (run_if)(self).map_err(|err| {
+                            error::RunWorkload::Run((system_names[batch.0.unwrap()].clone(), err))
+                        })?
+                    } else {
+                        true
+                    }
+                } else {
+                    true
+                },
+                batches_run_if
+                    .1
+                    .iter()
+                    .map(|run_if_index| {
+                        if !is_selected(*run_if_index) {
+                            Ok(false)
+                        } else if let Some(run_if) = &batches.sequential_run_if[*run_if_index] {
                             (run_if)(self).map_err(|err| {
                                 error::RunWorkload::Run((
-                                    system_names[batch.0.unwrap()].clone(),
+                                    system_names[batches.sequential[*run_if_index]].clone(),
                                     err,
                                 ))
-                            })?
-                        } else {
-                            true
-                        }
-                    } else {
-                        true
-                    },
-                    batches_run_if
-                        .1
-                        .iter()
-                        .map(|run_if_index| {
-                            if let Some(run_if) = &batches.sequential_run_if[*run_if_index] {
-                                (run_if)(self).map_err(|err| {
-                                    error::RunWorkload::Run((
-                                        system_names[batches.sequential[*run_if_index]].clone(),
-                                        err,
-                                    ))
-                                })
-                            } else {
-                                Ok(true)
-                            }
-                        })
-                        .collect::<Result<Vec<_>, error::RunWorkload>>()?,
-                );
-
-                rayon::in_place_scope(|scope| {
-                    scope.spawn(|_| {
-                        if batch.1.len() == 1 {
-                            if !run_if.1[0] {
-                                return;
-                            }
-
-                            #[cfg(feature = "tracing")]
-                            let system_span = tracing::info_span!(parent: parent_span.clone(), "system", name = ?system_names[batch.1[0]]);
-                            #[cfg(feature = "tracing")]
-                            let _system_span = system_span.enter();
-
-                            result = systems[batch.1[0]](self).map_err(|err| {
-                                error::RunWorkload::Run((system_names[batch.1[0]].clone(), err))
-                            });
+                            })
                         } else {
-                            use rayon::prelude::*;
-
-                            result = batch.1.par_iter().zip(run_if.1).try_for_each(|(&index, should_run)| {
-                                if !should_run {
-                                    return Ok(());
-                                }
-
-                                #[cfg(feature = "tracing")]
-                                let system_span = tracing::info_span!(parent: parent_span.clone(), "system", name = ?system_names[index]);
-                                #[cfg(feature = "tracing")]
-                                let _system_span = system_span.enter();
-
-                                (systems[index])(self).map_err(|err| {
-                                    error::RunWorkload::Run((system_names[index].clone(), err))
-                                })
-                            });
-                        }
-                    });
-
-                    if let Some(index) = batch.0 {
-                        if run_if.0 {
-                            #[cfg(feature = "tracing")]
-                            let system_span = tracing::info_span!(parent: parent_span.clone(), "system", name = ?system_names[index]);
-                            #[cfg(feature = "tracing")]
-                            let _system_span = system_span.enter();
-
-                            systems[index](self).map_err(|err| {
-                                error::RunWorkload::Run((system_names[index].clone(), err))
-                            })?;
+                            Ok(true)
                         }
-                    }
+                    })
+                    .collect::<Result<Vec<_>, error::RunWorkload>>()?,
+            );
 
-                    Ok(())
-                })?;
+            let mut results = Vec::with_capacity(batch.1.len());
+            results.resize_with(batch.1.len(), || Ok(()));
 
-                result?;
-            }
+            let mut tasks = batch
+                .1
+                .iter()
+                .zip(run_if.1)
+                .zip(&mut results)
+                .filter(|((_, should_run), _)| *should_run)
+                .map(|((&index, _), result)| {
+                    #[cfg(feature = "tracing")]
+                    let parent_span = &parent_span;
+
+                    move || {
+                        #[cfg(feature = "tracing")]
+                        let system_span = tracing::info_span!(parent: parent_span.clone(), "system", name = ?system_names[index]);
+                        #[cfg(feature = "tracing")]
+                        let _system_span = system_span.enter();
+                        #[cfg(feature = "borrow_diagnostics")]
+                        let _holder = BorrowHolder::enter_system(&*system_names[index]);
+
+                        *result = (systems[index])(self).map_err(|err| {
+                            error::RunWorkload::Run((system_names[index].clone(), err))
+                        });
+                    }
+                })
+                .collect::<Vec<_>>();
+            let mut tasks = tasks
+                .iter_mut()
+                .map(|task| -> &mut (dyn FnMut() + Send) { task })
+                .collect::<Vec<_>>();
+
+            let mut local_result = Ok(());
+            let mut local = || {
+                if let Some(index) = batch.0 {
+                    if run_if.0 {
+                        #[cfg(feature = "tracing")]
+                        let system_span = tracing::info_span!(parent: parent_span.clone(), "system", name = ?system_names[index]);
+                        #[cfg(feature = "tracing")]
+                        let _system_span = system_span.enter();
+                        #[cfg(feature = "borrow_diagnostics")]
+                        let _holder = BorrowHolder::enter_system(&*system_names[index]);
+
+                        local_result = systems[index](self).map_err(|err| {
+                            error::RunWorkload::Run((system_names[index].clone(), err))
+                        });
+                    }
+                }
+            };
 
-            Ok(())
-        };
+            executor.execute(&mut local, &mut tasks);
 
-        if let Some(thread_pool) = &self.thread_pool {
-            thread_pool.scope(|_| run_batch())
-        } else {
-            // Use non local ThreadPool
-            run_batch()
+            local_result?;
+            results.into_iter().collect::<Result<(), _>>()?;
+
+            self.materialize_reserved_entities();
         }
+
+        Ok(())
     }
     #[cfg(not(feature = "parallel"))]
     #[allow(clippy::type_complexity)]
@@ -865,6 +1259,7 @@ let i = world.run(sys1);
         system_names: &[Box<dyn Label>],
         batches: &Batches,
         #[cfg_attr(not(feature = "tracing"), allow(unused))] workload_name: &dyn Label,
+        selected: Option<&[bool]>,
     ) -> Result<(), error::RunWorkload> {
         #[cfg(feature = "tracing")]
         let parent_span = tracing::info_span!("workload", name = ?workload_name);
@@ -875,7 +1270,14 @@ let i = world.run(sys1);
             .sequential
             .iter()
             .zip(&batches.sequential_run_if)
-            .try_for_each(|(&index, run_if)| {
+            .enumerate()
+            .try_for_each(|(sequential_index, (&index, run_if))| {
+                if let Some(selected) = selected {
+                    if !selected[sequential_index] {
+                        return Ok(());
+                    }
+                }
+
                 if let Some(run_if) = run_if.as_ref() {
                     let should_run = (run_if)(self).map_err(|err| {
                         error::RunWorkload::Run((system_names[index].clone(), err))
@@ -891,11 +1293,25 @@ let i = world.run(sys1);
                     tracing::info_span!(parent: parent_span.clone(), "system", name = ?system_name);
                 #[cfg(feature = "tracing")]
                 let _system_span = system_span.enter();
+                #[cfg(feature = "borrow_diagnostics")]
+                let _holder = BorrowHolder::enter_system(&*system_names[index]);
 
                 (systems[index])(self)
-                    .map_err(|err| error::RunWorkload::Run((system_names[index].clone(), err)))
+                    .map_err(|err| error::RunWorkload::Run((system_names[index].clone(), err)))?;
+
+                self.materialize_reserved_entities();
+
+                Ok(())
             })
     }
+    /// Makes the ids reserved with [`Entities::reserve_entity`] alive.\
+    /// Does nothing if `Entities` is already borrowed.
+    fn materialize_reserved_entities(&self) {
+        if let Ok(all_storages) = self.all_storages.borrow() {
+            // borrowing `Entities` exclusively materializes the reserved ids
+            let _entities = all_storages.entities_mut();
+        }
+    }
     /// Run the default workload if there is one.
     ///
     /// ### Borrows
@@ -920,6 +1336,7 @@ let i = world.run(sys1);
                 &scheduler.system_names,
                 scheduler.default_workload(),
                 &scheduler.default,
+                None,
             )?
         }
         Ok(())
@@ -930,6 +1347,7 @@ let i = world.run(sys1);
     /// ### Errors
     ///
     /// - `AllStorages` is already borrowed.
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub fn all_storages(&self) -> Result<ARef<'_, &'_ AllStorages>, error::Borrow> {
         self.all_storages.borrow()
     }
@@ -939,6 +1357,7 @@ let i = world.run(sys1);
     /// ### Errors
     ///
     /// - `AllStorages` is already borrowed.
+    #[cfg_attr(feature = "borrow_diagnostics", track_caller)]
     pub fn all_storages_mut(&self) -> Result<ARefMut<'_, &'_ mut AllStorages>, error::Borrow> {
         self.all_storages.borrow_mut()
     }
@@ -959,59 +1378,327 @@ let i = world.run(sys1);
 
         Ok(())
     }
-
-    /// Increments the current tracking cycle and returns the previous value.
-    #[inline]
-    pub(crate) fn get_current(&self) -> TrackingTimestamp {
-        TrackingTimestamp::new(
-            self.counter
-                .fetch_add(1, core::sync::atomic::Ordering::Acquire),
-        )
-    }
-
-    /// Returns a timestamp used to clear tracking information.
-    pub fn get_tracking_timestamp(&self) -> TrackingTimestamp {
-        TrackingTimestamp::new(self.counter.load(core::sync::atomic::Ordering::Acquire))
+    /// Inserts `storage`, replacing the storage of the same type if there is one.\
+    /// Used to put back a storage deserialized on its own, like a [`SparseSet`] or [`Entities`](crate::Entities).
+    ///
+    /// The entities of a [`SparseSet`] are not added to [`Entities`](crate::Entities).
+    pub fn insert_storage<S: 'static + Storage + Send + Sync>(&mut self, storage: S) {
+        self.all_storages.get_mut().insert_storage(storage);
     }
-}
-
-impl World {
-    /// Creates a new entity with the components passed as argument and returns its `EntityId`.  
-    /// `component` must always be a tuple, even for a single component.
+    /// Adds a [`DynamicStorage`] for components described by `info` at `storage_id`.\
+    /// Does nothing if the storage already exists with the same description.
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - The storage at `storage_id` is borrowed exclusively.
+    /// - The storage at `storage_id` isn't a [`DynamicStorage`].
+    /// - The storage at `storage_id` describes its components differently.
     ///
     /// ### Example
     ///
     /// ```
-    /// use shipyard::{Component, World};
+    /// use core::alloc::Layout;
+    /// use shipyard::{DynamicComponentInfo, StorageId, World};
     ///
-    /// #[derive(Component)]
-    /// struct U32(u32);
+    /// let mut world = World::new();
     ///
-    /// #[derive(Component)]
-    /// struct USIZE(usize);
+    /// let health = StorageId::Custom(0);
+    /// world
+    ///     .add_dynamic_storage(health, DynamicComponentInfo::new("health", Layout::new::<u32>()))
+    ///     .unwrap();
     ///
-    /// let mut world = World::new();
+    /// let entity = world.add_entity(());
+    /// world
+    ///     .borrow_dynamic_mut(health)
+    ///     .unwrap()
+    ///     .insert_bytes(entity, &10u32.to_ne_bytes());
     ///
-    /// let entity0 = world.add_entity((U32(0),));
-    /// let entity1 = world.add_entity((U32(1), USIZE(11)));
+    /// let healths = world.borrow_dynamic(health).unwrap();
+    /// assert_eq!(healths.get_bytes(entity), Some(&10u32.to_ne_bytes()[..]));
     /// ```
-    #[inline]
-    pub fn add_entity<C: TupleAddComponent>(&mut self, component: C) -> EntityId {
-        self.all_storages.get_mut().add_entity(component)
+    pub fn add_dynamic_storage(
+        &self,
+        storage_id: StorageId,
+        info: DynamicComponentInfo,
+    ) -> Result<(), error::AddDynamicStorage> {
+        let all_storages = self
+            .all_storages
+            .borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+
+        match DynamicView::new(storage_id, all_storages) {
+            Ok(storage) if storage.info().describes_same(&info) => Ok(()),
+            Ok(storage) => Err(error::AddDynamicStorage::DifferentInfo(
+                storage.info().name().to_string().into(),
+            )),
+            Err(error::CustomStorageView::GetStorage(error::GetStorage::MissingStorage {
+                ..
+            })) => {
+                let _ = self
+                    .all_storages
+                    .borrow()
+                    .map_err(error::GetStorage::AllStoragesBorrow)?
+                    .custom_storage_or_insert_by_id(storage_id, || DynamicStorage::new(info))?;
+
+                Ok(())
+            }
+            Err(err) => Err(err.into()),
+        }
     }
-    /// Creates multiple new entities and returns an iterator yielding the new `EntityId`s.  
-    /// `source` must always yield a tuple, even for a single component.
+    /// Borrows the [`DynamicStorage`] at `storage_id`.
     ///
-    /// ### Example
+    /// ### Borrows
     ///
-    /// ```
-    /// use shipyard::{Component, World};
+    /// - [`AllStorages`] (shared)
+    /// - [`DynamicStorage`] (shared)
     ///
-    /// #[derive(Component)]
-    /// struct U32(u32);
+    /// ### Errors
     ///
-    /// #[derive(Component)]
-    /// struct USIZE(usize);
+    /// - [`AllStorages`] borrow failed.
+    /// - [`DynamicStorage`] borrow failed.
+    /// - The storage at `storage_id` isn't a [`DynamicStorage`].
+    pub fn borrow_dynamic(
+        &self,
+        storage_id: StorageId,
+    ) -> Result<DynamicView<'_>, error::CustomStorageView> {
+        let all_storages = self
+            .all_storages
+            .borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+
+        DynamicView::new(storage_id, all_storages)
+    }
+    /// Borrows the [`DynamicStorage`] at `storage_id` exclusively.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - [`DynamicStorage`] (exclusive)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - [`DynamicStorage`] borrow failed.
+    /// - The storage at `storage_id` isn't a [`DynamicStorage`].
+    pub fn borrow_dynamic_mut(
+        &self,
+        storage_id: StorageId,
+    ) -> Result<DynamicViewMut<'_>, error::CustomStorageView> {
+        let all_storages = self
+            .all_storages
+            .borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+
+        DynamicViewMut::new(storage_id, all_storages)
+    }
+
+    /// Increments the current tracking cycle and returns the previous value.
+    #[inline]
+    pub(crate) fn get_current(&self) -> TrackingTimestamp {
+        TrackingTimestamp::new(
+            self.counter
+                .fetch_add(1, core::sync::atomic::Ordering::Acquire),
+        )
+    }
+
+    /// Returns a timestamp used to clear tracking information.
+    pub fn get_tracking_timestamp(&self) -> TrackingTimestamp {
+        TrackingTimestamp::new(self.counter.load(core::sync::atomic::Ordering::Acquire))
+    }
+
+    /// Collects the changes made to `C` components since `last`: inserted or modified components, tombstones for removed or deleted ones
+    /// and the entities deleted while they had a `C` component.\
+    /// Returns the timestamp to use as `last` for the next delta.
+    ///
+    /// `C` can be a single component or a tuple of components, they all need [`track::All`](crate::track::All) tracking.\
+    /// Use `TrackingTimestamp::new(0)` to collect all components for a first delta.
+    ///
+    /// The delta can be serialized and sent to another [`World`] then applied with [`World::apply_delta`].
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `C` storages (shared)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `C` storage borrow failed.
+    /// - `C` storage doesn't exist.
+    /// - `C` tracking isn't enabled.
+    ///
+    /// ### Example
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use shipyard::{Component, TrackingTimestamp, World};
+    ///
+    /// #[derive(Component, Clone, Serialize, Deserialize, PartialEq, Debug)]
+    /// struct Health(u32);
+    ///
+    /// let mut server = World::new();
+    /// server.track_all::<Health>();
+    /// let mut client = World::new();
+    ///
+    /// let entity = server.add_entity(Health(100));
+    ///
+    /// let (delta, last) = server.delta::<Health>(TrackingTimestamp::new(0)).unwrap();
+    /// client.apply_delta::<Health>(delta);
+    /// assert_eq!(**client.get::<&Health>(entity).unwrap(), Health(100));
+    ///
+    /// server.remove::<Health>(entity);
+    ///
+    /// let (delta, last) = server.delta::<Health>(last).unwrap();
+    /// assert_eq!(delta.removed, [entity]);
+    /// client.apply_delta::<Health>(delta);
+    /// assert!(client.get::<&Health>(entity).is_err());
+    ///
+    /// server.add_component(entity, Health(50));
+    /// server.delete_entity(entity);
+    ///
+    /// let (delta, _) = server.delta::<Health>(last).unwrap();
+    /// assert_eq!(delta.deleted_entities, [entity]);
+    /// client.apply_delta::<Health>(delta);
+    /// assert!(!client.is_entity_alive(entity));
+    /// ```
+    #[cfg(feature = "serde1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde1")))]
+    pub fn delta<C: DeltaComponents>(
+        &self,
+        last: TrackingTimestamp,
+    ) -> Result<(C::Delta, TrackingTimestamp), error::GetStorage> {
+        self.all_storages
+            .borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?
+            .delta::<C>(last)
+    }
+
+    /// Applies changes collected by [`World::delta`].
+    ///
+    /// Entities are added with the same [`EntityId`] they have in the source [`World`].\
+    /// Tombstones remove the components, the entities stay alive. Deleted entities are deleted with all their components.
+    #[cfg(feature = "serde1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde1")))]
+    pub fn apply_delta<C: DeltaComponents>(&mut self, delta: C::Delta) {
+        self.all_storages.get_mut().apply_delta::<C>(delta);
+    }
+
+    /// Serializes the alive entities and `C` components.
+    ///
+    /// `C` can be a single [`Persistent`](crate::Persistent) component or a tuple of them.\
+    /// Components are saved under their [`Persistent::KEY`](crate::Persistent::KEY) along with their [`Persistent::VERSION`](crate::Persistent::VERSION).
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - [`Entities`](crate::Entities) (shared)
+    /// - `C` storages (shared)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - [`Entities`](crate::Entities) borrow failed.
+    /// - `C` storage borrow failed.
+    /// - Serialization failed.
+    ///
+    /// ### Example
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use shipyard::{Component, Persistent, World};
+    ///
+    /// #[derive(Component, Serialize, Deserialize, PartialEq, Debug)]
+    /// struct Health(u32);
+    ///
+    /// impl Persistent for Health {
+    ///     const KEY: &'static str = "health";
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.add_entity(Health(100));
+    ///
+    /// let mut bytes = Vec::new();
+    /// world
+    ///     .save::<Health, _>(&mut serde_json::Serializer::new(&mut bytes))
+    ///     .unwrap();
+    ///
+    /// let mut loaded = World::new();
+    /// loaded
+    ///     .load::<Health, _>(&mut serde_json::Deserializer::from_slice(&bytes))
+    ///     .unwrap();
+    /// assert_eq!(**loaded.get::<&Health>(entity).unwrap(), Health(100));
+    /// ```
+    #[cfg(feature = "serde1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde1")))]
+    pub fn save<C: PersistentComponents, S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let all_storages = self
+            .all_storages
+            .borrow()
+            .map_err(|err| serde::ser::Error::custom(error::GetStorage::AllStoragesBorrow(err)))?;
+
+        persist::save::<C, S>(&all_storages, serializer)
+    }
+
+    /// Adds the entities and `C` components saved with [`World::save`].
+    ///
+    /// Components saved with an older version are loaded with [`Persistent::migrate`](crate::Persistent::migrate).\
+    /// Components saved under a key that isn't part of `C` are skipped if the format can skip values.
+    /// Entities keep their [`EntityId`], a component whose index is used by a more recent entity is skipped.
+    ///
+    /// ### Errors
+    ///
+    /// - Deserialization failed.
+    /// - A component was saved with a more recent version.
+    /// - A component was saved with an older version and couldn't be migrated.
+    #[cfg(feature = "serde1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde1")))]
+    pub fn load<'de, C: PersistentComponents, D: serde::Deserializer<'de>>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        persist::load::<C, D>(self.all_storages.get_mut(), deserializer)
+    }
+}
+
+impl World {
+    /// Creates a new entity with the components passed as argument and returns its `EntityId`.  
+    /// `component` must always be a tuple, even for a single component.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct USIZE(usize);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity0 = world.add_entity((U32(0),));
+    /// let entity1 = world.add_entity((U32(1), USIZE(11)));
+    /// ```
+    #[inline]
+    pub fn add_entity<C: TupleAddComponent>(&mut self, component: C) -> EntityId {
+        self.all_storages.get_mut().add_entity(component)
+    }
+    /// Creates multiple new entities and returns an iterator yielding the new `EntityId`s.  
+    /// `source` must always yield a tuple, even for a single component.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct USIZE(usize);
     ///
     /// let mut world = World::new();
     ///
@@ -1105,6 +1792,8 @@ impl World {
     }
     /// Deletes an entity with all its components. Returns true if the entity were alive.
     ///
+    /// Entities related to `entity` with a [`CleanupPolicy::DeleteSource`](crate::CleanupPolicy::DeleteSource) relation are deleted too.
+    ///
     /// ### Example
     ///
     /// ```
@@ -1250,6 +1939,12 @@ impl World {
     pub fn spawn(&mut self, entity: EntityId) -> bool {
         self.all_storages.get_mut().spawn(entity)
     }
+    /// Make the given entity alive, its index has to be outside of the local range.\
+    /// See [`Entities::try_spawn`](crate::Entities::try_spawn).
+    #[inline]
+    pub fn try_spawn(&mut self, entity: EntityId) -> Result<(), error::Spawn> {
+        self.all_storages.get_mut().try_spawn(entity)
+    }
 
     /// Deletes all components for which `f(id, &component)` returns `false`.
     ///
@@ -1327,6 +2022,528 @@ impl World {
         self.all_storages.get_mut().track_all::<T>();
     }
 
+    /// Creates an index of `T` components, used by [`IndexedView`], [`World::find_by`] and [`World::find_all_by`].
+    ///
+    /// From then on `T`'s storage logs which entities gained, changed or lost their component.
+    /// The index only goes through the entries logged since it was last borrowed,
+    /// it doesn't use `T`'s tracking, which stays under the user's control.
+    ///
+    /// Adding an index for a component that is already indexed does nothing.
+    pub fn add_index<T: IndexKey + Send + Sync>(&mut self) -> &mut World {
+        let all_storages = self.all_storages.get_mut();
+        if all_storages.custom_storage::<Indexed<T>>().is_err() {
+            let indexed = Indexed::new(
+                &mut all_storages
+                    .custom_storage_or_insert_mut(SparseSet::<T>::new)
+                    .unwrap(),
+            );
+            let _ = all_storages.custom_storage_or_insert(|| indexed);
+        }
+
+        self
+    }
+
+    /// Returns an entity whose `T` component has `key` as [`IndexKey::index_key`].
+    /// If multiple entities share the same key, any of them can be returned.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `T` storage (shared)
+    /// - `T` index (exclusive)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `T` storage borrow failed.
+    /// - `T` index borrow failed or `T` is not indexed, see [`World::add_index`].
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, Get, IndexKey, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Tile {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// impl IndexKey for Tile {
+    ///     type Key = (i32, i32);
+    ///
+    ///     fn index_key(&self) -> (i32, i32) {
+    ///         (self.x, self.y)
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_index::<Tile>();
+    ///
+    /// let entity = world.add_entity(Tile { x: 0, y: 0 });
+    ///
+    /// world.run(|mut tiles: ViewMut<Tile>| {
+    ///     (&mut tiles).get(entity).unwrap().x = 1;
+    /// });
+    ///
+    /// assert_eq!(world.find_by::<Tile>(&(0, 0)).unwrap(), None);
+    /// assert_eq!(world.find_by::<Tile>(&(1, 0)).unwrap(), Some(entity));
+    /// ```
+    pub fn find_by<T: IndexKey + Send + Sync>(
+        &self,
+        key: &T::Key,
+    ) -> Result<Option<EntityId>, error::GetStorage> {
+        Ok(self.borrow::<IndexedView<'_, T>>()?.find(key))
+    }
+
+    /// Returns all entities whose `T` component has `key` as [`IndexKey::index_key`].
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `T` storage (shared)
+    /// - `T` index (exclusive)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `T` storage borrow failed.
+    /// - `T` index borrow failed or `T` is not indexed, see [`World::add_index`].
+    pub fn find_all_by<T: IndexKey + Send + Sync>(
+        &self,
+        key: &T::Key,
+    ) -> Result<Vec<EntityId>, error::GetStorage> {
+        Ok(self.borrow::<IndexedView<'_, T>>()?.find_all(key).to_vec())
+    }
+
+    /// Creates a [`SpatialGrid`] of `T` components, used by [`SpatialGridView`], [`World::query_rect`] and [`World::query_radius`].
+    ///
+    /// `cell_size` should be close to the radius of the most common queries.
+    /// The grid only revisits the entities whose `T` changed since it was last borrowed,
+    /// `T`'s tracking isn't enabled.
+    ///
+    /// Adding a grid for a component that already has one does nothing.
+    ///
+    /// ### Panics
+    ///
+    /// - `cell_size` is not greater than 0.
+    #[track_caller]
+    pub fn add_spatial_grid<T: SpatialPosition + Send + Sync>(
+        &mut self,
+        cell_size: f32,
+    ) -> &mut World {
+        let all_storages = self.all_storages.get_mut();
+        if all_storages.custom_storage::<SpatialGrid<T>>().is_err() {
+            let grid = SpatialGrid::new(
+                cell_size,
+                &mut all_storages
+                    .custom_storage_or_insert_mut(SparseSet::<T>::new)
+                    .unwrap(),
+            );
+            let _ = all_storages.custom_storage_or_insert(|| grid);
+        }
+
+        self
+    }
+
+    /// Returns the entities whose `T` position is within `min` and `max`, inclusive.
+    /// The order of the entities is unspecified.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `T` storage (shared)
+    /// - `T` grid (exclusive)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `T` storage borrow failed.
+    /// - `T` grid borrow failed or `T` has no grid, see [`World::add_spatial_grid`].
+    pub fn query_rect<T: SpatialPosition + Send + Sync>(
+        &self,
+        min: [f32; 2],
+        max: [f32; 2],
+    ) -> Result<Vec<EntityId>, error::GetStorage> {
+        Ok(self.borrow::<SpatialGridView<'_, T>>()?.query_rect(min, max))
+    }
+
+    /// Returns the entities whose `T` position is at most `radius` away from `center`.
+    /// The order of the entities is unspecified.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `T` storage (shared)
+    /// - `T` grid (exclusive)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `T` storage borrow failed.
+    /// - `T` grid borrow failed or `T` has no grid, see [`World::add_spatial_grid`].
+    pub fn query_radius<T: SpatialPosition + Send + Sync>(
+        &self,
+        center: [f32; 2],
+        radius: f32,
+    ) -> Result<Vec<EntityId>, error::GetStorage> {
+        Ok(self.borrow::<SpatialGridView<'_, T>>()?.query_radius(center, radius))
+    }
+
+    /// Creates a [`Sorted`] order of `T` components, used by [`SortedView`] and [`World::range_by`].
+    ///
+    /// The order is updated entity by entity from the changes `T`'s storage logged since it was last borrowed.
+    /// `T`'s tracking isn't enabled.
+    ///
+    /// Adding an order for a component that is already sorted does nothing.
+    pub fn add_sorted<T: SortKey + Send + Sync>(&mut self) -> &mut World {
+        let all_storages = self.all_storages.get_mut();
+        if all_storages.custom_storage::<Sorted<T>>().is_err() {
+            let sorted = Sorted::new(
+                &mut all_storages
+                    .custom_storage_or_insert_mut(SparseSet::<T>::new)
+                    .unwrap(),
+            );
+            let _ = all_storages.custom_storage_or_insert(|| sorted);
+        }
+
+        self
+    }
+
+    /// Returns the entities whose `T` component has a [`SortKey::sort_key`] within `range`, ordered by key.
+    /// Lookups use a binary search, `key..=key` returns all entities with this exact key.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `T` storage (shared)
+    /// - `T` order (exclusive)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `T` storage borrow failed.
+    /// - `T` order borrow failed or `T` is not sorted, see [`World::add_sorted`].
+    pub fn range_by<T: SortKey + Send + Sync>(
+        &self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<Vec<EntityId>, error::GetStorage> {
+        Ok(self.borrow::<SortedView<'_, T>>()?.range(range).collect())
+    }
+
+    /// Creates a [`Reactive`] set of the entities matching `Q`, used by [`World::reactive_entities`] and [`World::reactive_changes`].
+    /// Enables insertion, removal and deletion tracking for `Q`'s components.
+    ///
+    /// The removal and deletion tracking enabled this way is cleared once every reactive set using the component has seen it,
+    /// [`removed`](crate::View::removed) and [`deleted`](crate::View::deleted) then only return the changes since the least recent access of these sets.\
+    /// Tracking that was already enabled is left to the user to clear.
+    ///
+    /// The set catches up with the changes made to `Q`'s components on each access.
+    /// Entities matching when the set is created are reported as entered.
+    /// Adding a set for a signature that already has one does nothing.
+    ///
+    /// ### Panics
+    ///
+    /// - `Q` only contains [`Not`](crate::Not) components.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, Not, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// #[derive(Component)]
+    /// struct Dead;
+    ///
+    /// let mut world = World::new();
+    /// world.add_reactive::<(Enemy, Not<Dead>)>();
+    ///
+    /// let enemy = world.add_entity((Enemy,));
+    ///
+    /// let changes = world.reactive_changes::<(Enemy, Not<Dead>)>().unwrap();
+    /// assert_eq!(changes.entered, vec![enemy]);
+    ///
+    /// world.add_component(enemy, (Dead,));
+    ///
+    /// let changes = world.reactive_changes::<(Enemy, Not<Dead>)>().unwrap();
+    /// assert_eq!(changes.exited, vec![enemy]);
+    /// assert!(world.reactive_entities::<(Enemy, Not<Dead>)>().unwrap().is_empty());
+    /// ```
+    #[track_caller]
+    pub fn add_reactive<Q: ReactiveQuery>(&mut self) -> &mut World {
+        assert!(
+            Q::included_count() > 0,
+            "{} only contains Not components.",
+            core::any::type_name::<Q>()
+        );
+
+        let current = self.get_current();
+
+        let all_storages = self.all_storages.get_mut();
+        if all_storages.custom_storage::<Reactive<Q>>().is_err() {
+            Q::track(all_storages, StorageId::of::<Reactive<Q>>(), current);
+
+            // `Reactive::new` borrows storages, it can't run while the new storage is inserted
+            let reactive = Reactive::<Q>::new(all_storages, current).unwrap();
+            all_storages
+                .exclusive_storage_or_insert_mut(StorageId::of::<Reactive<Q>>(), || reactive);
+        }
+
+        self
+    }
+
+    /// Returns the entities matching `Q`.
+    /// The order of the entities is unspecified.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `Q`'s storages (shared, then exclusive if available to clear the tracking seen by all sets)
+    /// - `Q` set (exclusive)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - Storage borrow failed.
+    /// - `Q` has no set, see [`World::add_reactive`].
+    pub fn reactive_entities<Q: ReactiveQuery>(&self) -> Result<Vec<EntityId>, error::GetStorage> {
+        let all_storages = self
+            .all_storages
+            .borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+        let mut reactive = all_storages.custom_storage_mut::<Reactive<Q>>()?;
+
+        reactive.sync(&all_storages, self.get_current())?;
+
+        Ok(reactive.iter().collect())
+    }
+
+    /// Returns the entities that started or stopped matching `Q` since the last call.
+    /// The order of the entities is unspecified.
+    ///
+    /// An entity that entered then exited between two calls isn't reported.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `Q`'s storages (shared, then exclusive if available to clear the tracking seen by all sets)
+    /// - `Q` set (exclusive)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - Storage borrow failed.
+    /// - `Q` has no set, see [`World::add_reactive`].
+    pub fn reactive_changes<Q: ReactiveQuery>(&self) -> Result<ReactiveChanges, error::GetStorage> {
+        let all_storages = self
+            .all_storages
+            .borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+        let mut reactive = all_storages.custom_storage_mut::<Reactive<Q>>()?;
+
+        reactive.sync(&all_storages, self.get_current())?;
+
+        Ok(reactive.take_changes())
+    }
+
+    /// Records the changes made to `T` components in an undo/redo history.
+    ///
+    /// Changes are grouped into transactions with [`World::commit_history`] and reverted with [`World::undo`] and [`World::redo`].\
+    /// `T`'s storage clones a component when it's first modified, removed or deleted after a commit,
+    /// unchanged components aren't copied. `T`'s tracking is neither enabled nor cleared.
+    ///
+    /// Recording a component that is already recorded does nothing.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, World};
+    ///
+    /// #[derive(Component, Clone, PartialEq, Debug)]
+    /// struct Position(f32, f32);
+    ///
+    /// let mut world = World::new();
+    /// world.record_history::<Position>();
+    ///
+    /// let entity = world.add_entity(Position(0.0, 0.0));
+    /// world.commit_history();
+    ///
+    /// world.get::<&mut Position>(entity).unwrap().0 = 10.0;
+    /// world.commit_history();
+    ///
+    /// world.delete_entity(entity);
+    /// world.commit_history();
+    ///
+    /// world.undo();
+    /// assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(10.0, 0.0));
+    ///
+    /// world.undo();
+    /// assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(0.0, 0.0));
+    ///
+    /// world.redo();
+    /// assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(10.0, 0.0));
+    /// ```
+    pub fn record_history<T: Component + Clone + Send + Sync>(&mut self) -> &mut World {
+        // changes made before `T` was recorded are committed without its before-images
+        self.commit_history();
+
+        {
+            let all_storages = self.all_storages.get_mut();
+            let mut sparse_set = all_storages
+                .custom_storage_or_insert_mut(SparseSet::<T>::new)
+                .unwrap();
+            let mut history = all_storages
+                .custom_storage_or_insert_mut(History::new)
+                .unwrap();
+
+            history.register(&mut sparse_set);
+        }
+
+        self
+    }
+
+    /// Groups the changes made to recorded components since the last commit into a transaction.\
+    /// Committing clears the transactions that could be redone.
+    ///
+    /// Returns `false` if there wasn't any change.
+    pub fn commit_history(&mut self) -> bool {
+        let all_storages = &*self.all_storages.get_mut();
+
+        match all_storages.custom_storage_mut::<History>() {
+            Ok(mut history) => history.commit(all_storages),
+            Err(_) => false,
+        }
+    }
+
+    /// Reverts the last transaction, uncommitted changes are committed first.\
+    /// Entities whose components were all deleted are brought back, entities stay alive when all their components are undone.
+    ///
+    /// Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let all_storages = &*self.all_storages.get_mut();
+
+        match all_storages.custom_storage_mut::<History>() {
+            Ok(mut history) => {
+                history.commit(all_storages);
+
+                history.undo(all_storages, all_storages.get_current())
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Reapplies the last undone transaction.
+    ///
+    /// Returns `false` if there is nothing to redo, uncommitted changes clear the transactions that could be redone.
+    pub fn redo(&mut self) -> bool {
+        let all_storages = &*self.all_storages.get_mut();
+
+        match all_storages.custom_storage_mut::<History>() {
+            Ok(mut history) => {
+                history.commit(all_storages);
+
+                history.redo(all_storages, all_storages.get_current())
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Adds a relation of kind `R` from `source` to `target`.\
+    /// Returns `false` if the relation was already present.
+    ///
+    /// ### Panics
+    ///
+    /// - `source` or `target` is not alive.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Relation, World};
+    ///
+    /// struct Likes;
+    /// impl Relation for Likes {}
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let alice = world.add_entity(());
+    /// let bob = world.add_entity(());
+    /// let carol = world.add_entity(());
+    ///
+    /// world.add_relation::<Likes>(alice, bob);
+    /// world.add_relation::<Likes>(alice, carol);
+    /// world.add_relation::<Likes>(carol, bob);
+    ///
+    /// assert_eq!(world.relation_targets::<Likes>(alice).unwrap(), vec![bob, carol]);
+    /// assert_eq!(world.relation_sources::<Likes>(bob).unwrap(), vec![alice, carol]);
+    ///
+    /// world.delete_entity(bob);
+    ///
+    /// assert_eq!(world.relation_targets::<Likes>(alice).unwrap(), vec![carol]);
+    /// ```
+    #[track_caller]
+    pub fn add_relation<R: Relation>(&mut self, source: EntityId, target: EntityId) -> bool {
+        self.all_storages
+            .get_mut()
+            .add_relation::<R>(source, target)
+    }
+
+    /// Removes the relation of kind `R` from `source` to `target`.\
+    /// Returns `false` if the relation wasn't present.
+    pub fn remove_relation<R: Relation>(&mut self, source: EntityId, target: EntityId) -> bool {
+        self.all_storages
+            .get_mut()
+            .remove_relation::<R>(source, target)
+    }
+
+    /// Returns the targets of all `R` relations starting from `source`.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `R` relations (shared)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `R` relations borrow failed.
+    pub fn relation_targets<R: Relation>(
+        &self,
+        source: EntityId,
+    ) -> Result<Vec<EntityId>, error::GetStorage> {
+        let all_storages = self
+            .all_storages
+            .borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+        let relations = all_storages.custom_storage_or_insert(Relations::<R>::new)?;
+
+        Ok(relations.targets(source).to_vec())
+    }
+
+    /// Returns the sources of all `R` relations pointing to `target`.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `R` relations (shared)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `R` relations borrow failed.
+    pub fn relation_sources<R: Relation>(
+        &self,
+        target: EntityId,
+    ) -> Result<Vec<EntityId>, error::GetStorage> {
+        let all_storages = self
+            .all_storages
+            .borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+        let relations = all_storages.custom_storage_or_insert(Relations::<R>::new)?;
+
+        Ok(relations.sources(target).to_vec())
+    }
+
     #[doc = "Retrieve components of `entity`.
 
 Multiple components can be queried at the same time using a tuple.
@@ -1512,8 +2729,7 @@ for (i, j) in &mut iter {
     pub fn is_entity_alive(&mut self, entity: EntityId) -> bool {
         self.all_storages
             .get_mut()
-            .exclusive_storage_mut::<Entities>()
-            .unwrap()
+            .exclusive_entities_mut()
             .is_alive(entity)
     }
 
@@ -1,16 +1,20 @@
 use crate::all_storages::{AllStoragesBuilder, LockPresent, ThreadIdPresent};
-use crate::atomic_refcell::AtomicRefCell;
+use crate::atomic_refcell::{AtomicRefCell, WaitState};
+use crate::executor::Executor;
 use crate::public_transport::ShipyardRwLock;
 use crate::world::World;
+use alloc::boxed::Box;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 use core::sync::atomic::AtomicU32;
 
-/// Builder for [`World`] when one wants custom lock, custom thread pool
+/// Builder for [`World`] when one wants custom lock, custom thread pool, custom executor
 /// or custom thread id provider function.
 pub struct WorldBuilder<Lock, ThreadId> {
     all_storages_builder: AllStoragesBuilder<Lock, ThreadId>,
     #[cfg(feature = "parallel")]
     thread_pool: Option<rayon::ThreadPool>,
+    executor: Option<Box<dyn Executor>>,
 }
 
 impl World {
@@ -22,6 +26,7 @@ impl World {
             all_storages_builder: AllStoragesBuilder::<LockPresent, ThreadIdPresent>::new(),
             #[cfg(feature = "parallel")]
             thread_pool: None,
+            executor: None,
         }
     }
 
@@ -34,6 +39,7 @@ impl World {
                 crate::all_storages::MissingLock,
                 ThreadIdPresent,
             >::new(),
+            executor: None,
         }
     }
 
@@ -47,6 +53,7 @@ impl World {
                 crate::all_storages::MissingLock,
                 crate::all_storages::MissingThreadId,
             >::new(),
+            executor: None,
         }
     }
 }
@@ -62,6 +69,7 @@ impl<Lock, ThreadId> WorldBuilder<Lock, ThreadId> {
             all_storages_builder: self.all_storages_builder.with_custom_lock::<L>(),
             #[cfg(feature = "parallel")]
             thread_pool: self.thread_pool,
+            executor: self.executor,
         }
     }
 
@@ -83,6 +91,7 @@ impl<Lock, ThreadId> WorldBuilder<Lock, ThreadId> {
             all_storages_builder: self.all_storages_builder.with_custom_thread_id(thread_id),
             #[cfg(feature = "parallel")]
             thread_pool: self.thread_pool,
+            executor: self.executor,
         }
     }
 
@@ -100,21 +109,40 @@ impl<Lock, ThreadId> WorldBuilder<Lock, ThreadId> {
 
         self
     }
+
+    /// Use a custom [`Executor`] to run workloads.
+    ///
+    /// Takes precedence over the local [`ThreadPool`](rayon::ThreadPool) and the default [`RayonExecutor`](crate::RayonExecutor).\
+    /// Without the `parallel` feature, this is the only way to run the systems of a batch in parallel.
+    pub fn with_executor(
+        mut self,
+        executor: impl Executor + 'static,
+    ) -> WorldBuilder<Lock, ThreadId> {
+        self.executor = Some(Box::new(executor));
+
+        self
+    }
 }
 
 impl WorldBuilder<LockPresent, ThreadIdPresent> {
     /// Creates a new [`World`] based on the [`WorldBuilder`] config.
     pub fn build(self) -> World {
         let counter = Arc::new(AtomicU32::new(1));
+        let wait_state = Arc::new(WaitState::default());
 
-        let all_storages = self.all_storages_builder.build(counter.clone());
+        let all_storages = self
+            .all_storages_builder
+            .build(counter.clone(), wait_state.clone());
 
         World {
             all_storages,
             scheduler: AtomicRefCell::new(Default::default()),
             counter,
+            wait_state,
             #[cfg(feature = "parallel")]
             thread_pool: self.thread_pool,
+            executor: self.executor,
+            plugins: Vec::new(),
         }
     }
 }
@@ -156,3 +156,70 @@ fn workload() {
     world.run_default().unwrap();
     world.run_default().unwrap();
 }
+
+#[test]
+fn reserve_entity() {
+    fn reserve(entities: EntitiesView, reserved: UniqueView<Reserved>) {
+        let ids = (0..10)
+            .map(|_| entities.reserve_entity())
+            .collect::<Vec<_>>();
+
+        reserved.0.lock().unwrap().extend(ids);
+    }
+
+    struct Reserved(std::sync::Mutex<Vec<EntityId>>);
+    impl Unique for Reserved {}
+
+    let mut world = World::new();
+    world.add_unique(Reserved(std::sync::Mutex::new(Vec::new())));
+
+    let deleted = world
+        .bulk_add_entity((0..4).map(|i| (U32(i),)))
+        .collect::<Vec<_>>();
+    for entity in deleted {
+        world.delete_entity(entity);
+    }
+
+    world.add_workload(|| (reserve, reserve, reserve).into_workload());
+    world.run_default().unwrap();
+
+    let reserved = world
+        .remove_unique::<Reserved>()
+        .unwrap()
+        .0
+        .into_inner()
+        .unwrap();
+    assert_eq!(reserved.len(), 30);
+    assert_eq!(
+        reserved.iter().filter(|entity| entity.gen() == 1).count(),
+        4
+    );
+
+    let entities = world.borrow::<EntitiesView>().unwrap();
+    for (i, entity) in reserved.iter().enumerate() {
+        assert!(entities.is_alive(*entity));
+        assert!(!reserved[..i].contains(entity));
+    }
+    drop(entities);
+
+    let entity = world.add_entity((U32(0),));
+    assert_eq!(entity.index(), 30);
+}
+
+#[test]
+fn reserved_entity_exclusive_access() {
+    let mut world = World::new();
+
+    let deleted = world.add_entity(());
+    world.delete_entity(deleted);
+
+    let recycled = world.borrow::<EntitiesView>().unwrap().reserve_entity();
+    let fresh = world.borrow::<EntitiesView>().unwrap().reserve_entity();
+
+    world.add_component(recycled, U32(0));
+    assert_eq!(world.get::<&U32>(recycled).unwrap().0, 0);
+
+    let reserved = world.borrow::<EntitiesView>().unwrap().reserve_entity();
+    assert!(world.is_entity_alive(reserved));
+    assert!(world.is_entity_alive(fresh));
+}
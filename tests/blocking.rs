@@ -0,0 +1,117 @@
+#![cfg(feature = "std")]
+
+use shipyard::*;
+use std::time::Duration;
+
+struct U32;
+impl Component for U32 {}
+
+struct Counter(u32);
+impl Unique for Counter {}
+
+#[test]
+fn wait_for_release() {
+    let world = World::new();
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let u32s = world.borrow::<ViewMut<U32>>().unwrap();
+            sender.send(()).unwrap();
+            std::thread::sleep(Duration::from_millis(50));
+            drop(u32s);
+        });
+
+        receiver.recv().unwrap();
+        assert!(world.borrow::<View<U32>>().is_err());
+        assert!(world.borrow_blocking::<View<U32>>(None).is_ok());
+    });
+}
+
+#[test]
+fn wait_for_all_storages_release() {
+    let world = World::new();
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    let all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            assert!(world.borrow::<View<U32>>().is_err());
+            sender.send(()).unwrap();
+            assert!(world.borrow_blocking::<View<U32>>(None).is_ok());
+        });
+
+        receiver.recv().unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        drop(all_storages);
+    });
+}
+
+#[test]
+fn other_world_release() {
+    let world = World::new();
+    let other_world = World::new();
+
+    let _u32s = world.borrow::<ViewMut<U32>>().unwrap();
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            for _ in 0..10 {
+                drop(other_world.borrow::<ViewMut<U32>>().unwrap());
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        });
+
+        assert!(world
+            .borrow_blocking::<View<U32>>(Some(Duration::from_millis(20)))
+            .is_err());
+    });
+}
+
+#[test]
+fn timeout() {
+    let world = World::new();
+
+    let _u32s = world.borrow::<ViewMut<U32>>().unwrap();
+
+    assert!(matches!(
+        world.borrow_blocking::<View<U32>>(Some(Duration::from_millis(10))),
+        Err(error::GetStorage::StorageBorrow {
+            borrow: error::Borrow::Unique,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn missing_storage() {
+    let world = World::new();
+
+    assert!(matches!(
+        world.borrow_blocking::<UniqueView<Counter>>(None),
+        Err(error::GetStorage::MissingStorage { .. })
+    ));
+}
+
+#[test]
+fn run_blocking() {
+    fn increment(mut counter: UniqueViewMut<Counter>, _u32s: ViewMut<U32>) {
+        counter.0 += 1;
+    }
+
+    let world = World::new();
+    world.add_unique(Counter(0));
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    world.run_blocking(increment, None).unwrap();
+                }
+            });
+        }
+    });
+
+    assert_eq!(world.borrow::<UniqueView<Counter>>().unwrap().0, 400);
+}
@@ -86,7 +86,9 @@ fn invalid_borrow() {
         Some(error::GetStorage::StorageBorrow {
             name: Some(type_name::<SparseSet<U32>>()),
             id: StorageId::of::<SparseSet<U32>>(),
-            borrow: error::Borrow::Unique
+            borrow: error::Borrow::Unique,
+            #[cfg(feature = "borrow_diagnostics")]
+            holders: Vec::new(),
         })
     );
 }
@@ -102,7 +104,9 @@ fn all_storages_invalid_borrow() {
         Some(error::GetStorage::StorageBorrow {
             name: Some(type_name::<SparseSet<U32>>()),
             id: StorageId::of::<SparseSet<U32>>(),
-            borrow: error::Borrow::Unique
+            borrow: error::Borrow::Unique,
+            #[cfg(feature = "borrow_diagnostics")]
+            holders: Vec::new(),
         })
     );
 }
@@ -152,7 +156,9 @@ fn non_send_storage_in_other_thread() {
                 Some(error::GetStorage::StorageBorrow {
                     name: Some(type_name::<NonSend<SparseSet<NotSend>>>()),
                     id: StorageId::of::<NonSend<SparseSet<NotSend>>>(),
-                    borrow: error::Borrow::WrongThread
+                    borrow: error::Borrow::WrongThread,
+                    #[cfg(feature = "borrow_diagnostics")]
+                    holders: Vec::new(),
                 })
             )
         },
@@ -171,7 +177,9 @@ fn non_send_sync_storage_in_other_thread() {
                 Some(error::GetStorage::StorageBorrow {
                     name: Some(type_name::<NonSendSync<SparseSet<NotSendSync>>>()),
                     id: StorageId::of::<NonSendSync<SparseSet<NotSendSync>>>(),
-                    borrow: error::Borrow::WrongThread
+                    borrow: error::Borrow::WrongThread,
+                    #[cfg(feature = "borrow_diagnostics")]
+                    holders: Vec::new(),
                 })
             )
         },
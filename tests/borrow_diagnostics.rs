@@ -0,0 +1,67 @@
+#![cfg(feature = "borrow_diagnostics")]
+
+use shipyard::*;
+
+#[derive(Component, Debug)]
+struct U32;
+
+#[test]
+fn location() {
+    let world = World::new();
+
+    let line = line!() + 1;
+    let u32s = world.borrow::<ViewMut<U32>>().unwrap();
+
+    let err = world.borrow::<View<U32>>().unwrap_err();
+    match err.holders() {
+        [BorrowHolder::Location(location)] => {
+            assert_eq!(location.file(), file!());
+            assert_eq!(location.line(), line);
+        }
+        holders => panic!("{:?}", holders),
+    }
+    assert!(err
+        .to_string()
+        .contains(&format!("Held by: {}:{}", file!(), line)));
+
+    drop(u32s);
+
+    let _first = world.borrow::<View<U32>>().unwrap();
+    let _second = world.borrow::<View<U32>>().unwrap();
+    let err = world.borrow::<ViewMut<U32>>().unwrap_err();
+    assert_eq!(err.holders().len(), 2);
+}
+
+#[test]
+fn holders_are_kept_by_the_error() {
+    let world = World::new();
+
+    let u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    let err = world.borrow::<View<U32>>().unwrap_err();
+    drop(u32s);
+
+    let _first = world.borrow::<View<U32>>().unwrap();
+    let _second = world.borrow::<View<U32>>().unwrap();
+    let other_err = world.borrow::<ViewMut<U32>>().unwrap_err();
+
+    assert_eq!(err.holders().len(), 1);
+    assert_eq!(other_err.holders().len(), 2);
+}
+
+#[test]
+fn system() {
+    fn holder(all_storages: AllStoragesView<'_>) {
+        let _u32s = all_storages.borrow::<ViewMut<U32>>().unwrap();
+
+        let err = all_storages.borrow::<View<U32>>().unwrap_err();
+        match err.holders() {
+            [BorrowHolder::System(label)] => assert!(format!("{:?}", label).contains("holder")),
+            holders => panic!("{:?}", holders),
+        }
+        assert!(err.to_string().contains("Held by: system"));
+    }
+
+    let world = World::new();
+    world.add_workload(|| holder.into_workload());
+    world.run_default().unwrap();
+}
@@ -0,0 +1,184 @@
+use core::alloc::Layout;
+use shipyard::*;
+use std::sync::Arc;
+
+const POSITION: StorageId = StorageId::Custom(0);
+const NAME: StorageId = StorageId::Custom(1);
+
+fn position_info() -> DynamicComponentInfo {
+    DynamicComponentInfo::new("position", Layout::new::<[f32; 2]>())
+}
+
+fn bytes(position: [f32; 2]) -> Vec<u8> {
+    position.iter().flat_map(|x| x.to_ne_bytes()).collect()
+}
+
+#[test]
+fn plain_bytes() {
+    let mut world = World::new();
+    world
+        .add_dynamic_storage(POSITION, position_info())
+        .unwrap();
+
+    let e0 = world.add_entity(());
+    let e1 = world.add_entity(());
+    let e2 = world.add_entity(());
+
+    {
+        let mut positions = world.borrow_dynamic_mut(POSITION).unwrap();
+        positions.insert_bytes(e0, &bytes([0.0, 1.0]));
+        positions.insert_bytes(e1, &bytes([2.0, 3.0]));
+        positions.insert_bytes(e2, &bytes([4.0, 5.0]));
+        positions.insert_bytes(e1, &bytes([6.0, 7.0]));
+
+        assert_eq!(positions.len(), 3);
+        assert!(positions.remove(e0));
+        assert!(!positions.remove(e0));
+    }
+
+    let positions = world.borrow_dynamic(POSITION).unwrap();
+    assert_eq!(positions.info().name(), "position");
+    assert_eq!(positions.get_bytes(e0), None);
+    assert_eq!(positions.get_bytes(e1), Some(&bytes([6.0, 7.0])[..]));
+
+    let mut iter: Vec<_> = positions
+        .iter()
+        .map(|(entity, ptr)| (entity, unsafe { *ptr.cast::<[f32; 2]>() }))
+        .collect();
+    iter.sort_unstable_by_key(|(entity, _)| *entity);
+    assert_eq!(iter, vec![(e1, [6.0, 7.0]), (e2, [4.0, 5.0])]);
+}
+
+#[test]
+fn drop_and_clone() {
+    let mut world = World::new();
+    world
+        .add_dynamic_storage(NAME, DynamicComponentInfo::of::<Arc<str>>())
+        .unwrap();
+
+    let name: Arc<str> = Arc::from("shipyard");
+    let e0 = world.add_entity(());
+    let e1 = world.add_entity(());
+
+    {
+        let mut names = world.borrow_dynamic_mut(NAME).unwrap();
+
+        let component = std::mem::ManuallyDrop::new(name.clone());
+        unsafe { names.insert(e0, (&*component as *const Arc<str>).cast()) };
+
+        assert!(names.clone_component(e0, e1));
+        assert_eq!(Arc::strong_count(&name), 3);
+        assert_eq!(
+            unsafe { &**names.get(e1).unwrap().cast::<Arc<str>>() },
+            "shipyard"
+        );
+
+        let mut taken = std::mem::MaybeUninit::<Arc<str>>::uninit();
+        assert!(unsafe { names.take(e1, taken.as_mut_ptr().cast()) });
+        let taken = unsafe { taken.assume_init() };
+        assert_eq!(Arc::strong_count(&name), 3);
+        drop(taken);
+        assert_eq!(Arc::strong_count(&name), 2);
+    }
+
+    world.delete_entity(e0);
+    assert_eq!(Arc::strong_count(&name), 1);
+
+    world.add_entity(());
+    let e2 = world.add_entity(());
+    {
+        let mut names = world.borrow_dynamic_mut(NAME).unwrap();
+        let component = std::mem::ManuallyDrop::new(name.clone());
+        unsafe { names.insert(e2, (&*component as *const Arc<str>).cast()) };
+    }
+    assert_eq!(Arc::strong_count(&name), 2);
+
+    drop(world);
+    assert_eq!(Arc::strong_count(&name), 1);
+}
+
+#[test]
+fn zero_sized() {
+    let mut world = World::new();
+    world
+        .add_dynamic_storage(
+            POSITION,
+            DynamicComponentInfo::new("tag", Layout::new::<()>()),
+        )
+        .unwrap();
+
+    let entities: Vec<_> = (0..10).map(|_| world.add_entity(())).collect();
+
+    let mut tags = world.borrow_dynamic_mut(POSITION).unwrap();
+    for &entity in &entities {
+        tags.insert_bytes(entity, &[]);
+    }
+    tags.remove(entities[3]);
+
+    assert_eq!(tags.len(), 9);
+    assert!(!tags.contains(entities[3]));
+    assert!(tags.contains(entities[4]));
+}
+
+#[test]
+fn wrong_type() {
+    let world = World::new();
+    world
+        .add_custom_storage(POSITION, SparseSet::<Unit>::new_custom_storage())
+        .unwrap();
+
+    assert!(matches!(
+        world.borrow_dynamic(POSITION),
+        Err(error::CustomStorageView::WrongType(_))
+    ));
+    assert!(matches!(
+        world.borrow_dynamic(NAME),
+        Err(error::CustomStorageView::GetStorage(
+            error::GetStorage::MissingStorage { .. }
+        ))
+    ));
+}
+
+#[test]
+fn add_existing_storage() {
+    let world = World::new();
+    world
+        .add_dynamic_storage(POSITION, position_info())
+        .unwrap();
+    world
+        .add_dynamic_storage(POSITION, position_info())
+        .unwrap();
+
+    assert!(matches!(
+        world.add_dynamic_storage(POSITION, DynamicComponentInfo::of::<u8>()),
+        Err(error::AddDynamicStorage::DifferentInfo(_))
+    ));
+
+    world
+        .add_custom_storage(NAME, SparseSet::<Unit>::new_custom_storage())
+        .unwrap();
+    assert!(matches!(
+        world.add_dynamic_storage(NAME, position_info()),
+        Err(error::AddDynamicStorage::CustomStorageView(
+            error::CustomStorageView::WrongType(_)
+        ))
+    ));
+}
+
+#[test]
+#[should_panic]
+fn bytes_with_drop() {
+    let mut world = World::new();
+    world
+        .add_dynamic_storage(NAME, DynamicComponentInfo::of::<Arc<str>>())
+        .unwrap();
+
+    let entity = world.add_entity(());
+    world
+        .borrow_dynamic_mut(NAME)
+        .unwrap()
+        .insert_bytes(entity, &[0; 16]);
+}
+
+struct Unit;
+impl Component for Unit {}
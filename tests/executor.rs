@@ -0,0 +1,190 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use shipyard::*;
+use std::sync::Arc;
+
+struct U32(u32);
+impl Component for U32 {}
+
+struct Unit;
+impl Component for Unit {}
+
+struct Counting {
+    batches: Arc<AtomicUsize>,
+    tasks: Arc<AtomicUsize>,
+}
+
+impl Executor for Counting {
+    fn execute(&self, local: &mut (dyn FnMut() + Send), tasks: &mut [&mut (dyn FnMut() + Send)]) {
+        self.batches.fetch_add(1, Ordering::Relaxed);
+        self.tasks.fetch_add(tasks.len(), Ordering::Relaxed);
+
+        SequentialExecutor.execute(local, tasks);
+    }
+}
+
+fn add_one(mut u32s: ViewMut<U32>) {
+    for mut u32 in (&mut u32s).iter() {
+        u32.0 += 1;
+    }
+}
+
+fn read_u32(_: View<U32>) {}
+
+fn read_unit(_: View<Unit>) {}
+
+#[test]
+fn custom_executor() {
+    let batches = Arc::new(AtomicUsize::new(0));
+    let tasks = Arc::new(AtomicUsize::new(0));
+
+    let world = World::builder()
+        .with_executor(Counting {
+            batches: batches.clone(),
+            tasks: tasks.clone(),
+        })
+        .build();
+
+    let entity = world
+        .borrow::<EntitiesViewMut>()
+        .unwrap()
+        .add_entity(&mut world.borrow::<ViewMut<U32>>().unwrap(), U32(0));
+
+    Workload::new("")
+        .with_system(add_one)
+        .with_system(read_u32)
+        .with_system(read_unit)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default().unwrap();
+
+    assert_eq!(batches.load(Ordering::Relaxed), 2);
+    assert_eq!(tasks.load(Ordering::Relaxed), 3);
+    assert_eq!(world.borrow::<View<U32>>().unwrap()[entity].0, 1);
+}
+
+#[test]
+fn sequential_executor() {
+    let world = World::builder().with_executor(SequentialExecutor).build();
+
+    let entity = world
+        .borrow::<EntitiesViewMut>()
+        .unwrap()
+        .add_entity(&mut world.borrow::<ViewMut<U32>>().unwrap(), U32(0));
+
+    Workload::new("")
+        .with_system(add_one)
+        .with_system(add_one)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default().unwrap();
+
+    assert_eq!(world.borrow::<View<U32>>().unwrap()[entity].0, 2);
+}
+
+#[test]
+fn executor_error() {
+    let world = World::builder().with_executor(SequentialExecutor).build();
+
+    Workload::new("")
+        .with_try_system(|| -> Result<(), &'static str> { Err("error") })
+        .with_system(read_u32)
+        .add_to_world(&world)
+        .unwrap();
+
+    assert!(world.run_default().is_err());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn thread_pool_executor() {
+    let thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap();
+
+    let mut world = World::builder().with_executor(thread_pool).build();
+
+    let entity = world.add_entity(U32(0));
+
+    Workload::new("")
+        .with_system(add_one)
+        .with_system(read_unit)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default().unwrap();
+
+    assert_eq!(world.get::<&U32>(entity).unwrap().0, 1);
+    assert!(world.remove_executor().is_some());
+}
+
+// with thread_local, AllStorages can't be exclusively borrowed from the pool's threads
+#[cfg(all(feature = "parallel", not(feature = "thread_local")))]
+#[test]
+fn local_systems_run_in_thread_pool() {
+    struct Threads(Vec<usize>);
+    impl Unique for Threads {}
+
+    fn count_threads(all_storages: AllStoragesViewMut<'_>) {
+        all_storages
+            .borrow::<UniqueViewMut<Threads>>()
+            .unwrap()
+            .0
+            .push(rayon::current_num_threads());
+    }
+
+    for with_executor in [false, true] {
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(3)
+            .build()
+            .unwrap();
+
+        let builder = World::builder();
+        let world = if with_executor {
+            builder.with_executor(thread_pool).build()
+        } else {
+            builder.with_local_thread_pool(thread_pool).build()
+        };
+        world.add_unique(Threads(Vec::new()));
+
+        Workload::new("")
+            .with_system(count_threads)
+            .with_system(|| assert_eq!(rayon::current_num_threads(), 3))
+            .add_to_world(&world)
+            .unwrap();
+
+        world.run_default().unwrap();
+
+        assert_eq!(world.borrow::<UniqueView<Threads>>().unwrap().0, [3]);
+    }
+}
+
+#[test]
+fn set_executor() {
+    let batches = Arc::new(AtomicUsize::new(0));
+    let tasks = Arc::new(AtomicUsize::new(0));
+
+    let mut world = World::new();
+
+    world.set_executor(Counting {
+        batches: batches.clone(),
+        tasks: tasks.clone(),
+    });
+
+    Workload::new("")
+        .with_system(read_u32)
+        .with_system(read_unit)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default().unwrap();
+
+    assert_eq!(batches.load(Ordering::Relaxed), 1);
+    assert_eq!(tasks.load(Ordering::Relaxed), 2);
+
+    assert!(world.remove_executor().is_some());
+    world.run_default().unwrap();
+    assert_eq!(batches.load(Ordering::Relaxed), 1);
+}
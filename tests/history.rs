@@ -0,0 +1,197 @@
+use shipyard::*;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Position(u32);
+impl Component for Position {}
+
+impl IndexKey for Position {
+    type Key = u32;
+
+    fn index_key(&self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Name(&'static str);
+impl Component for Name {}
+
+#[test]
+fn undo_redo() {
+    let mut world = World::new();
+    world.record_history::<Position>().record_history::<Name>();
+
+    assert!(!world.commit_history());
+    assert!(!world.undo());
+
+    let entity0 = world.add_entity((Position(0), Name("0")));
+    let entity1 = world.add_entity(Position(1));
+    assert!(world.commit_history());
+
+    world.run(|mut positions: ViewMut<Position>| {
+        for mut position in (&mut positions).iter() {
+            position.0 += 10;
+        }
+    });
+    world.remove::<Name>(entity0);
+    world.delete_entity(entity1);
+    assert!(world.commit_history());
+
+    assert!(world.undo());
+    assert_eq!(**world.get::<&Position>(entity0).unwrap(), Position(0));
+    assert_eq!(**world.get::<&Name>(entity0).unwrap(), Name("0"));
+    assert!(world.is_entity_alive(entity1));
+    assert_eq!(**world.get::<&Position>(entity1).unwrap(), Position(1));
+
+    assert!(world.redo());
+    assert_eq!(**world.get::<&Position>(entity0).unwrap(), Position(10));
+    assert!(world.get::<&Name>(entity0).is_err());
+    assert!(world.get::<&Position>(entity1).is_err());
+
+    assert!(world.undo());
+    assert!(world.undo());
+    assert!(world.get::<&Position>(entity0).is_err());
+    assert!(world.get::<&Name>(entity0).is_err());
+    assert!(world.get::<&Position>(entity1).is_err());
+    assert!(!world.undo());
+
+    assert!(world.redo());
+    assert_eq!(**world.get::<&Position>(entity0).unwrap(), Position(0));
+    assert_eq!(**world.get::<&Position>(entity1).unwrap(), Position(1));
+}
+
+#[test]
+fn new_changes_clear_redo() {
+    let mut world = World::new();
+    world.record_history::<Position>();
+
+    let entity = world.add_entity(Position(0));
+    world.commit_history();
+
+    world.get::<&mut Position>(entity).unwrap().0 = 1;
+    world.commit_history();
+
+    assert!(world.undo());
+    assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(0));
+
+    // uncommitted, the undo commits it first
+    world.get::<&mut Position>(entity).unwrap().0 = 2;
+    assert!(!world.redo());
+
+    assert!(world.undo());
+    assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(0));
+    assert!(world.redo());
+    assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(2));
+    assert!(!world.redo());
+}
+
+#[test]
+fn record_existing_components() {
+    let mut world = World::new();
+
+    let entity = world.add_entity(Position(0));
+    world.record_history::<Position>();
+
+    world.get::<&mut Position>(entity).unwrap().0 = 1;
+    world.commit_history();
+
+    assert!(world.undo());
+    assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(0));
+    assert!(!world.undo());
+}
+
+#[test]
+fn tracking_left_to_the_user() {
+    let mut world = World::new();
+    world.track_removal::<Position>();
+    world.record_history::<Position>();
+
+    let entity0 = world.add_entity(Position(0));
+    let entity1 = world.add_entity(Position(1));
+    world.commit_history();
+
+    world.delete_entity(entity0);
+    world.remove::<Position>(entity1);
+    assert!(world.commit_history());
+
+    world.run(|positions: View<Position, track::Removal>| {
+        assert!(!positions.is_tracking_insertion());
+        assert!(!positions.is_tracking_deletion());
+        assert_eq!(positions.removed().collect::<Vec<_>>(), vec![entity1]);
+    });
+
+    assert!(world.undo());
+    assert_eq!(**world.get::<&Position>(entity0).unwrap(), Position(0));
+    assert_eq!(**world.get::<&Position>(entity1).unwrap(), Position(1));
+}
+
+#[test]
+fn first_image_of_the_transaction() {
+    let mut world = World::new();
+    world.record_history::<Position>();
+
+    let entity = world.add_entity(Position(0));
+    world.commit_history();
+
+    for i in 1..1000 {
+        world.run(|mut positions: ViewMut<Position>| {
+            (&mut positions).get(entity).unwrap().0 = i;
+        });
+    }
+    world.remove::<Position>(entity);
+    world.add_component(entity, Position(1000));
+    assert!(world.commit_history());
+
+    assert!(world.undo());
+    assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(0));
+    assert!(world.redo());
+    assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(1000));
+}
+
+#[test]
+fn mapped_without_write() {
+    let mut world = World::new();
+    world.record_history::<Position>();
+
+    let entity = world.add_entity(Position(0));
+    world.commit_history();
+
+    world.run(|mut positions: ViewMut<Position>| {
+        let position = Mut::map((&mut positions).get(entity).unwrap(), |position| {
+            &mut position.0
+        });
+        assert_eq!(*position, 0);
+    });
+    let position = world.get::<&mut Position>(entity).unwrap();
+    assert_eq!(position.0, 0);
+    drop(position);
+    assert!(!world.commit_history());
+
+    world.run(|mut positions: ViewMut<Position>| {
+        let mut position = Mut::map((&mut positions).get(entity).unwrap(), |position| {
+            &mut position.0
+        });
+        *position = 1;
+    });
+    assert!(world.commit_history());
+
+    assert!(world.undo());
+    assert_eq!(**world.get::<&Position>(entity).unwrap(), Position(0));
+}
+
+#[test]
+fn with_index() {
+    let mut world = World::new();
+    world.record_history::<Position>();
+    world.add_index::<Position>();
+
+    let entity = world.add_entity(Position(0));
+    world.commit_history();
+
+    world.get::<&mut Position>(entity).unwrap().0 = 1;
+    assert_eq!(world.find_by::<Position>(&1).unwrap(), Some(entity));
+
+    assert!(world.undo());
+    assert_eq!(world.find_by::<Position>(&0).unwrap(), Some(entity));
+    assert_eq!(world.find_by::<Position>(&1).unwrap(), None);
+}
@@ -0,0 +1,235 @@
+use shipyard::*;
+
+struct Name(&'static str);
+impl Component for Name {}
+
+impl IndexKey for Name {
+    type Key = &'static str;
+
+    fn index_key(&self) -> &'static str {
+        self.0
+    }
+}
+
+struct Team(u32);
+impl Component for Team {}
+
+impl IndexKey for Team {
+    type Key = u32;
+
+    fn index_key(&self) -> u32 {
+        self.0
+    }
+}
+
+#[test]
+fn existing_components() {
+    let mut world = World::new();
+
+    let entity = world.add_entity(Name("a"));
+    world.add_index::<Name>();
+
+    assert_eq!(world.find_by::<Name>(&"a").unwrap(), Some(entity));
+    assert_eq!(world.find_by::<Name>(&"b").unwrap(), None);
+}
+
+#[test]
+fn insert_and_modify() {
+    let mut world = World::new();
+    world.add_index::<Name>();
+
+    let entity = world.add_entity(Name("a"));
+    assert_eq!(world.find_by::<Name>(&"a").unwrap(), Some(entity));
+
+    world.run(|mut names: ViewMut<Name>| {
+        (&mut names).get(entity).unwrap().0 = "b";
+    });
+    assert_eq!(world.find_by::<Name>(&"a").unwrap(), None);
+    assert_eq!(world.find_by::<Name>(&"b").unwrap(), Some(entity));
+
+    world.add_component(entity, Name("c"));
+    assert_eq!(world.find_by::<Name>(&"b").unwrap(), None);
+    assert_eq!(world.find_by::<Name>(&"c").unwrap(), Some(entity));
+}
+
+#[test]
+fn remove_and_delete() {
+    let mut world = World::new();
+    world.add_index::<Name>();
+
+    let removed = world.add_entity(Name("removed"));
+    let deleted = world.add_entity(Name("deleted"));
+    let deleted_component = world.add_entity(Name("deleted_component"));
+
+    // sync before the changes
+    assert_eq!(world.find_by::<Name>(&"removed").unwrap(), Some(removed));
+
+    world.remove::<Name>(removed);
+    world.delete_entity(deleted);
+    world.delete_component::<Name>(deleted_component);
+
+    assert_eq!(world.find_by::<Name>(&"removed").unwrap(), None);
+    assert_eq!(world.find_by::<Name>(&"deleted").unwrap(), None);
+    assert_eq!(world.find_by::<Name>(&"deleted_component").unwrap(), None);
+
+    world.add_component(removed, Name("removed"));
+    assert_eq!(world.find_by::<Name>(&"removed").unwrap(), Some(removed));
+}
+
+#[test]
+fn cleared_tracking() {
+    let mut world = World::new();
+    world.track_removal::<Name>();
+    world.add_index::<Name>();
+
+    let entity = world.add_entity(Name("a"));
+    world.remove::<Name>(entity);
+
+    world.run(|mut names: ViewMut<Name>| names.clear_all_removed_and_deleted());
+
+    assert_eq!(world.find_by::<Name>(&"a").unwrap(), None);
+}
+
+#[test]
+fn user_tracking_untouched() {
+    let mut world = World::new();
+    world.track_removal::<Name>();
+    world.add_index::<Name>();
+
+    let entity = world.add_entity(Name("a"));
+    world.remove::<Name>(entity);
+
+    assert_eq!(world.find_by::<Name>(&"a").unwrap(), None);
+    world.run(|names: View<Name, track::Removal>| {
+        assert!(!names.is_tracking_insertion());
+        assert_eq!(names.removed().collect::<Vec<_>>(), vec![entity]);
+    });
+}
+
+#[test]
+fn system() {
+    let mut world = World::new();
+    world.add_index::<Name>();
+
+    let entity = world.add_entity(Name("a"));
+
+    world.run(|mut names: ViewMut<Name>| {
+        (&mut names).get(entity).unwrap().0 = "b";
+    });
+
+    world.run(|names: IndexedView<Name>| {
+        assert_eq!(names.find(&"a"), None);
+        assert_eq!(names.find(&"b"), Some(entity));
+        assert_eq!(names.find_all(&"b"), &[entity]);
+        assert_eq!(names.get(entity).unwrap().0, "b");
+    });
+}
+
+#[test]
+fn bulk_and_iteration() {
+    let mut world = World::new();
+    world.add_index::<Team>();
+
+    let entities = world
+        .bulk_add_entity((0..10).map(|_| (Team(0), Name("a"))))
+        .collect::<Vec<_>>();
+
+    world.run(|mut teams: ViewMut<Team>| {
+        for (i, mut team) in (&mut teams).iter().enumerate() {
+            if i % 2 == 0 {
+                team.0 = 1;
+            }
+        }
+    });
+
+    let mut team1 = world.find_all_by::<Team>(&1).unwrap();
+    team1.sort_unstable();
+    assert_eq!(
+        team1,
+        entities.iter().copied().step_by(2).collect::<Vec<_>>()
+    );
+    assert_eq!(world.find_all_by::<Team>(&0).unwrap().len(), 5);
+}
+
+#[test]
+fn many_changes_between_lookups() {
+    let mut world = World::new();
+    world.add_index::<Team>();
+
+    let entity = world.add_entity(Team(0));
+    world.add_entity(Team(0));
+    assert_eq!(world.find_all_by::<Team>(&0).unwrap().len(), 2);
+
+    for i in 0..1000 {
+        world.run(|mut teams: ViewMut<Team>| {
+            (&mut teams).get(entity).unwrap().0 = i;
+        });
+    }
+
+    assert_eq!(world.find_by::<Team>(&999).unwrap(), Some(entity));
+    assert_eq!(world.find_all_by::<Team>(&0).unwrap().len(), 1);
+}
+
+#[test]
+fn multiple_entities() {
+    let mut world = World::new();
+    world.add_index::<Team>();
+
+    let e0 = world.add_entity(Team(0));
+    let e1 = world.add_entity(Team(0));
+    let e2 = world.add_entity(Team(1));
+
+    let mut team0 = world.find_all_by::<Team>(&0).unwrap();
+    team0.sort_unstable();
+    assert_eq!(team0, vec![e0, e1]);
+    assert_eq!(world.find_all_by::<Team>(&1).unwrap(), vec![e2]);
+    assert!(world.find_all_by::<Team>(&2).unwrap().is_empty());
+}
+
+#[test]
+fn missing_index() {
+    let world = World::new();
+
+    assert!(matches!(
+        world.find_by::<Name>(&"a"),
+        Err(error::GetStorage::MissingStorage { .. })
+    ));
+}
+
+#[test]
+fn sync_drops_deleted_components() {
+    let mut world = World::new();
+    world.add_index::<Name>();
+
+    let entity = world.add_entity(Name("a"));
+    world.add_entity(Name("b"));
+    world.borrow::<IndexedView<Name>>().unwrap();
+
+    world.delete_component::<Name>(entity);
+    world.borrow::<IndexedView<Name>>().unwrap();
+
+    let all_storages = world.borrow::<AllStoragesView>().unwrap();
+    assert_eq!(
+        all_storages
+            .custom_storage::<Indexed<Name>>()
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn shared_lookup() {
+    let mut world = World::new();
+    world.add_index::<Name>();
+
+    let entity = world.add_entity(Name("a"));
+    assert_eq!(world.find_by::<Name>(&"a").unwrap(), Some(entity));
+    world.add_component(entity, Name("b"));
+
+    let _names = world.borrow::<View<Name>>().unwrap();
+
+    assert_eq!(world.find_by::<Name>(&"a").unwrap(), None);
+    assert_eq!(world.find_by::<Name>(&"b").unwrap(), Some(entity));
+    assert_eq!(world.find_all_by::<Name>(&"b").unwrap(), vec![entity]);
+}
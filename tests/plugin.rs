@@ -0,0 +1,108 @@
+use shipyard::*;
+
+struct U32(u32);
+impl Component for U32 {}
+impl Unique for U32 {}
+
+struct USIZE(usize);
+impl Component for USIZE {}
+impl Unique for USIZE {}
+
+struct Base;
+
+impl Plugin for Base {
+    fn build(&self, world: &mut World) -> Result<(), error::AddPlugin> {
+        world.add_unique(U32(0));
+        world.track_insertion::<USIZE>();
+
+        Workload::new("base")
+            .with_system(|mut u32: UniqueViewMut<U32>| u32.0 += 1)
+            .add_to_world(world)
+            .map_err(error::AddPlugin::from_custom)
+    }
+}
+
+struct Dependent;
+
+impl Plugin for Dependent {
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies.add::<Base>();
+    }
+
+    fn build(&self, world: &mut World) -> Result<(), error::AddPlugin> {
+        world.add_unique(USIZE(10));
+
+        Ok(())
+    }
+}
+
+struct Failing;
+
+impl Plugin for Failing {
+    fn build(&self, _: &mut World) -> Result<(), error::AddPlugin> {
+        Err(error::AddPlugin::from_custom("failed"))
+    }
+}
+
+#[test]
+fn add_plugin() {
+    let mut world = World::new();
+
+    world.add_plugin(Base).unwrap();
+    world.add_plugin(Dependent).unwrap();
+
+    assert!(world.contains_plugin::<Base>());
+    assert!(world.contains_plugin::<Dependent>());
+    assert_eq!(
+        world.plugins().collect::<Vec<_>>(),
+        vec![core::any::type_name::<Base>(), core::any::type_name::<Dependent>()]
+    );
+
+    world.run_workload("base").unwrap();
+
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 1);
+    assert_eq!(world.borrow::<UniqueView<USIZE>>().unwrap().0, 10);
+    assert!(world.borrow::<ViewMut<USIZE>>().unwrap().is_tracking_insertion());
+}
+
+#[test]
+fn duplicate_plugin() {
+    let mut world = World::new();
+
+    world.add_plugin(Base).unwrap();
+
+    assert_eq!(
+        world.add_plugin(Base),
+        Err(error::AddPlugin::AlreadyAdded(core::any::type_name::<Base>()))
+    );
+}
+
+#[test]
+fn missing_dependency() {
+    let mut world = World::new();
+
+    assert_eq!(
+        world.add_plugin(Dependent),
+        Err(error::AddPlugin::MissingDependency {
+            plugin: core::any::type_name::<Dependent>(),
+            dependency: core::any::type_name::<Base>(),
+        })
+    );
+    assert!(!world.contains_plugin::<Dependent>());
+    assert!(world.borrow::<UniqueView<USIZE>>().is_err());
+}
+
+#[test]
+fn failed_build() {
+    let mut world = World::new();
+
+    assert!(matches!(
+        world.add_plugin(Failing),
+        Err(error::AddPlugin::Custom(_))
+    ));
+    assert!(!world.contains_plugin::<Failing>());
+    assert!(matches!(
+        world.add_plugin(Failing),
+        Err(error::AddPlugin::Custom(_))
+    ));
+}
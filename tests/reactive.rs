@@ -0,0 +1,161 @@
+use shipyard::*;
+
+struct A;
+impl Component for A {}
+
+struct B;
+impl Component for B {}
+
+struct C;
+impl Component for C {}
+
+type Query = (A, B, Not<C>);
+
+fn sorted(mut entities: Vec<EntityId>) -> Vec<EntityId> {
+    entities.sort_unstable();
+    entities
+}
+
+#[test]
+fn existing_entities() {
+    let mut world = World::new();
+
+    let matching = world.add_entity((A, B));
+    world.add_entity((A, B, C));
+    world.add_entity((A,));
+
+    world.add_reactive::<Query>();
+
+    assert_eq!(world.reactive_entities::<Query>().unwrap(), vec![matching]);
+    assert_eq!(
+        world.reactive_changes::<Query>().unwrap(),
+        ReactiveChanges {
+            entered: vec![matching],
+            exited: Vec::new(),
+        }
+    );
+    assert_eq!(
+        world.reactive_changes::<Query>().unwrap(),
+        ReactiveChanges::default()
+    );
+}
+
+#[test]
+fn enter_and_exit() {
+    let mut world = World::new();
+    world.add_reactive::<Query>();
+
+    let e0 = world.add_entity((A, B));
+    let e1 = world.add_entity((A,));
+    let e2 = world.add_entity((A, B));
+    let e3 = world.add_entity((A, B));
+
+    assert_eq!(
+        sorted(world.reactive_changes::<Query>().unwrap().entered),
+        vec![e0, e2, e3]
+    );
+
+    world.add_component(e1, (B,));
+    world.add_component(e2, (C,));
+    world.remove::<(B,)>(e3);
+
+    let changes = world.reactive_changes::<Query>().unwrap();
+    assert_eq!(changes.entered, vec![e1]);
+    assert_eq!(sorted(changes.exited), vec![e2, e3]);
+    assert_eq!(
+        sorted(world.reactive_entities::<Query>().unwrap()),
+        vec![e0, e1]
+    );
+
+    world.delete_entity(e0);
+    world.delete_component::<(C,)>(e2);
+
+    let changes = world.reactive_changes::<Query>().unwrap();
+    assert_eq!(changes.entered, vec![e2]);
+    assert_eq!(changes.exited, vec![e0]);
+}
+
+#[test]
+fn net_changes() {
+    let mut world = World::new();
+    world.add_reactive::<Query>();
+
+    let entity = world.add_entity((A, B));
+    world.add_component(entity, (C,));
+
+    assert_eq!(
+        world.reactive_changes::<Query>().unwrap(),
+        ReactiveChanges::default()
+    );
+
+    world.delete_component::<(C,)>(entity);
+    world.reactive_entities::<Query>().unwrap();
+    world.add_component(entity, (C,));
+
+    assert_eq!(
+        world.reactive_changes::<Query>().unwrap(),
+        ReactiveChanges::default()
+    );
+}
+
+#[test]
+fn clear_seen_tracking() {
+    let mut world = World::new();
+    world.add_reactive::<Query>();
+    world.add_reactive::<(C,)>();
+
+    let entity = world.add_entity((A, B, C));
+    world.reactive_changes::<Query>().unwrap();
+    world.reactive_changes::<(C,)>().unwrap();
+
+    world.delete_component::<(C,)>(entity);
+    world.reactive_changes::<Query>().unwrap();
+
+    // `(C,)` hasn't seen the deletion yet
+    assert_eq!(
+        world
+            .borrow::<View<C, track::Deletion>>()
+            .unwrap()
+            .deleted()
+            .count(),
+        1
+    );
+    assert_eq!(
+        world.reactive_changes::<(C,)>().unwrap().exited,
+        vec![entity]
+    );
+
+    world.reactive_changes::<Query>().unwrap();
+    assert_eq!(
+        world
+            .borrow::<View<C, track::Deletion>>()
+            .unwrap()
+            .deleted()
+            .count(),
+        0
+    );
+
+    // tracking enabled by the user is left alone
+    let mut world = World::new();
+    world.track_deletion::<A>();
+    world.add_reactive::<Query>();
+
+    let entity = world.add_entity((A, B));
+    world.delete_component::<(A,)>(entity);
+    world.reactive_changes::<Query>().unwrap();
+
+    assert_eq!(
+        world
+            .borrow::<View<A, track::Deletion>>()
+            .unwrap()
+            .deleted()
+            .count(),
+        1
+    );
+}
+
+#[test]
+#[should_panic]
+fn only_not() {
+    World::new().add_reactive::<(Not<A>,)>();
+}
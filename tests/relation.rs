@@ -0,0 +1,132 @@
+use shipyard::*;
+
+struct Likes;
+impl Relation for Likes {}
+
+struct ChildOf;
+impl Relation for ChildOf {
+    const CLEANUP: CleanupPolicy = CleanupPolicy::DeleteSource;
+}
+
+struct Unit;
+impl Component for Unit {}
+
+fn is_alive(world: &World, entity: EntityId) -> bool {
+    world.borrow::<EntitiesView>().unwrap().is_alive(entity)
+}
+
+fn sorted(mut entities: Vec<EntityId>) -> Vec<EntityId> {
+    entities.sort_unstable();
+    entities
+}
+
+#[test]
+fn many_to_many() {
+    let mut world = World::new();
+
+    let a = world.add_entity(());
+    let b = world.add_entity(());
+    let c = world.add_entity(());
+
+    assert!(world.add_relation::<Likes>(a, b));
+    assert!(world.add_relation::<Likes>(a, c));
+    assert!(world.add_relation::<Likes>(b, c));
+    assert!(!world.add_relation::<Likes>(a, b));
+
+    assert_eq!(
+        sorted(world.relation_targets::<Likes>(a).unwrap()),
+        vec![b, c]
+    );
+    assert_eq!(
+        sorted(world.relation_sources::<Likes>(c).unwrap()),
+        vec![a, b]
+    );
+    assert!(world.relation_sources::<Likes>(a).unwrap().is_empty());
+    assert!(world.relation_targets::<ChildOf>(a).unwrap().is_empty());
+
+    assert!(world.remove_relation::<Likes>(a, c));
+    assert!(!world.remove_relation::<Likes>(a, c));
+    assert_eq!(world.relation_sources::<Likes>(c).unwrap(), vec![b]);
+}
+
+#[test]
+fn remove_relation_policy() {
+    let mut world = World::new();
+
+    let a = world.add_entity(());
+    let b = world.add_entity(());
+
+    world.add_relation::<Likes>(a, b);
+    world.add_relation::<Likes>(b, a);
+
+    world.delete_entity(b);
+
+    assert!(is_alive(&world, a));
+    assert!(world.relation_targets::<Likes>(a).unwrap().is_empty());
+    assert!(world.relation_sources::<Likes>(a).unwrap().is_empty());
+}
+
+#[test]
+fn delete_source_policy() {
+    let mut world = World::new();
+
+    let root = world.add_entity(());
+    let child = world.add_entity((Unit,));
+    let grand_child = world.add_entity(());
+    let other = world.add_entity(());
+
+    world.add_relation::<ChildOf>(child, root);
+    world.add_relation::<ChildOf>(grand_child, child);
+    world.add_relation::<Likes>(other, child);
+
+    world.delete_entity(root);
+
+    assert!(!is_alive(&world, child));
+    assert!(!is_alive(&world, grand_child));
+    assert!(is_alive(&world, other));
+    assert!(world.borrow::<View<Unit>>().unwrap().is_empty());
+    assert!(world.relation_targets::<Likes>(other).unwrap().is_empty());
+}
+
+#[test]
+fn cycle() {
+    let mut world = World::new();
+
+    let a = world.add_entity(());
+    let b = world.add_entity(());
+
+    world.add_relation::<ChildOf>(a, b);
+    world.add_relation::<ChildOf>(b, a);
+
+    world.delete_entity(a);
+
+    assert!(!is_alive(&world, b));
+}
+
+#[test]
+fn strip() {
+    let mut world = World::new();
+
+    let a = world.add_entity(());
+    let b = world.add_entity(());
+
+    world.add_relation::<ChildOf>(a, b);
+    world.add_relation::<ChildOf>(b, a);
+
+    world.strip(a);
+
+    assert!(world.relation_targets::<ChildOf>(a).unwrap().is_empty());
+    assert_eq!(world.relation_targets::<ChildOf>(b).unwrap(), vec![a]);
+}
+
+#[test]
+#[should_panic]
+fn dead_target() {
+    let mut world = World::new();
+
+    let a = world.add_entity(());
+    let b = world.add_entity(());
+    world.delete_entity(b);
+
+    world.add_relation::<Likes>(a, b);
+}
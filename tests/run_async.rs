@@ -0,0 +1,112 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use shipyard::*;
+
+struct U32(u32);
+impl Component for U32 {}
+
+struct USIZE(usize);
+impl Component for USIZE {}
+impl Unique for USIZE {}
+
+fn noop_waker() -> Waker {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    unsafe { Waker::from_raw(noop_raw_waker()) }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = noop_waker();
+    let mut context = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// Returns `Pending` once before completing, simulates waiting for the next frame.
+struct NextFrame(bool);
+
+impl Future for NextFrame {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            Poll::Pending
+        }
+    }
+}
+
+async fn increment(mut u32s: ViewMut<'_, U32>) {
+    NextFrame(false).await;
+
+    for mut u32 in (&mut u32s).iter() {
+        u32.0 += 1;
+    }
+}
+
+#[test]
+fn block_on_executor() {
+    let mut world = World::new();
+
+    let entity = world.add_entity(U32(0));
+
+    world.run_async(increment, block_on);
+
+    assert_eq!(world.get::<&U32>(entity).unwrap().0, 1);
+}
+
+async fn sum(u32s: View<'_, U32>, mut usize: UniqueViewMut<'_, USIZE>) -> usize {
+    NextFrame(false).await;
+
+    usize.0 = u32s.iter().map(|u32| u32.0 as usize).sum();
+    usize.0
+}
+
+#[test]
+fn multiple_views() {
+    let mut world = World::new();
+
+    world.add_entity(U32(1));
+    world.add_entity(U32(2));
+    world.add_unique(USIZE(0));
+
+    assert_eq!(world.run_async(sum, block_on), 3);
+    assert_eq!(world.borrow::<UniqueView<USIZE>>().unwrap().0, 3);
+}
+
+#[test]
+fn borrow_held_across_await() {
+    let mut world = World::new();
+
+    world.add_entity(U32(0));
+
+    let mut future = world.run_async(increment, Box::pin);
+
+    let waker = noop_waker();
+    let mut context = Context::from_waker(&waker);
+
+    assert!(future.as_mut().poll(&mut context).is_pending());
+    assert!(world.borrow::<View<U32>>().is_err());
+
+    assert!(future.as_mut().poll(&mut context).is_ready());
+    drop(future);
+
+    assert!(world.borrow::<View<U32>>().is_ok());
+}
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use shipyard::*;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct Position(u32);
+impl Component for Position {}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct Name(String);
+impl Component for Name {}
+
+fn send<T: Serialize + serde::de::DeserializeOwned>(delta: &T) -> T {
+    serde_json::from_str(&serde_json::to_string(delta).unwrap()).unwrap()
+}
+
+#[test]
+fn server_client() {
+    let mut server = World::new();
+    server.track_all::<(Position, Name)>();
+    let mut client = World::new();
+
+    let entity0 = server.add_entity((Position(0), Name("0".to_string())));
+    let entity1 = server.add_entity((Position(1), Name("1".to_string())));
+    let entity2 = server.add_entity(Position(2));
+
+    let (delta, last) = server
+        .delta::<(Position, Name)>(TrackingTimestamp::new(0))
+        .unwrap();
+    assert_eq!(delta.0.changed.len(), 3);
+    assert_eq!(delta.1.changed.len(), 2);
+    client.apply_delta::<(Position, Name)>(send(&delta));
+
+    assert_eq!(**client.get::<&Position>(entity0).unwrap(), Position(0));
+    assert_eq!(
+        **client.get::<&Name>(entity1).unwrap(),
+        Name("1".to_string())
+    );
+    assert_eq!(**client.get::<&Position>(entity2).unwrap(), Position(2));
+
+    let (delta, last) = server.delta::<(Position, Name)>(last).unwrap();
+    assert!(delta.0.is_empty());
+    assert!(delta.1.is_empty());
+
+    server.get::<&mut Position>(entity0).unwrap().0 = 10;
+    server.remove::<Name>(entity1);
+    server.delete_entity(entity2);
+
+    let (delta, _) = server.delta::<(Position, Name)>(last).unwrap();
+    assert_eq!(delta.0.changed, [(entity0, Position(10))]);
+    assert!(delta.0.removed.is_empty());
+    assert_eq!(delta.0.deleted_entities, [entity2]);
+    assert!(delta.1.changed.is_empty());
+    assert_eq!(delta.1.removed, [entity1]);
+    client.apply_delta::<(Position, Name)>(send(&delta));
+
+    assert_eq!(**client.get::<&Position>(entity0).unwrap(), Position(10));
+    assert_eq!(**client.get::<&Position>(entity1).unwrap(), Position(1));
+    assert!(client.get::<&Name>(entity1).is_err());
+    assert!(client.get::<&Position>(entity2).is_err());
+    assert!(!client.is_entity_alive(entity2));
+}
+
+#[test]
+fn reused_index() {
+    let mut server = World::new();
+    server.track_all::<(Position, Name)>();
+    let mut client = World::new();
+
+    let entity1 = server.add_entity(Name("1".to_string()));
+
+    let (delta, last) = server
+        .delta::<(Position, Name)>(TrackingTimestamp::new(0))
+        .unwrap();
+    client.apply_delta::<(Position, Name)>(send(&delta));
+
+    server.delete_entity(entity1);
+    let entity2 = server.add_entity(Position(2));
+    assert_eq!(entity2.index(), entity1.index());
+
+    let (delta, _) = server.delta::<(Position, Name)>(last).unwrap();
+    client.apply_delta::<(Position, Name)>(send(&delta));
+
+    assert!(!client.is_entity_alive(entity1));
+    assert_eq!(**client.get::<&Position>(entity2).unwrap(), Position(2));
+    assert!(client.get::<&Name>(entity2).is_err());
+    assert_eq!(client.borrow::<View<Name>>().unwrap().len(), 0);
+}
+
+#[test]
+fn older_entity_deleted_on_spawn() {
+    let mut server = World::new();
+    server.track_all::<Position>();
+    let mut client = World::new();
+
+    let entity1 = server.add_entity(Position(1));
+    let (delta, last) = server.delta::<Position>(TrackingTimestamp::new(0)).unwrap();
+    client.apply_delta::<Position>(send(&delta));
+    client.add_component(entity1, Name("1".to_string()));
+
+    server.delete_entity(entity1);
+    let entity2 = server.add_entity(Position(2));
+
+    // the deletion isn't part of the delta, only the new entity
+    let (mut delta, _) = server.delta::<Position>(last).unwrap();
+    delta.deleted_entities.clear();
+    client.apply_delta::<Position>(send(&delta));
+
+    assert!(!client.is_entity_alive(entity1));
+    assert_eq!(**client.get::<&Position>(entity2).unwrap(), Position(2));
+    assert_eq!(client.borrow::<View<Name>>().unwrap().len(), 0);
+}
+
+#[test]
+fn missing_tracking() {
+    let mut world = World::new();
+    world.add_entity(Position(0));
+
+    assert!(world.delta::<Position>(TrackingTimestamp::new(0)).is_err());
+}
@@ -1 +1,5 @@
+mod delta;
 mod entity_id;
+mod persist;
+mod storage;
+mod workload_description;
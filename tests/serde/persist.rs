@@ -0,0 +1,157 @@
+use bincode::Options;
+use serde::{Deserialize, Deserializer, Serialize};
+use shipyard::*;
+
+#[derive(Serialize, Deserialize)]
+struct PositionV0(u32, u32);
+impl Component for PositionV0 {}
+impl Persistent for PositionV0 {
+    const KEY: &'static str = "position";
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct Position {
+    x: u32,
+    y: u32,
+    z: u32,
+}
+impl Component for Position {}
+impl Persistent for Position {
+    const KEY: &'static str = "position";
+    const VERSION: u32 = 1;
+
+    fn migrate<'de, D: Deserializer<'de>>(version: u32, deserializer: D) -> Result<Self, D::Error> {
+        assert_eq!(version, 0);
+
+        let PositionV0(x, y) = PositionV0::deserialize(deserializer)?;
+
+        Ok(Position { x, y, z: 0 })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct Name(String);
+impl Component for Name {}
+impl Persistent for Name {
+    const KEY: &'static str = "name";
+}
+
+fn to_json<C: PersistentComponents>(world: &World) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    world
+        .save::<C, _>(&mut serde_json::Serializer::new(&mut bytes))
+        .unwrap();
+
+    bytes
+}
+
+#[test]
+fn json() {
+    let mut world = World::new();
+    let entity0 = world.add_entity((Position { x: 0, y: 1, z: 2 }, Name("entity0".to_string())));
+    let empty = world.add_entity(());
+    let entity1 = world.add_entity(Name("entity1".to_string()));
+    world.delete_entity(empty);
+    let empty = world.add_entity(());
+
+    let bytes = to_json::<(Position, Name)>(&world);
+
+    let mut loaded = World::new();
+    loaded
+        .load::<(Position, Name), _>(&mut serde_json::Deserializer::from_slice(&bytes))
+        .unwrap();
+
+    assert_eq!(
+        **loaded.get::<&Position>(entity0).unwrap(),
+        Position { x: 0, y: 1, z: 2 }
+    );
+    assert_eq!(
+        **loaded.get::<&Name>(entity0).unwrap(),
+        Name("entity0".to_string())
+    );
+    assert_eq!(
+        **loaded.get::<&Name>(entity1).unwrap(),
+        Name("entity1".to_string())
+    );
+    assert!(loaded.get::<&Position>(entity1).is_err());
+    assert!(loaded.is_entity_alive(empty));
+    assert_eq!(empty.gen(), 1);
+}
+
+#[test]
+fn bincode() {
+    let mut world = World::new();
+    let entity = world.add_entity((Position { x: 0, y: 1, z: 2 }, Name("0".to_string())));
+
+    let bytes = bincode::serialize(&SaveWith(&world)).unwrap();
+
+    let mut loaded = World::new();
+    loaded
+        .load::<(Position, Name), _>(&mut bincode::Deserializer::from_slice(
+            &bytes,
+            bincode::DefaultOptions::new()
+                .with_fixint_encoding()
+                .allow_trailing_bytes(),
+        ))
+        .unwrap();
+
+    assert_eq!(
+        **loaded.get::<&Position>(entity).unwrap(),
+        Position { x: 0, y: 1, z: 2 }
+    );
+    assert_eq!(
+        **loaded.get::<&Name>(entity).unwrap(),
+        Name("0".to_string())
+    );
+}
+
+struct SaveWith<'a>(&'a World);
+
+impl Serialize for SaveWith<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.save::<(Position, Name), S>(serializer)
+    }
+}
+
+#[test]
+fn migration() {
+    let mut world = World::new();
+    let entity = world.add_entity(PositionV0(1, 2));
+
+    let bytes = to_json::<PositionV0>(&world);
+
+    let mut loaded = World::new();
+    loaded
+        .load::<Position, _>(&mut serde_json::Deserializer::from_slice(&bytes))
+        .unwrap();
+
+    assert_eq!(
+        **loaded.get::<&Position>(entity).unwrap(),
+        Position { x: 1, y: 2, z: 0 }
+    );
+
+    // loading a more recent version fails
+    let bytes = to_json::<Position>(&loaded);
+    assert!(World::new()
+        .load::<PositionV0, _>(&mut serde_json::Deserializer::from_slice(&bytes))
+        .is_err());
+}
+
+#[test]
+fn skip_unknown_key() {
+    let mut world = World::new();
+    let entity = world.add_entity((Position { x: 0, y: 1, z: 2 }, Name("0".to_string())));
+
+    let bytes = to_json::<(Position, Name)>(&world);
+
+    let mut loaded = World::new();
+    loaded
+        .load::<Name, _>(&mut serde_json::Deserializer::from_slice(&bytes))
+        .unwrap();
+
+    assert_eq!(
+        **loaded.get::<&Name>(entity).unwrap(),
+        Name("0".to_string())
+    );
+    assert!(loaded.get::<&Position>(entity).is_err());
+}
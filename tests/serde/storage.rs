@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct Inventory(Vec<u32>);
+impl Component for Inventory {}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct Tick(u32);
+impl Unique for Tick {}
+
+#[test]
+fn sparse_set_snapshot() {
+    let mut world = World::new();
+    world.track_all::<Inventory>();
+
+    let player0 = world.add_entity(Inventory(vec![0, 1]));
+    let player1 = world.add_entity(Inventory(vec![2]));
+    let player2 = world.add_entity(Inventory(vec![]));
+    world.delete_component::<Inventory>(player2);
+
+    let snapshot = serde_json::to_string(&*world.borrow::<View<Inventory>>().unwrap()).unwrap();
+
+    world.get::<&mut Inventory>(player0).unwrap().0.clear();
+    world.remove::<Inventory>(player1);
+
+    let inventories: SparseSet<Inventory> = serde_json::from_str(&snapshot).unwrap();
+    world.insert_storage(inventories);
+
+    assert_eq!(
+        **world.get::<&Inventory>(player0).unwrap(),
+        Inventory(vec![0, 1])
+    );
+    assert_eq!(
+        **world.get::<&Inventory>(player1).unwrap(),
+        Inventory(vec![2])
+    );
+    assert!(world.get::<&Inventory>(player2).is_err());
+
+    // the tracking state came along
+    let inventories = world.borrow::<View<Inventory, track::All>>().unwrap();
+    assert_eq!(inventories.inserted().iter().count(), 2);
+    assert_eq!(
+        inventories.deleted().collect::<Vec<_>>(),
+        vec![(player2, &Inventory(vec![]))]
+    );
+}
+
+#[test]
+fn unique_and_entities() {
+    let mut world = World::new();
+    world.add_unique(Tick(3));
+    let entity0 = world.add_entity(());
+    let entity1 = world.add_entity(());
+    world.delete_entity(entity0);
+
+    let (tick, entities) = {
+        let all_storages = world.all_storages().unwrap();
+        let tick = all_storages
+            .custom_storage::<UniqueStorage<Tick>>()
+            .unwrap();
+        let entities = all_storages.custom_storage::<Entities>().unwrap();
+
+        (
+            bincode::serialize(&*tick).unwrap(),
+            bincode::serialize(&*entities).unwrap(),
+        )
+    };
+
+    let mut loaded = World::new();
+    loaded.insert_storage(bincode::deserialize::<UniqueStorage<Tick>>(&tick).unwrap());
+    loaded.insert_storage(bincode::deserialize::<Entities>(&entities).unwrap());
+
+    assert_eq!(*loaded.borrow::<UniqueView<Tick>>().unwrap(), Tick(3));
+    assert!(!loaded.is_entity_alive(entity0));
+    assert!(loaded.is_entity_alive(entity1));
+
+    let recycled = loaded.add_entity(());
+    assert_eq!(recycled.index(), entity0.index());
+    assert_eq!(recycled.gen(), 1);
+}
@@ -0,0 +1,42 @@
+use shipyard::*;
+
+struct U32(u32);
+impl Component for U32 {}
+impl Unique for U32 {}
+
+#[test]
+fn load_from_json() {
+    let mut registry = SystemRegistry::new();
+    registry
+        .register("double", |mut u32: UniqueViewMut<U32>| u32.0 *= 2)
+        .register("increment", |mut u32: UniqueViewMut<U32>| u32.0 += 1);
+
+    let description: WorkloadDescription = serde_json::from_str(
+        r#"{
+            "name": "game",
+            "systems": [
+                { "name": "double", "after_all": ["increment"] },
+                { "name": "increment", "tags": ["simulation"] }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let world = World::new();
+    world.add_unique(U32(1));
+
+    registry
+        .load_workload(&description)
+        .unwrap()
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_workload("game".to_string()).unwrap();
+
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 4);
+    assert_eq!(
+        serde_json::from_str::<WorkloadDescription>(&serde_json::to_string(&description).unwrap())
+            .unwrap(),
+        description
+    );
+}
@@ -0,0 +1,137 @@
+use shipyard::*;
+
+struct Z(i32);
+impl Component for Z {}
+
+impl SortKey for Z {
+    type Key = i32;
+
+    fn sort_key(&self) -> i32 {
+        self.0
+    }
+}
+
+#[test]
+fn range() {
+    let mut world = World::new();
+
+    let e5 = world.add_entity(Z(5));
+    world.add_sorted::<Z>();
+
+    let e1 = world.add_entity(Z(1));
+    let e3 = world.add_entity(Z(3));
+    let e9 = world.add_entity(Z(9));
+
+    assert_eq!(world.range_by::<Z>(..).unwrap(), vec![e1, e3, e5, e9]);
+    assert_eq!(world.range_by::<Z>(3..9).unwrap(), vec![e3, e5]);
+    assert_eq!(world.range_by::<Z>(3..=9).unwrap(), vec![e3, e5, e9]);
+    assert_eq!(world.range_by::<Z>(..=3).unwrap(), vec![e1, e3]);
+    assert_eq!(world.range_by::<Z>(4..).unwrap(), vec![e5, e9]);
+    assert_eq!(world.range_by::<Z>(5..=5).unwrap(), vec![e5]);
+    assert!(world.range_by::<Z>(6..=8).unwrap().is_empty());
+}
+
+#[test]
+fn keeps_order() {
+    let mut world = World::new();
+    world.add_sorted::<Z>();
+
+    let a = world.add_entity(Z(0));
+    let b = world.add_entity(Z(1));
+    let c = world.add_entity(Z(2));
+
+    assert_eq!(world.range_by::<Z>(..).unwrap(), vec![a, b, c]);
+
+    world.run(|mut zs: ViewMut<Z>| {
+        (&mut zs).get(a).unwrap().0 = 10;
+    });
+    world.add_component(c, Z(-1));
+    world.remove::<Z>(b);
+
+    assert_eq!(world.range_by::<Z>(..).unwrap(), vec![c, a]);
+
+    world.delete_entity(c);
+    world.add_component(b, Z(20));
+
+    assert_eq!(world.range_by::<Z>(..).unwrap(), vec![a, b]);
+
+    world.delete_component::<Z>(a);
+
+    assert_eq!(world.range_by::<Z>(..).unwrap(), vec![b]);
+}
+
+#[test]
+fn missing_order() {
+    let world = World::new();
+
+    assert!(matches!(
+        world.range_by::<Z>(..),
+        Err(error::GetStorage::MissingStorage { .. })
+    ));
+}
+
+#[test]
+fn system() {
+    let mut world = World::new();
+    world.add_sorted::<Z>();
+
+    let a = world.add_entity(Z(0));
+    let b = world.add_entity(Z(1));
+
+    world.run(|mut zs: ViewMut<Z>| {
+        for mut z in (&mut zs).iter() {
+            z.0 = -z.0;
+        }
+    });
+
+    world.run(|zs: SortedView<Z>| {
+        assert_eq!(zs.range(..).collect::<Vec<_>>(), vec![b, a]);
+        assert_eq!(zs.range(..0).rev().collect::<Vec<_>>(), vec![b]);
+        assert_eq!(zs.get(b).unwrap().0, -1);
+    });
+}
+
+#[test]
+fn tracking_left_to_the_user() {
+    let mut world = World::new();
+    world.track_modification::<Z>();
+    world.add_sorted::<Z>();
+
+    let entity = world.add_entity(Z(0));
+    world.run(|mut zs: ViewMut<Z>| {
+        (&mut zs).get(entity).unwrap().0 = 1;
+    });
+
+    assert_eq!(world.range_by::<Z>(1..).unwrap(), vec![entity]);
+    world.run(|zs: View<Z, track::Modification>| {
+        assert!(!zs.is_tracking_insertion());
+        assert_eq!(zs.modified().iter().ids().collect::<Vec<_>>(), vec![entity]);
+    });
+}
+
+#[test]
+fn many_changes_between_ranges() {
+    let mut world = World::new();
+    world.add_sorted::<Z>();
+
+    let entities = world.bulk_add_entity((0..10).map(Z)).collect::<Vec<_>>();
+    assert_eq!(world.range_by::<Z>(..).unwrap(), entities);
+
+    for _ in 0..100 {
+        world.run(|mut zs: ViewMut<Z>| {
+            for mut z in (&mut zs).iter() {
+                z.0 = 9 - z.0;
+            }
+        });
+    }
+
+    world.run(|mut zs: ViewMut<Z>| {
+        for mut z in (&mut zs).iter() {
+            z.0 = -z.0;
+        }
+    });
+
+    let mut reversed = entities;
+    reversed.reverse();
+    assert_eq!(world.range_by::<Z>(..).unwrap(), reversed);
+}
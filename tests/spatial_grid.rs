@@ -0,0 +1,176 @@
+use shipyard::*;
+
+struct Pos(f32, f32);
+impl Component for Pos {}
+
+impl SpatialPosition for Pos {
+    fn position(&self) -> [f32; 2] {
+        [self.0, self.1]
+    }
+}
+
+fn sorted(mut entities: Vec<EntityId>) -> Vec<EntityId> {
+    entities.sort_unstable();
+    entities
+}
+
+#[test]
+fn rect_and_radius() {
+    let mut world = World::new();
+
+    let origin = world.add_entity(Pos(0.0, 0.0));
+    world.add_spatial_grid::<Pos>(4.0);
+
+    let near = world.add_entity(Pos(-3.0, 3.0));
+    let corner = world.add_entity(Pos(5.0, 5.0));
+    let far = world.add_entity(Pos(-100.0, 42.0));
+
+    assert_eq!(
+        sorted(world.query_rect::<Pos>([-5.0, -5.0], [5.0, 5.0]).unwrap()),
+        sorted(vec![origin, near, corner])
+    );
+    assert_eq!(
+        sorted(world.query_radius::<Pos>([0.0, 0.0], 5.0).unwrap()),
+        sorted(vec![origin, near])
+    );
+    assert_eq!(
+        world.query_radius::<Pos>([-100.0, 40.0], 3.0).unwrap(),
+        vec![far]
+    );
+    assert!(world
+        .query_rect::<Pos>([1.0, 1.0], [-1.0, -1.0])
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn modify_and_remove() {
+    let mut world = World::new();
+    world.add_spatial_grid::<Pos>(1.0);
+
+    let moving = world.add_entity(Pos(0.5, 0.5));
+    let removed = world.add_entity(Pos(0.5, 0.5));
+    let deleted = world.add_entity(Pos(0.5, 0.5));
+
+    assert_eq!(
+        sorted(world.query_radius::<Pos>([0.0, 0.0], 1.0).unwrap()),
+        sorted(vec![moving, removed, deleted])
+    );
+
+    world.run(|mut positions: ViewMut<Pos>| {
+        let mut pos = (&mut positions).get(moving).unwrap();
+        pos.0 = 10.5;
+        pos.1 = -10.5;
+    });
+    world.remove::<Pos>(removed);
+    world.delete_entity(deleted);
+
+    assert!(world
+        .query_radius::<Pos>([0.0, 0.0], 1.0)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        world
+            .query_rect::<Pos>([10.0, -11.0], [11.0, -10.0])
+            .unwrap(),
+        vec![moving]
+    );
+
+    world.delete_component::<Pos>(moving);
+
+    assert!(world
+        .query_rect::<Pos>([10.0, -11.0], [11.0, -10.0])
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn large_query() {
+    let mut world = World::new();
+    world.add_spatial_grid::<Pos>(0.001);
+
+    let a = world.add_entity(Pos(-1000.0, 0.0));
+    let b = world.add_entity(Pos(1000.0, 0.0));
+
+    assert_eq!(
+        sorted(
+            world
+                .query_rect::<Pos>([f32::MIN, f32::MIN], [f32::MAX, f32::MAX])
+                .unwrap()
+        ),
+        sorted(vec![a, b])
+    );
+}
+
+#[test]
+fn missing_grid() {
+    let world = World::new();
+
+    assert!(matches!(
+        world.query_radius::<Pos>([0.0, 0.0], 1.0),
+        Err(error::GetStorage::MissingStorage { .. })
+    ));
+}
+
+#[test]
+fn system() {
+    let mut world = World::new();
+    world.add_spatial_grid::<Pos>(1.0);
+
+    let entity = world.add_entity(Pos(0.5, 0.5));
+
+    world.run(|mut positions: ViewMut<Pos>| {
+        for mut pos in (&mut positions).iter() {
+            pos.0 = 20.5;
+        }
+    });
+
+    world.run(|positions: SpatialGridView<Pos>| {
+        assert!(positions.query_radius([0.0, 0.0], 1.0).is_empty());
+        assert_eq!(positions.query_rect([20.0, 0.0], [21.0, 1.0]), vec![entity]);
+        assert_eq!(positions.get(entity).unwrap().0, 20.5);
+    });
+}
+
+#[test]
+fn tracking_left_to_the_user() {
+    let mut world = World::new();
+    world.add_spatial_grid::<Pos>(1.0);
+
+    let entity = world.add_entity(Pos(0.5, 0.5));
+
+    world.run(|positions: View<Pos>| {
+        assert!(!positions.is_tracking_insertion());
+        assert!(!positions.is_tracking_modification());
+        assert!(!positions.is_tracking_removal());
+    });
+    assert_eq!(
+        world.query_rect::<Pos>([0.0, 0.0], [1.0, 1.0]).unwrap(),
+        vec![entity]
+    );
+}
+
+#[test]
+fn many_moves_between_queries() {
+    let mut world = World::new();
+    world.add_spatial_grid::<Pos>(1.0);
+
+    let entity = world.add_entity(Pos(0.5, 0.5));
+    let still = world.add_entity(Pos(0.5, 0.5));
+    assert_eq!(world.query_radius::<Pos>([0.0, 0.0], 1.0).unwrap().len(), 2);
+
+    for i in 0..1000 {
+        world.run(|mut positions: ViewMut<Pos>| {
+            (&mut positions).get(entity).unwrap().0 = i as f32 + 0.5;
+        });
+    }
+
+    assert_eq!(
+        world.query_radius::<Pos>([0.0, 0.0], 1.0).unwrap(),
+        vec![still]
+    );
+    assert_eq!(
+        world.query_radius::<Pos>([999.0, 0.0], 1.0).unwrap(),
+        vec![entity]
+    );
+}
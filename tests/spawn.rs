@@ -213,3 +213,77 @@ fn new_world() {
         EntityId::new_from_index_and_gen(6, 0)
     );
 }
+
+#[test]
+fn local_range() {
+    let mut server = World::new();
+    let mut client = World::new();
+    server
+        .borrow::<EntitiesViewMut>()
+        .unwrap()
+        .set_local_range(0..100);
+    client
+        .borrow::<EntitiesViewMut>()
+        .unwrap()
+        .set_local_range(100..200);
+
+    let server_entity = server.add_entity((U32(0),));
+    let predicted = client.add_entity((U32(1),));
+    assert_eq!(server_entity.index(), 0);
+    assert_eq!(predicted.index(), 100);
+
+    assert_eq!(client.try_spawn(server_entity), Ok(()));
+    assert_eq!(
+        client.try_spawn(server_entity),
+        Err(error::Spawn::AliveEntity(server_entity))
+    );
+    assert_eq!(
+        server.try_spawn(server_entity),
+        Err(error::Spawn::LocalIndex)
+    );
+
+    let next_server_entity = server.add_entity((U32(2),));
+    client.delete_entity(server_entity);
+    assert_eq!(client.try_spawn(next_server_entity), Ok(()));
+
+    // the removed server index isn't reused for client entities
+    client.delete_entity(predicted);
+    let predicted = client.add_entity((U32(3),));
+    assert_eq!(predicted.index(), 100);
+    assert_eq!(predicted.gen(), 1);
+    assert_eq!(client.add_entity((U32(4),)).index(), 101);
+
+    let stale = EntityId::new_from_index_and_gen(0, 0);
+    client.delete_entity(next_server_entity);
+    client.spawn(EntityId::new_from_index_and_gen(0, 3));
+    client.delete_entity(EntityId::new_from_index_and_gen(0, 3));
+    assert_eq!(
+        client.try_spawn(stale),
+        Err(error::Spawn::OutdatedGeneration)
+    );
+}
+
+#[test]
+fn remote_mapping() {
+    let mut server = World::new();
+    let mut client = World::new();
+
+    let remote = server.add_entity((U32(0),));
+
+    let local = client
+        .borrow::<EntitiesViewMut>()
+        .unwrap()
+        .local_id_or_generate(remote);
+
+    let entities = client.borrow::<EntitiesView>().unwrap();
+    assert!(entities.is_alive(local));
+    assert_eq!(entities.local_id(remote), Some(local));
+    assert_eq!(entities.remote_id(local), Some(remote));
+    drop(entities);
+
+    client.delete_entity(local);
+
+    let entities = client.borrow::<EntitiesView>().unwrap();
+    assert_eq!(entities.local_id(remote), None);
+    assert_eq!(entities.remote_id(local), None);
+}
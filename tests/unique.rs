@@ -33,6 +33,32 @@ fn unique_storage() {
     }
 }
 
+#[test]
+fn optional_unique() {
+    fn read(usize: Option<UniqueView<USIZE>>) -> Option<usize> {
+        usize.map(|usize| usize.0)
+    }
+    fn increment(usize: Option<UniqueViewMut<USIZE>>) {
+        if let Some(mut usize) = usize {
+            usize.0 += 1;
+        }
+    }
+
+    let world = World::new();
+
+    assert_eq!(world.run(read), None);
+
+    world.add_workload(|| (increment, increment).into_workload());
+    world.run_default().unwrap();
+
+    world.add_unique(USIZE(0));
+    world.run_default().unwrap();
+    assert_eq!(world.run(read), Some(2));
+
+    let (_, info) = (increment, read).into_workload().build().unwrap();
+    assert_eq!(info.batch_info.len(), 2);
+}
+
 #[test]
 fn not_unique_storage() {
     let world = World::new();
@@ -1,5 +1,6 @@
 #[cfg(feature = "thread_local")]
 mod non_send_sync;
+mod registry;
 
 struct U32(u32);
 impl Component for U32 {}
@@ -74,6 +75,19 @@ fn are_all_uniques_present_in_world() {
         .are_all_uniques_present_in_world(&world)
         .unwrap();
 
+    Workload::new("")
+        .with_system(|_: Option<UniqueView<USIZE>>| {})
+        .are_all_uniques_present_in_world(&world)
+        .unwrap();
+
+    let type_info = {
+        let mut borrow_info = Vec::new();
+        Option::<UniqueView<USIZE>>::borrow_info(&mut borrow_info);
+        borrow_info.remove(0)
+    };
+    assert!(type_info.optional);
+    assert!(type_info.name.starts_with("shipyard::unique::UniqueStorage"));
+
     let type_info = {
         let mut borrow_info = Vec::new();
         UniqueView::<USIZE>::borrow_info(&mut borrow_info);
@@ -217,3 +231,76 @@ fn tracking_enabled() {
 
     world.run_workload(w).unwrap();
 }
+
+#[test]
+fn run_workload_with_tags() {
+    fn double(mut i: UniqueViewMut<U32>) {
+        i.0 *= 2;
+    }
+    fn add_ten(mut i: UniqueViewMut<U32>) {
+        i.0 += 10;
+    }
+    fn add_one(mut i: UniqueViewMut<U32>) {
+        i.0 += 1;
+    }
+    fn count(mut i: UniqueViewMut<USIZE>) {
+        i.0 += 1;
+    }
+
+    let world = World::new();
+
+    world.add_unique(U32(1));
+    world.add_unique(USIZE(0));
+
+    Workload::new("")
+        .with_system(double.tag("simulation"))
+        .with_system(add_ten.tag("presentation"))
+        .with_system(add_one.tag("simulation"))
+        .with_system(count.tag("presentation"))
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_workload_with_tags("", ["simulation"]).unwrap();
+
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 3);
+    assert_eq!(world.borrow::<UniqueView<USIZE>>().unwrap().0, 0);
+
+    world.run_workload_with_tags("", ["presentation"]).unwrap();
+
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 13);
+    assert_eq!(world.borrow::<UniqueView<USIZE>>().unwrap().0, 1);
+}
+
+#[test]
+fn run_workload_filtered() {
+    fn increment(mut i: UniqueViewMut<U32>) {
+        i.0 += 1;
+    }
+    fn count(mut i: UniqueViewMut<USIZE>) {
+        i.0 += 1;
+    }
+
+    let world = World::new();
+
+    world.add_unique(U32(0));
+    world.add_unique(USIZE(0));
+
+    Workload::new("")
+        .with_workload(Workload::new("nested").with_system(increment))
+        .with_system(count)
+        .add_to_world(&world)
+        .unwrap();
+
+    let nested = "nested".as_label();
+    world
+        .run_workload_filtered("", |tags| !tags.contains(&nested))
+        .unwrap();
+
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 0);
+    assert_eq!(world.borrow::<UniqueView<USIZE>>().unwrap().0, 1);
+
+    assert!(matches!(
+        world.run_workload_filtered("missing", |_| true),
+        Err(error::RunWorkload::MissingWorkload)
+    ));
+}
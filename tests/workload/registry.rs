@@ -0,0 +1,132 @@
+use shipyard::*;
+
+struct U32(u32);
+impl Component for U32 {}
+impl Unique for U32 {}
+
+fn double(mut u32: UniqueViewMut<U32>) {
+    u32.0 *= 2;
+}
+
+fn increment(mut u32: UniqueViewMut<U32>) {
+    u32.0 += 1;
+}
+
+fn add_ten(mut u32: UniqueViewMut<U32>) {
+    u32.0 += 10;
+}
+
+fn registry() -> SystemRegistry {
+    let mut registry = SystemRegistry::new();
+
+    registry
+        .register("double", double)
+        .register("increment", increment)
+        .register("add_ten", add_ten)
+        .register_run_if("never", || false);
+
+    registry
+}
+
+fn system(name: &str) -> SystemDescription {
+    SystemDescription {
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn load_workload() {
+    let world = World::new();
+    world.add_unique(U32(1));
+
+    let description = WorkloadDescription {
+        name: "game".to_string(),
+        systems: vec![
+            SystemDescription {
+                before_all: vec!["double".to_string()],
+                ..system("increment")
+            },
+            system("double"),
+            SystemDescription {
+                run_if: Some("never".to_string()),
+                ..system("add_ten")
+            },
+        ],
+    };
+
+    registry()
+        .load_workload(&description)
+        .unwrap()
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_workload("game").unwrap();
+
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 4);
+
+    world.run_workload("game".to_string()).unwrap();
+
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 10);
+}
+
+#[test]
+fn load_workload_tags() {
+    let world = World::new();
+    world.add_unique(U32(1));
+
+    let description = WorkloadDescription {
+        name: "game".to_string(),
+        systems: vec![
+            SystemDescription {
+                tags: vec!["simulation".to_string()],
+                ..system("double")
+            },
+            SystemDescription {
+                tags: vec!["presentation".to_string()],
+                ..system("increment")
+            },
+        ],
+    };
+
+    registry()
+        .load_workload(&description)
+        .unwrap()
+        .add_to_world(&world)
+        .unwrap();
+
+    world
+        .run_workload_with_tags("game", ["simulation"])
+        .unwrap();
+
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 2);
+}
+
+#[test]
+fn missing_system() {
+    let description = WorkloadDescription {
+        name: "game".to_string(),
+        systems: vec![system("double"), system("triple")],
+    };
+
+    assert_eq!(
+        registry().load_workload(&description).err(),
+        Some(error::LoadWorkload::MissingSystem("triple".to_string()))
+    );
+}
+
+#[test]
+fn missing_run_if() {
+    let description = WorkloadDescription {
+        name: "game".to_string(),
+        systems: vec![SystemDescription {
+            run_if: Some("always".to_string()),
+            ..system("double")
+        }],
+    };
+
+    assert_eq!(
+        registry().load_workload(&description).err(),
+        Some(error::LoadWorkload::MissingRunIf("always".to_string()))
+    );
+}